@@ -1,7 +1,18 @@
 import { methods } from "./methods";
+import { getAgentConfig } from "./rpc/config";
+import { installLogFilter, isLogLevel, setLogLevel } from "./rpc/log";
 import { emitEvent } from "./rpc/reply";
 import { createRpcRouter } from "./rpc/router";
 
+// Honor the host-controlled log level for everything the agent prints.
+installLogFilter();
+
+// Apply host-supplied config (if any) before the first message goes out.
+const config = getAgentConfig();
+if (isLogLevel(config.logLevel)) {
+  setLogLevel(config.logLevel);
+}
+
 // Agent boot event (useful for FE to confirm the script is alive).
 emitEvent("agent_loaded");
 
@@ -1,10 +1,19 @@
 import type { MethodHandler } from "../../rpc/types";
+import { getAgentConfig } from "../../rpc/config";
+import { cancelOperation, listOperations as registeredOperations } from "../../rpc/operations";
+import { listWatches as registeredWatches, stopAllWatches as stopRegisteredWatches } from "../../rpc/watches";
+import { getLogLevel as currentLogLevel, isLogLevel, setLogLevel as applyLogLevel } from "../../rpc/log";
 
 // Ping - health check
 export const ping: MethodHandler = () => {
   return { pong: true, timestamp: Date.now() };
 };
 
+// Versions as seen from inside the target
+export const getVersion: MethodHandler = () => {
+  return { frida: Frida.version, runtime: Script.runtime };
+};
+
 // Get process architecture
 export const getArch: MethodHandler = () => {
   return { arch: Process.arch };
@@ -21,3 +30,118 @@ export const getProcessInfo: MethodHandler = () => {
     codeSigningPolicy: Process.codeSigningPolicy,
   };
 };
+
+// Probe which feature families this process supports so the UI can gate them
+export const getCapabilities: MethodHandler = () => {
+  return {
+    arch: Process.arch,
+    platform: Process.platform,
+    pointerSize: Process.pointerSize,
+    runtime: Script.runtime,
+    objc: typeof ObjC !== "undefined" && ObjC.available,
+    java: typeof Java !== "undefined" && Java.available,
+    kernel: Kernel.available,
+    stalker: typeof Stalker !== "undefined",
+    hardwareBreakpoints: Process.arch === "arm64" || Process.arch === "x64" || Process.arch === "ia32",
+  };
+};
+
+// Get the agent log level
+export const getLogLevel: MethodHandler = () => {
+  return { level: currentLogLevel() };
+};
+
+// Set the agent log level (error/warn/info/debug)
+export const setLogLevel: MethodHandler = ({ params }) => {
+  const { level } = (params || {}) as { level?: unknown };
+
+  if (!isLogLevel(level)) {
+    throw new Error("level must be one of error, warn, info, debug");
+  }
+
+  applyLogLevel(level);
+  return { level };
+};
+
+// Get the config injected at load time
+export const getConfig: MethodHandler = () => {
+  return getAgentConfig();
+};
+
+// Force a garbage collection. Both QJS and V8 expose `gc()` in Frida; heap sizes are
+// Frida's own heap (`Frida.heapSize`), which not every build reports.
+export const runGc: MethodHandler = () => {
+  const heapSize = () => (typeof Frida.heapSize === "number" ? Frida.heapSize : null);
+
+  if (typeof gc !== "function") {
+    throw new Error(`gc() is not available in the ${Script.runtime} runtime`);
+  }
+
+  const before = heapSize();
+  gc();
+  const after = heapSize();
+
+  return { runtime: Script.runtime, heapBefore: before, heapAfter: after };
+};
+
+// Evaluate an expression in the agent's global scope (indirect eval) and return it as JSON.
+// Promises are awaited. This runs arbitrary code inside the target; the host gates it.
+export const evaluate: MethodHandler = async ({ params }) => {
+  const { expression } = (params || {}) as { expression?: string };
+
+  if (typeof expression !== "string" || expression.length === 0) {
+    throw new Error("expression parameter is required");
+  }
+
+  const indirectEval = eval;
+  let value: unknown = indirectEval(expression);
+  if (value instanceof Promise) {
+    value = await value;
+  }
+
+  if (value === undefined) {
+    return { type: "undefined", value: null };
+  }
+  if (typeof value === "function") {
+    throw new Error("Result is a function and cannot be serialized");
+  }
+  if (value instanceof NativePointer) {
+    return { type: "pointer", value: value.toString() };
+  }
+
+  let json: string | undefined;
+  try {
+    json = JSON.stringify(value, (_key, v) => (typeof v === "bigint" ? v.toString() : v));
+  } catch (e) {
+    throw new Error(`Result cannot be serialized to JSON (circular or exotic value): ${e}`);
+  }
+  if (json === undefined) {
+    throw new Error(`Result of type ${typeof value} cannot be serialized to JSON`);
+  }
+  return { type: typeof value, value: JSON.parse(json) };
+};
+
+// Long-running operations (scans, allocation tracking) currently registered.
+export const listOperations: MethodHandler = () => {
+  return registeredOperations();
+};
+
+// Cooperative: scans stop at their next checkpoint, trackers detach immediately.
+export const cancelAgentOperation: MethodHandler = ({ params }) => {
+  const { opId } = (params || {}) as { opId?: string };
+
+  if (!opId) {
+    throw new Error("opId parameter is required");
+  }
+
+  return { opId, cancelled: cancelOperation(opId) };
+};
+
+// Active observers, tails and trackers.
+export const listWatches: MethodHandler = () => {
+  return registeredWatches();
+};
+
+export const stopAllWatches: MethodHandler = () => {
+  return { stopped: stopRegisteredWatches() };
+};
@@ -1,7 +1,22 @@
 import type { MethodHandler } from "../rpc/types";
 
 // Core methods
-import { ping, getArch, getProcessInfo } from "./core";
+import {
+  ping,
+  getVersion,
+  getArch,
+  getProcessInfo,
+  getCapabilities,
+  getLogLevel,
+  setLogLevel,
+  getConfig,
+  runGc,
+  evaluate,
+  listOperations,
+  cancelAgentOperation,
+  listWatches,
+  stopAllWatches,
+} from "./core";
 
 // Process methods
 import {
@@ -14,10 +29,12 @@ import {
   findRangeByAddress,
   attachModuleObserver,
   detachModuleObserver,
+  moduleTimelineGet,
   attachThreadObserver,
   detachThreadObserver,
   setExceptionHandler,
   getMainModule,
+  enumerateFds,
 } from "./process";
 
 // Native methods
@@ -26,6 +43,7 @@ import {
   enumerateExports,
   enumerateImports,
   enumerateSymbols,
+  listSymbols,
   findModuleByAddress,
   enumerateSections,
   enumerateDependencies,
@@ -33,6 +51,10 @@ import {
   findSymbolByName,
   loadModule,
   findGlobalExportByName,
+  addressToOffset,
+  offsetToAddress,
+  describeAddress,
+  moduleDependencies,
 } from "./native";
 
 // Native function methods
@@ -45,6 +67,7 @@ import {
   getExportByName,
   resolveSymbol,
   getDebugSymbol,
+  symbolicate,
   getFunctionByAddress,
   createNativeCallback,
   deleteNativeCallback,
@@ -74,6 +97,7 @@ import {
 // Memory methods
 import {
   readMemory,
+  readMemoryRaw,
   writeMemory,
   searchMemory,
   enumerateRanges,
@@ -91,6 +115,12 @@ import {
   memoryWatchRemove,
   memoryWatchList,
   memoryWatchClear,
+  findPointers,
+  memorySummary,
+  readStruct,
+  allocationTrackStart,
+  allocationTrackStop,
+  allocationSnapshot,
 } from "./memory";
 
 // Advanced memory methods
@@ -116,6 +146,7 @@ import {
 // Thread methods
 import {
   enumerateThreads,
+  threadContext,
   getBacktrace,
   getCurrentThreadId,
   setHardwareBreakpoint,
@@ -123,6 +154,8 @@ import {
   setHardwareWatchpoint,
   unsetHardwareWatchpoint,
   threadSleep,
+  threadSuspendAll,
+  threadResumeAll,
 } from "./thread";
 
 // Interceptor methods
@@ -131,9 +164,16 @@ import {
   interceptorDetach,
   interceptorDetachAll,
   interceptorList,
+  interceptorSetFormat,
+  interceptorHookModule,
+  interceptorHookVtable,
+  interceptorUnhookModule,
   interceptorReplace,
   interceptorRevert,
   interceptorFlush,
+  interceptorReplaceFunction,
+  interceptorRestoreFunction,
+  interceptorSetEnabled,
 } from "./interceptor";
 
 // ObjC methods
@@ -146,6 +186,7 @@ import {
   objcEnumerateProtocols,
   objcChoose,
   objcScheduleOnMainThread,
+  objcSearchMethods,
 } from "./objc";
 
 // Java methods
@@ -159,6 +200,7 @@ import {
   javaEnumerateClassLoaders,
   javaPerform,
   javaScheduleOnMainThread,
+  javaSearchMethods,
 } from "./java";
 
 // IO methods
@@ -177,6 +219,7 @@ import {
   sqliteQuery,
   sqliteDumpSchema,
 } from "./io";
+import { fileTailStart, fileTailStop } from "./io/tail";
 
 // Advanced methods (Cloak, Script, Kernel)
 import {
@@ -226,8 +269,19 @@ import {
 export const methods: Record<string, MethodHandler> = {
   // Core
   ping,
+  get_version: getVersion,
   get_arch: getArch,
   get_process_info: getProcessInfo,
+  get_capabilities: getCapabilities,
+  get_log_level: getLogLevel,
+  set_log_level: setLogLevel,
+  get_config: getConfig,
+  gc: runGc,
+  eval: evaluate,
+  list_operations: listOperations,
+  cancel_operation: cancelAgentOperation,
+  list_watches: listWatches,
+  stop_all_watches: stopAllWatches,
 
   // Process
   get_current_dir: getCurrentDir,
@@ -239,9 +293,11 @@ export const methods: Record<string, MethodHandler> = {
   find_range_by_address: findRangeByAddress,
   attach_module_observer: attachModuleObserver,
   detach_module_observer: detachModuleObserver,
+  module_timeline: moduleTimelineGet,
   attach_thread_observer: attachThreadObserver,
   detach_thread_observer: detachThreadObserver,
   set_exception_handler: setExceptionHandler,
+  enumerate_fds: enumerateFds,
   get_main_module: getMainModule,
 
   // Native - Module enumeration
@@ -249,6 +305,7 @@ export const methods: Record<string, MethodHandler> = {
   enumerate_exports: enumerateExports,
   enumerate_imports: enumerateImports,
   enumerate_symbols: enumerateSymbols,
+  list_symbols: listSymbols,
   find_module_by_address: findModuleByAddress,
   enumerate_sections: enumerateSections,
   enumerate_dependencies: enumerateDependencies,
@@ -256,6 +313,10 @@ export const methods: Record<string, MethodHandler> = {
   find_symbol_by_name: findSymbolByName,
   load_module: loadModule,
   find_global_export_by_name: findGlobalExportByName,
+  address_to_offset: addressToOffset,
+  offset_to_address: offsetToAddress,
+  describe_address: describeAddress,
+  module_dependencies: moduleDependencies,
 
   // Native - Functions
   create_native_function: createNativeFunction,
@@ -266,6 +327,7 @@ export const methods: Record<string, MethodHandler> = {
   get_export_by_name: getExportByName,
   resolve_symbol: resolveSymbol,
   get_debug_symbol: getDebugSymbol,
+  symbolicate,
   get_function_by_address: getFunctionByAddress,
 
   // Native - Callbacks
@@ -297,8 +359,12 @@ export const methods: Record<string, MethodHandler> = {
 
   // Memory - Basic
   read_memory: readMemory,
+  read_memory_raw: readMemoryRaw,
   write_memory: writeMemory,
   search_memory: searchMemory,
+  find_pointers: findPointers,
+  memory_summary: memorySummary,
+  read_struct: readStruct,
   enumerate_ranges: enumerateRanges,
   allocate_memory: allocateMemory,
   memory_scan_async: memoryScanAsync,
@@ -318,6 +384,9 @@ export const methods: Record<string, MethodHandler> = {
   memory_watch_remove: memoryWatchRemove,
   memory_watch_list: memoryWatchList,
   memory_watch_clear: memoryWatchClear,
+  allocation_track_start: allocationTrackStart,
+  allocation_track_stop: allocationTrackStop,
+  allocation_snapshot: allocationSnapshot,
 
   // Memory - Advanced
   memory_protect: memoryProtect,
@@ -339,6 +408,7 @@ export const methods: Record<string, MethodHandler> = {
 
   // Thread
   enumerate_threads: enumerateThreads,
+  thread_context: threadContext,
   get_backtrace: getBacktrace,
   get_current_thread_id: getCurrentThreadId,
   set_hardware_breakpoint: setHardwareBreakpoint,
@@ -346,15 +416,24 @@ export const methods: Record<string, MethodHandler> = {
   set_hardware_watchpoint: setHardwareWatchpoint,
   unset_hardware_watchpoint: unsetHardwareWatchpoint,
   thread_sleep: threadSleep,
+  thread_suspend_all: threadSuspendAll,
+  thread_resume_all: threadResumeAll,
 
   // Interceptor
   interceptor_attach: interceptorAttach,
   interceptor_detach: interceptorDetach,
   interceptor_detach_all: interceptorDetachAll,
   interceptor_list: interceptorList,
+  interceptor_set_format: interceptorSetFormat,
+  interceptor_hook_module: interceptorHookModule,
+  interceptor_hook_vtable: interceptorHookVtable,
+  interceptor_unhook_module: interceptorUnhookModule,
   interceptor_replace: interceptorReplace,
   interceptor_revert: interceptorRevert,
   interceptor_flush: interceptorFlush,
+  interceptor_replace_function: interceptorReplaceFunction,
+  interceptor_restore_function: interceptorRestoreFunction,
+  interceptor_set_enabled: interceptorSetEnabled,
 
   // Stalker
   stalker_follow: stalkerFollow,
@@ -384,6 +463,7 @@ export const methods: Record<string, MethodHandler> = {
   objc_enumerate_protocols: objcEnumerateProtocols,
   objc_choose: objcChoose,
   objc_schedule_on_main_thread: objcScheduleOnMainThread,
+  objc_search_methods: objcSearchMethods,
 
   // Java
   java_available: javaAvailable,
@@ -395,12 +475,15 @@ export const methods: Record<string, MethodHandler> = {
   java_enumerate_class_loaders: javaEnumerateClassLoaders,
   java_perform: javaPerform,
   java_schedule_on_main_thread: javaScheduleOnMainThread,
+  java_search_methods: javaSearchMethods,
 
   // IO - File
   file_read_all_text: fileReadAllText,
   file_read_all_bytes: fileReadAllBytes,
   file_write_all_text: fileWriteAllText,
   file_write_all_bytes: fileWriteAllBytes,
+  file_tail_start: fileTailStart,
+  file_tail_stop: fileTailStop,
 
   // IO - Socket
   socket_connect: socketConnect,
@@ -1,17 +1,199 @@
+import { emitEvent } from "../../rpc/reply";
 import type { MethodHandler } from "../../rpc/types";
 
+type ActiveInterceptor = {
+  listener: InvocationListener;
+  target: string;
+  address: string;
+  // Muted hooks stay installed but skip their callbacks entirely.
+  enabled: boolean;
+};
+
 // Store for active interceptors
-const activeInterceptors: Map<string, InvocationListener> = new Map();
+const activeInterceptors: Map<string, ActiveInterceptor> = new Map();
+
+const isMuted = (id: string) => activeInterceptors.get(id)?.enabled === false;
+
+// "verbose" sends named fields; "compact" sends a positional `c` array and drops `target`
+// (look it up via interceptor_list):
+//   interceptor_enter: c = [threadId, pc, sp, arg0, arg1, arg2, arg3] (argCount args if set)
+//   interceptor_leave: c = [threadId, retval]
+type HookFormat = "verbose" | "compact";
+let hookFormat: HookFormat = "verbose";
+
+export const interceptorSetFormat: MethodHandler = ({ params }) => {
+  const { format } = (params || {}) as { format?: HookFormat };
+
+  if (format !== "verbose" && format !== "compact") {
+    throw new Error('format must be "verbose" or "compact"');
+  }
+
+  hookFormat = format;
+  return { format };
+};
+
+type HookCondition = {
+  argIndex: number;
+  op: "eq" | "ne" | "gt" | "lt" | "contains";
+  value: number | string;
+};
 
 type AttachParams = {
   target: string; // Address or symbol name
   onEnter?: boolean;
   onLeave?: boolean;
+  condition?: HookCondition;
+  paired?: boolean;
+  capture?: number[]; // Arg indices read as UTF-8 strings at entry (paired only)
+};
+
+// Paired hooks stash entry state on the per-invocation context and emit one `hook_call`
+// event on leave instead of separate enter/leave events, so every return arrives next to the
+// arguments it belongs to. Captured strings are read at entry, before the callee can change
+// or free the buffers. Paired events always use named fields, whatever the hook format.
+type PairedOptions = {
+  capture: number[];
 };
 
+function readCapture(arg: NativePointer): string | null {
+  try {
+    return arg.readUtf8String();
+  } catch {
+    return null;
+  }
+}
+
+// Numeric comparisons treat the argument as an unsigned pointer-sized integer;
+// `contains` reads it as a UTF-8 C string. Unreadable strings never match.
+function matchesCondition(args: InvocationArguments, condition: HookCondition): boolean {
+  const arg = args[condition.argIndex];
+  if (condition.op === "contains") {
+    try {
+      return arg.readUtf8String()?.includes(String(condition.value)) ?? false;
+    } catch {
+      return false;
+    }
+  }
+
+  const actual = uint64(arg.toString());
+  const expected = uint64(String(condition.value));
+  switch (condition.op) {
+    case "eq":
+      return actual.equals(expected);
+    case "ne":
+      return !actual.equals(expected);
+    case "gt":
+      return actual.compare(expected) > 0;
+    case "lt":
+      return actual.compare(expected) < 0;
+  }
+}
+
+const DEFAULT_LOGGED_ARGS = 4;
+
+function argValues(args: InvocationArguments, count: number): (string | null)[] {
+  return Array.from({ length: count }, (_, i) => args[i]?.toString() || null);
+}
+
+// Install a logging hook and register it under a fresh id. `argCount` is how many arguments
+// each event carries (4 unless the caller knows the signature).
+function installLogger(
+  target: string,
+  targetPtr: NativePointer,
+  onEnter: boolean,
+  onLeave: boolean,
+  condition?: HookCondition,
+  paired?: PairedOptions,
+  argCount = DEFAULT_LOGGED_ARGS,
+): string {
+  const id = `interceptor_${target}_${Date.now()}`;
+
+  if (paired) {
+    const { capture } = paired;
+    const listener = Interceptor.attach(targetPtr, {
+      onEnter(args) {
+        if (isMuted(id)) return;
+        if (condition && !matchesCondition(args, condition)) return;
+        this.entry = {
+          args: argValues(args, argCount),
+          captured: capture.map((i) => ({ index: i, value: readCapture(args[i]) })),
+          context: { pc: this.context.pc.toString(), sp: this.context.sp.toString() },
+          time: Date.now(),
+        };
+      },
+      onLeave(retval) {
+        const entry = this.entry;
+        if (!entry || isMuted(id)) return;
+        emitEvent("hook_call", {
+          hookId: id,
+          target,
+          threadId: this.threadId,
+          ...entry,
+          retval: retval.toString(),
+          durationMs: Date.now() - entry.time,
+        });
+      },
+    });
+    activeInterceptors.set(id, { listener, target, address: targetPtr.toString(), enabled: true });
+    return id;
+  }
+
+  // With a condition, onEnter must run to evaluate it; unmatched calls skip onLeave too.
+  const listener = Interceptor.attach(targetPtr, {
+    onEnter: onEnter || condition ? function(args) {
+      if (isMuted(id)) return;
+      if (condition) {
+        this.matched = matchesCondition(args, condition);
+        if (!this.matched || !onEnter) return;
+      }
+      const values = argValues(args, argCount);
+      const pc = this.context.pc.toString();
+      const sp = this.context.sp.toString();
+      if (hookFormat === "compact") {
+        send({ type: "carf:event", event: "interceptor_enter", id, c: [this.threadId, pc, sp, ...values] });
+        return;
+      }
+      send({
+        type: "carf:event",
+        event: "interceptor_enter",
+        id,
+        target,
+        threadId: this.threadId,
+        context: { pc, sp },
+        args: values,
+      });
+    } : undefined,
+    onLeave: onLeave ? function(retval) {
+      if (isMuted(id) || (condition && !this.matched)) return;
+      if (hookFormat === "compact") {
+        send({ type: "carf:event", event: "interceptor_leave", id, c: [this.threadId, retval.toString()] });
+        return;
+      }
+      send({
+        type: "carf:event",
+        event: "interceptor_leave",
+        id,
+        target,
+        threadId: this.threadId,
+        retval: retval.toString(),
+      });
+    } : undefined,
+  });
+
+  activeInterceptors.set(id, {
+    listener,
+    target,
+    address: targetPtr.toString(),
+    enabled: true,
+  });
+
+  return id;
+}
+
 // Attach interceptor to a function
 export const interceptorAttach: MethodHandler = ({ params }) => {
-  const { target, onEnter = true, onLeave = true } = (params || {}) as AttachParams;
+  const { target, onEnter = true, onLeave = true, condition, paired = false, capture = [] } =
+    (params || {}) as AttachParams;
 
   if (!target) {
     throw new Error("target parameter is required");
@@ -26,48 +208,153 @@ export const interceptorAttach: MethodHandler = ({ params }) => {
       throw new Error(`Target '${target}' not found`);
     }
 
-    const id = `interceptor_${target}_${Date.now()}`;
-    
-    const listener = Interceptor.attach(targetPtr, {
-      onEnter: onEnter ? function(args) {
-        send({
-          type: "carf:event",
-          event: "interceptor_enter",
-          id,
-          target,
-          threadId: this.threadId,
-          context: {
-            pc: this.context.pc.toString(),
-            sp: this.context.sp.toString(),
-          },
-          args: [
-            args[0]?.toString() || null,
-            args[1]?.toString() || null,
-            args[2]?.toString() || null,
-            args[3]?.toString() || null,
-          ],
-        });
-      } : undefined,
-      onLeave: onLeave ? function(retval) {
-        send({
-          type: "carf:event",
-          event: "interceptor_leave",
-          id,
-          target,
-          threadId: this.threadId,
-          retval: retval.toString(),
-        });
-      } : undefined,
-    });
+    const id = installLogger(target, targetPtr, onEnter, onLeave, condition, paired ? { capture } : undefined);
 
-    activeInterceptors.set(id, listener);
-
-    return { id, target: targetPtr.toString() };
+    return { id, target: targetPtr.toString(), paired };
   } catch (e) {
     throw new Error(`Failed to attach interceptor: ${e}`);
   }
 };
 
+type HookVtableParams = {
+  address: string;
+  index: number;
+  argCount?: number;
+  // `address` already points at the vtable rather than at an object whose first word does.
+  vtable?: boolean;
+};
+
+// Hook the virtual function in slot `index`. Assumes the Itanium/MSVC layout with the vtable
+// pointer in the object's first word; classes with multiple bases have further vtable
+// pointers at other offsets, which callers can pass directly with `vtable`.
+export const interceptorHookVtable: MethodHandler = ({ params }) => {
+  const { address, index, argCount = DEFAULT_LOGGED_ARGS, vtable = false } = (params || {}) as HookVtableParams;
+
+  if (!address) {
+    throw new Error("address parameter is required");
+  }
+  if (!Number.isInteger(index) || index < 0) {
+    throw new Error("index must be a non-negative integer");
+  }
+
+  let vtablePtr: NativePointer;
+  let functionPtr: NativePointer;
+  try {
+    vtablePtr = vtable ? new NativePointer(address) : new NativePointer(address).readPointer();
+  } catch (e) {
+    throw new Error(`Cannot read vtable pointer of object at ${address}: ${e}`);
+  }
+  const slot = vtablePtr.add(index * Process.pointerSize);
+  try {
+    functionPtr = slot.readPointer();
+  } catch (e) {
+    throw new Error(`Vtable slot ${index} at ${slot} is unreadable: ${e}`);
+  }
+
+  const range = Process.findRangeByAddress(functionPtr);
+  if (!range || !range.protection.includes("x")) {
+    throw new Error(`Vtable slot ${index} at ${slot} holds ${functionPtr}, which is not executable code`);
+  }
+
+  const target = `vtable ${vtablePtr}[${index}]`;
+  const id = installLogger(target, functionPtr, true, true, undefined, undefined, argCount);
+  const symbol = DebugSymbol.fromAddress(functionPtr);
+
+  return {
+    id,
+    vtable: vtablePtr.toString(),
+    slot: slot.toString(),
+    function: functionPtr.toString(),
+    symbol: symbol.name ?? null,
+  };
+};
+
+// Hook ids installed by interceptor_hook_module, per module name.
+const moduleHooks: Map<string, string[]> = new Map();
+
+type HookModuleParams = {
+  module: string;
+  include?: string; // Regex over export names
+  exclude?: string;
+  limit?: number;
+  dryRun?: boolean;
+};
+
+// Install onEnter-only logging hooks on every matching function export of a module. Hooking a
+// hot library this way can slow the target to a crawl; start narrow with `include`.
+export const interceptorHookModule: MethodHandler = ({ params }) => {
+  const { module: moduleName, include, exclude, limit = 500, dryRun = false } =
+    (params || {}) as HookModuleParams;
+
+  if (!moduleName) {
+    throw new Error("module parameter is required");
+  }
+
+  const mod = Process.findModuleByName(moduleName);
+  if (!mod) {
+    throw new Error(`Module '${moduleName}' not found`);
+  }
+  if (!dryRun && moduleHooks.has(mod.name)) {
+    throw new Error(`Module '${mod.name}' is already hooked`);
+  }
+
+  const includeRe = include ? new RegExp(include) : null;
+  const excludeRe = exclude ? new RegExp(exclude) : null;
+  const matches = mod
+    .enumerateExports()
+    .filter((exp) => exp.type === "function")
+    .filter((exp) => (!includeRe || includeRe.test(exp.name)) && !excludeRe?.test(exp.name));
+
+  const selected = matches.slice(0, limit);
+  const exports = selected.map((exp) => ({ name: exp.name, address: exp.address.toString() }));
+  const result = { module: mod.name, exports, truncated: matches.length > selected.length };
+
+  if (dryRun) {
+    return { ...result, hookIds: [], failed: [] };
+  }
+
+  const hookIds: string[] = [];
+  const failed: { name: string; error: string }[] = [];
+  for (const exp of selected) {
+    try {
+      hookIds.push(installLogger(`${mod.name}!${exp.name}`, exp.address, true, false));
+    } catch (e) {
+      // Some exports are too short to hook or live in non-executable thunks.
+      failed.push({ name: exp.name, error: String(e) });
+    }
+  }
+  moduleHooks.set(mod.name, hookIds);
+
+  return { ...result, hookIds, failed };
+};
+
+// Remove every hook installed by interceptor_hook_module for a module
+export const interceptorUnhookModule: MethodHandler = ({ params }) => {
+  const { module: moduleName } = (params || {}) as { module?: string };
+
+  if (!moduleName) {
+    throw new Error("module parameter is required");
+  }
+
+  const name = Process.findModuleByName(moduleName)?.name ?? moduleName;
+  const hookIds = moduleHooks.get(name);
+  if (!hookIds) {
+    throw new Error(`Module '${moduleName}' is not hooked`);
+  }
+
+  let detached = 0;
+  for (const id of hookIds) {
+    const entry = activeInterceptors.get(id);
+    if (!entry) continue;
+    entry.listener.detach();
+    activeInterceptors.delete(id);
+    detached++;
+  }
+  moduleHooks.delete(name);
+
+  return { module: name, detached };
+};
+
 // Detach interceptor
 export const interceptorDetach: MethodHandler = ({ params }) => {
   const { id } = (params || {}) as { id?: string };
@@ -76,13 +363,13 @@ export const interceptorDetach: MethodHandler = ({ params }) => {
     throw new Error("id parameter is required");
   }
 
-  const listener = activeInterceptors.get(id);
-  if (!listener) {
+  const entry = activeInterceptors.get(id);
+  if (!entry) {
     throw new Error(`Interceptor '${id}' not found`);
   }
 
   try {
-    listener.detach();
+    entry.listener.detach();
     activeInterceptors.delete(id);
     return { success: true, id };
   } catch (e) {
@@ -96,6 +383,7 @@ export const interceptorDetachAll: MethodHandler = () => {
     Interceptor.detachAll();
     const count = activeInterceptors.size;
     activeInterceptors.clear();
+    moduleHooks.clear();
     return { success: true, detached: count };
   } catch (e) {
     throw new Error(`Failed to detach all interceptors: ${e}`);
@@ -104,7 +392,29 @@ export const interceptorDetachAll: MethodHandler = () => {
 
 // List active interceptors
 export const interceptorList: MethodHandler = () => {
-  return Array.from(activeInterceptors.keys());
+  return Array.from(activeInterceptors, ([id, entry]) => ({
+    id,
+    target: entry.target,
+    address: entry.address,
+    enabled: entry.enabled,
+  }));
+};
+
+// Mute or unmute a hook without detaching it
+export const interceptorSetEnabled: MethodHandler = ({ params }) => {
+  const { id, enabled } = (params || {}) as { id?: string; enabled?: boolean };
+
+  if (!id || typeof enabled !== "boolean") {
+    throw new Error("id and enabled parameters are required");
+  }
+
+  const entry = activeInterceptors.get(id);
+  if (!entry) {
+    throw new Error(`Interceptor '${id}' not found`);
+  }
+
+  entry.enabled = enabled;
+  return { id, enabled };
 };
 
 type ReplaceParams = {
@@ -162,6 +472,114 @@ export const interceptorRevert: MethodHandler = ({ params }) => {
   }
 };
 
+// Replacements installed via interceptor_replace_function. Callbacks must stay referenced
+// while installed. Replacing a function can easily destabilize the target: a wrong signature
+// or a throwing body corrupts the caller's state or crashes the process.
+type Replacement = {
+  target: string;
+  callback: NativeCallback<any, any>;
+};
+
+const activeReplacements: Map<string, Replacement> = new Map();
+let nextReplacementId = 1;
+
+type ReplaceFunctionParams = {
+  target: string;
+  returnType?: NativeCallbackReturnType;
+  argTypes?: NativeCallbackArgumentType[];
+  // Function body run as `(args, original) => { ... }`; `original` calls the real function.
+  body?: string;
+  returnValue?: number | string | null;
+};
+
+function resolveTarget(target: string): NativePointer {
+  const targetPtr = target.startsWith("0x")
+    ? new NativePointer(target)
+    : Module.findGlobalExportByName(target);
+
+  if (!targetPtr) {
+    throw new Error(`Target '${target}' not found`);
+  }
+  return targetPtr;
+}
+
+// Replace a function with a JS body or a constant return value
+export const interceptorReplaceFunction: MethodHandler = ({ params }) => {
+  const {
+    target,
+    returnType = "int",
+    argTypes = [],
+    body,
+    returnValue,
+  } = (params || {}) as ReplaceFunctionParams;
+
+  if (!target) {
+    throw new Error("target parameter is required");
+  }
+  if ((body === undefined) === (returnValue === undefined)) {
+    throw new Error("exactly one of body and returnValue is required");
+  }
+
+  const targetPtr = resolveTarget(target);
+  const key = targetPtr.toString();
+  for (const replacement of activeReplacements.values()) {
+    if (replacement.target === key) {
+      throw new Error(`Target '${target}' is already replaced`);
+    }
+  }
+
+  // Compiled before anything is installed, so a syntax error leaves the target untouched.
+  let implementation: (args: any[], original: NativeFunction<any, any>) => any;
+  if (body !== undefined) {
+    try {
+      implementation = new Function("args", "original", body) as typeof implementation;
+    } catch (e) {
+      throw new Error(`body does not compile: ${e}`);
+    }
+  } else {
+    const value = returnType === "pointer" ? new NativePointer(String(returnValue ?? 0)) : returnValue;
+    implementation = () => (returnType === "void" ? undefined : value);
+  }
+
+  // Calling the original through a NativeFunction bypasses the replacement.
+  const original = new NativeFunction(targetPtr, returnType, argTypes);
+  const callback = new NativeCallback(
+    (...args: any[]) => implementation(args, original),
+    returnType,
+    argTypes,
+  );
+
+  try {
+    Interceptor.replace(targetPtr, callback);
+  } catch (e) {
+    throw new Error(`Failed to replace function: ${e}`);
+  }
+
+  const id = `replace-${nextReplacementId++}`;
+  activeReplacements.set(id, { target: key, callback });
+  return { id, target: key };
+};
+
+// Revert a replacement installed by interceptor_replace_function
+export const interceptorRestoreFunction: MethodHandler = ({ params }) => {
+  const { id } = (params || {}) as { id?: string };
+
+  if (!id) {
+    throw new Error("id parameter is required");
+  }
+
+  const replacement = activeReplacements.get(id);
+  if (!replacement) {
+    throw new Error(`Replacement '${id}' not found`);
+  }
+
+  Interceptor.revert(new NativePointer(replacement.target));
+  Interceptor.flush();
+  activeReplacements.delete(id);
+
+  return { id, target: replacement.target };
+};
+
 // Flush pending interceptor changes
 export const interceptorFlush: MethodHandler = () => {
   try {
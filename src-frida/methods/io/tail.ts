@@ -0,0 +1,101 @@
+import type { MethodHandler } from "../../rpc/types";
+import { emitEvent } from "../../rpc/reply";
+import { registerWatch, unregisterWatch } from "../../rpc/watches";
+
+// Tail a file in the target's filesystem and stream new lines as `file_tail` events.
+
+type TailItem = {
+  id: string;
+  path: string;
+  file: File;
+  partial: string;
+  timer: number;
+};
+
+// Bounds so a runaway log can't flood the host.
+const MAX_CHUNK_BYTES = 64 * 1024;
+const MAX_LINES_PER_TICK = 200;
+const MAX_PARTIAL_CHARS = 16 * 1024;
+
+const tails = new Map<string, TailItem>();
+
+function createId(prefix: string) {
+  return `${prefix}-${Date.now()}-${Math.random().toString(36).slice(2, 10)}`;
+}
+
+function stopTail(id: string) {
+  const item = tails.get(id);
+  if (!item) return false;
+  clearInterval(item.timer);
+  try {
+    item.file.close();
+  } catch {
+    // Already closed
+  }
+  tails.delete(id);
+  unregisterWatch(id);
+  return true;
+}
+
+function pollTail(item: TailItem) {
+  let chunk: string;
+  try {
+    chunk = item.file.readText(MAX_CHUNK_BYTES);
+  } catch (e) {
+    emitEvent("file_tail_error", { tailId: item.id, path: item.path, error: String(e) });
+    stopTail(item.id);
+    return;
+  }
+  if (chunk.length === 0) return;
+
+  const parts = (item.partial + chunk).split("\n");
+  item.partial = parts.pop() ?? "";
+  if (item.partial.length > MAX_PARTIAL_CHARS) {
+    parts.push(item.partial);
+    item.partial = "";
+  }
+
+  const lines = parts.slice(0, MAX_LINES_PER_TICK);
+  const dropped = parts.length - lines.length;
+  if (lines.length === 0) return;
+
+  emitEvent("file_tail", { tailId: item.id, path: item.path, lines, dropped });
+}
+
+export const fileTailStart: MethodHandler = ({ params }) => {
+  const { path, intervalMs = 250 } = (params || {}) as { path?: string; intervalMs?: number };
+
+  if (!path) {
+    throw new Error("path parameter is required");
+  }
+
+  let file: File;
+  try {
+    file = new File(path, "r");
+    file.seek(0, File.SEEK_END);
+  } catch (e) {
+    throw new Error(`Failed to open file for tailing: ${e}`);
+  }
+
+  const id = createId("tail");
+  const item: TailItem = { id, path, file, partial: "", timer: 0 };
+  item.timer = setInterval(() => pollTail(item), Math.max(50, intervalMs));
+  tails.set(id, item);
+  registerWatch(id, "file_tail", path, () => stopTail(id));
+
+  return { tailId: id, path };
+};
+
+export const fileTailStop: MethodHandler = ({ params }) => {
+  const { tailId } = (params || {}) as { tailId?: string };
+
+  if (!tailId) {
+    throw new Error("tailId parameter is required");
+  }
+
+  if (!stopTail(tailId)) {
+    throw new Error(`Tail '${tailId}' not found`);
+  }
+
+  return { success: true, tailId };
+};
@@ -209,3 +209,53 @@ export const javaScheduleOnMainThread: MethodHandler = () => {
     });
   });
 };
+
+type MethodSearchParams = {
+  pattern?: string;
+  syntax?: "glob" | "regex";
+  limit?: number;
+};
+
+// Search methods across loaded classes. Glob patterns are `class!method` (e.g.
+// `*crypto*!*encrypt*`) and use Frida's native matcher; regex patterns are tested against
+// `class!method` after enumerating everything, so they're slower on big apps.
+export const javaSearchMethods: MethodHandler = ({ params }) => {
+  if (!Java.available) {
+    throw new Error("Java runtime not available");
+  }
+
+  const { pattern, syntax = "glob", limit = 200 } = (params || {}) as MethodSearchParams;
+
+  if (!pattern) {
+    throw new Error("pattern parameter is required");
+  }
+
+  const regex = syntax === "regex" ? new RegExp(pattern, "i") : null;
+  const query = regex ? "*!*" : pattern.includes("!") ? pattern : `${pattern}!*`;
+
+  return new Promise((resolve, reject) => {
+    try {
+      Java.perform(() => {
+        const results: { class: string; method: string }[] = [];
+        let truncated = false;
+
+        outer: for (const group of Java.enumerateMethods(`${query}/i`)) {
+          for (const cls of group.classes) {
+            for (const method of cls.methods) {
+              if (regex && !regex.test(`${cls.name}!${method}`)) continue;
+              if (results.length >= limit) {
+                truncated = true;
+                break outer;
+              }
+              results.push({ class: cls.name, method });
+            }
+          }
+        }
+
+        resolve({ results, count: results.length, truncated });
+      });
+    } catch (e) {
+      reject(new Error(`Failed to search methods: ${e}`));
+    }
+  });
+};
@@ -0,0 +1,235 @@
+import { beginOperation, endOperation, type Operation } from "../../rpc/operations";
+import { emitEvent } from "../../rpc/reply";
+import { registerWatch, unregisterWatch } from "../../rpc/watches";
+import type { MethodHandler } from "../../rpc/types";
+
+// Allocation tracking for leak hunting. Every malloc/realloc/free in the target goes through a
+// JS hook while this is on, which typically slows allocation-heavy code by an order of
+// magnitude. Calls below `minSize` return from onEnter without recording anything, so a
+// reasonable threshold is the main lever for keeping the overhead tolerable. Nothing is sent
+// per call: outstanding allocations are aggregated by call site and summarised on a timer.
+
+type Allocation = {
+  size: number;
+  site: string;
+  time: number;
+};
+
+type SiteStats = {
+  count: number;
+  bytes: number;
+};
+
+type Tracker = {
+  minSize: number;
+  intervalMs: number;
+  listeners: InvocationListener[];
+  timer: number;
+  op: Operation | null;
+  outstanding: Map<string, Allocation>;
+  sites: Map<string, SiteStats>;
+  totalAllocs: number;
+  totalFrees: number;
+  startedAt: number;
+};
+
+const DEFAULT_SUMMARY_INTERVAL_MS = 2000;
+const MIN_SUMMARY_INTERVAL_MS = 250;
+const SUMMARY_TOP_SITES = 10;
+const DEFAULT_SNAPSHOT_LIMIT = 1000;
+
+let tracker: Tracker | null = null;
+
+// `size_t` is 64-bit on 64-bit targets, so `toUInt32()` would wrap sizes of 4 GiB and up.
+// Doubles are exact well past any real allocation size.
+function readSize(arg: NativePointer): number {
+  return uint64(arg.toString(10)).toNumber();
+}
+
+function record(t: Tracker, address: NativePointer, size: number, site: NativePointer) {
+  if (address.isNull()) return;
+  const key = address.toString();
+  const siteKey = site.toString();
+  t.outstanding.set(key, { size, site: siteKey, time: Date.now() });
+  const stats = t.sites.get(siteKey);
+  if (stats) {
+    stats.count++;
+    stats.bytes += size;
+  } else {
+    t.sites.set(siteKey, { count: 1, bytes: size });
+  }
+  t.totalAllocs++;
+}
+
+function release(t: Tracker, address: NativePointer) {
+  if (address.isNull()) return;
+  const key = address.toString();
+  const alloc = t.outstanding.get(key);
+  if (!alloc) return;
+  t.outstanding.delete(key);
+  const stats = t.sites.get(alloc.site);
+  if (stats) {
+    stats.count--;
+    stats.bytes -= alloc.size;
+    if (stats.count <= 0) t.sites.delete(alloc.site);
+  }
+  t.totalFrees++;
+}
+
+// Symbolication is comparatively expensive, so it only happens for the sites being reported.
+function describeSite(site: string) {
+  const symbol = DebugSymbol.fromAddress(new NativePointer(site));
+  return {
+    site,
+    symbol: symbol.name ?? null,
+    module: symbol.moduleName ?? null,
+  };
+}
+
+function topSites(t: Tracker, limit: number) {
+  return Array.from(t.sites.entries())
+    .sort((a, b) => b[1].bytes - a[1].bytes)
+    .slice(0, limit)
+    .map(([site, stats]) => ({ ...describeSite(site), count: stats.count, bytes: stats.bytes }));
+}
+
+function totals(t: Tracker) {
+  let outstandingBytes = 0;
+  t.sites.forEach((stats) => {
+    outstandingBytes += stats.bytes;
+  });
+  return {
+    minSize: t.minSize,
+    outstandingCount: t.outstanding.size,
+    outstandingBytes,
+    totalAllocs: t.totalAllocs,
+    totalFrees: t.totalFrees,
+    siteCount: t.sites.size,
+    elapsedMs: Date.now() - t.startedAt,
+  };
+}
+
+function findAllocator(name: string): NativePointer {
+  const address = Module.findGlobalExportByName(name);
+  if (!address) throw new Error(`${name} not found`);
+  return address;
+}
+
+function stopTracker(t: Tracker) {
+  clearInterval(t.timer);
+  t.listeners.forEach((listener) => listener.detach());
+  if (t.op) endOperation(t.op);
+  unregisterWatch("allocation-tracking");
+}
+
+// Shared by allocation_track_stop, operation cancel and stop_all_watches.
+function stopActiveTracker(t: Tracker) {
+  if (tracker !== t) return;
+  stopTracker(t);
+  tracker = null;
+}
+
+export const allocationTrackStart: MethodHandler = ({ params }) => {
+  const { minSize = 0, intervalMs = DEFAULT_SUMMARY_INTERVAL_MS } = (params || {}) as {
+    minSize?: number;
+    intervalMs?: number;
+  };
+
+  if (tracker) throw new Error("allocation tracking is already running");
+  if (!Number.isInteger(minSize) || minSize < 0) throw new Error("minSize must be a non-negative integer");
+
+  const mallocPtr = findAllocator("malloc");
+  const freePtr = findAllocator("free");
+  const reallocPtr = findAllocator("realloc");
+
+  const t: Tracker = {
+    minSize,
+    intervalMs: Math.max(MIN_SUMMARY_INTERVAL_MS, intervalMs),
+    listeners: [],
+    timer: 0,
+    op: null,
+    outstanding: new Map(),
+    sites: new Map(),
+    totalAllocs: 0,
+    totalFrees: 0,
+    startedAt: Date.now(),
+  };
+
+  try {
+    t.listeners.push(
+      Interceptor.attach(mallocPtr, {
+        onEnter(args) {
+          const size = readSize(args[0]);
+          this.size = size >= t.minSize ? size : -1;
+        },
+        onLeave(retval) {
+          if (this.size >= 0) record(t, retval, this.size, this.returnAddress);
+        },
+      }),
+    );
+    t.listeners.push(
+      Interceptor.attach(reallocPtr, {
+        onEnter(args) {
+          this.old = args[0];
+          this.size = readSize(args[1]);
+        },
+        onLeave(retval) {
+          // A failed realloc leaves the old block alive.
+          if (retval.isNull() && this.size !== 0) return;
+          release(t, this.old);
+          if (this.size >= t.minSize) record(t, retval, this.size, this.returnAddress);
+        },
+      }),
+    );
+    t.listeners.push(
+      Interceptor.attach(freePtr, {
+        onEnter(args) {
+          release(t, args[0]);
+        },
+      }),
+    );
+  } catch (e) {
+    stopTracker(t);
+    throw e;
+  }
+
+  t.timer = setInterval(() => {
+    emitEvent("allocation_summary", { ...totals(t), topSites: topSites(t, SUMMARY_TOP_SITES) });
+  }, t.intervalMs) as unknown as number;
+
+  // Cancelling the operation is the same as allocation_track_stop.
+  const description = `malloc/free, minSize ${t.minSize}`;
+  t.op = beginOperation("allocation_tracking", description, () => stopActiveTracker(t));
+
+  tracker = t;
+  registerWatch("allocation-tracking", "allocations", description, () => stopActiveTracker(t));
+  return { minSize: t.minSize, intervalMs: t.intervalMs, opId: t.op.id };
+};
+
+export const allocationTrackStop: MethodHandler = () => {
+  const t = tracker;
+  if (!t) return { stopped: false };
+  stopActiveTracker(t);
+  return { stopped: true, ...totals(t) };
+};
+
+export const allocationSnapshot: MethodHandler = ({ params }) => {
+  const { limit = DEFAULT_SNAPSHOT_LIMIT } = (params || {}) as { limit?: number };
+
+  const t = tracker;
+  if (!t) throw new Error("allocation tracking is not running");
+
+  const allocations: { address: string; size: number; site: string; ageMs: number }[] = [];
+  const now = Date.now();
+  for (const [address, alloc] of t.outstanding) {
+    if (allocations.length >= limit) break;
+    allocations.push({ address, size: alloc.size, site: alloc.site, ageMs: now - alloc.time });
+  }
+
+  return {
+    ...totals(t),
+    sites: topSites(t, limit),
+    allocations,
+    truncated: t.outstanding.size > allocations.length,
+  };
+};
@@ -1,3 +1,5 @@
+import { BinaryResult } from "../../rpc/reply";
+import { beginOperation, endOperation, yieldToHost } from "../../rpc/operations";
 import type { MethodHandler } from "../../rpc/types";
 
 // Read memory at address
@@ -126,6 +128,98 @@ export const searchMemory: MethodHandler = async ({ params }) => {
   }
 };
 
+// Find pointer-sized values equal to an address ("who points here")
+export const findPointers: MethodHandler = ({ params }) => {
+  const {
+    address,
+    ranges,
+    limit = 256,
+    aligned = true,
+  } = (params || {}) as {
+    address?: string;
+    ranges?: { base: string; size: number }[];
+    limit?: number;
+    aligned?: boolean;
+  };
+
+  if (!address) {
+    throw new Error("address parameter is required");
+  }
+
+  const maxResults = Math.max(1, Math.min(limit, 10000));
+  const pointerSize = Process.pointerSize;
+
+  // Write the target through Frida so the pattern is in the target's byte order.
+  const scratch = Memory.alloc(pointerSize);
+  scratch.writePointer(new NativePointer(address));
+  const pattern = Array.from(new Uint8Array(scratch.readByteArray(pointerSize)!))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join(" ");
+
+  const targets =
+    ranges ??
+    Process.enumerateRanges("r--").map((r) => ({ base: r.base.toString(), size: r.size }));
+
+  const results: string[] = [];
+  let truncated = false;
+
+  for (const range of targets) {
+    if (truncated) break;
+
+    try {
+      for (const match of Memory.scanSync(new NativePointer(range.base), range.size, pattern)) {
+        // The scratch buffer itself always matches; don't report it.
+        if (match.address.equals(scratch)) continue;
+        if (aligned && match.address.and(pointerSize - 1).toInt32() !== 0) continue;
+        if (results.length >= maxResults) {
+          truncated = true;
+          break;
+        }
+        results.push(match.address.toString());
+      }
+    } catch {
+      // Skip unreadable ranges
+    }
+  }
+
+  return { address, results, count: results.length, truncated };
+};
+
+// Summarize mapped memory by protection, backing and module without shipping the range list
+export const memorySummary: MethodHandler = ({ params }) => {
+  const { topModules = 10 } = (params || {}) as { topModules?: number };
+
+  try {
+    const totals = { total: 0, rx: 0, rw: 0, ro: 0, none: 0, private: 0, shared: 0 };
+    const perModule = new Map<string, number>();
+    const modules = new ModuleMap();
+
+    for (const range of Process.enumerateRanges("---")) {
+      const prot = range.protection;
+      totals.total += range.size;
+      if (prot[2] === "x") totals.rx += range.size;
+      else if (prot[1] === "w") totals.rw += range.size;
+      else if (prot[0] === "r") totals.ro += range.size;
+      else totals.none += range.size;
+
+      // File-backed mappings are treated as shared; anonymous ones as private.
+      if (range.file) totals.shared += range.size;
+      else totals.private += range.size;
+
+      const name = modules.findName(range.base);
+      if (name) perModule.set(name, (perModule.get(name) ?? 0) + range.size);
+    }
+
+    const byModule = Array.from(perModule, ([name, size]) => ({ name, size }))
+      .sort((a, b) => b.size - a.size)
+      .slice(0, Math.max(0, topModules));
+
+    return { ...totals, modules: byModule };
+  } catch (e) {
+    throw new Error(`Failed to summarize memory: ${e}`);
+  }
+};
+
 // Enumerate memory ranges
 export const enumerateRanges: MethodHandler = ({ params }) => {
   const { protection = "r--" } = (params || {}) as { protection?: string };
@@ -143,6 +237,25 @@ export const enumerateRanges: MethodHandler = ({ params }) => {
   }
 };
 
+// Read raw bytes for host-side dumps (no hex formatting, sent as binary data)
+export const readMemoryRaw: MethodHandler = ({ params }) => {
+  const { address, size } = (params || {}) as { address?: string; size?: number };
+
+  if (!address) {
+    throw new Error("address parameter is required");
+  }
+  if (!size || size <= 0 || size > 4 * 1024 * 1024) {
+    throw new Error("size must be between 1 and 4MB");
+  }
+
+  const bytes = new NativePointer(address).readByteArray(size);
+  if (!bytes) {
+    throw new Error("Failed to read memory - null result");
+  }
+
+  return new BinaryResult({ address, size: bytes.byteLength }, bytes);
+};
+
 // Allocate memory
 export const allocateMemory: MethodHandler = ({ params }) => {
   const { size } = (params || {}) as { size?: number };
@@ -183,9 +296,12 @@ export const memoryScanAsync: MethodHandler = async ({ params }) => {
   // Import emitEvent for streaming
   const { emitEvent } = await import("../../rpc/reply");
 
-  // Create abort controller
+  // Create abort controller; cancelling the registered operation aborts it too.
   scanAbortController = { abort: false };
   const controller = scanAbortController;
+  const op = beginOperation("memory_scan", `scan ${pattern} in ${protection}`, () => {
+    controller.abort = true;
+  });
 
   let totalMatches = 0;
   let scannedRanges = 0;
@@ -194,7 +310,7 @@ export const memoryScanAsync: MethodHandler = async ({ params }) => {
     const ranges = Process.enumerateRanges(protection);
     const totalRanges = ranges.length;
 
-    emitEvent("memory_scan_started", { pattern, totalRanges });
+    emitEvent("memory_scan_started", { pattern, totalRanges, opId: op.id });
 
     for (const range of ranges) {
       if (controller.abort || totalMatches >= limit) break;
@@ -224,7 +340,7 @@ export const memoryScanAsync: MethodHandler = async ({ params }) => {
         // Skip unreadable ranges
       }
 
-      // Emit progress every 10 ranges
+      // Emit progress every 10 ranges, and let pending requests (abort/cancel) run
       if (scannedRanges % 10 === 0) {
         emitEvent("memory_scan_progress", {
           scannedRanges,
@@ -232,6 +348,7 @@ export const memoryScanAsync: MethodHandler = async ({ params }) => {
           matchCount: totalMatches,
           progress: Math.round((scannedRanges / totalRanges) * 100),
         });
+        await yieldToHost();
       }
     }
 
@@ -247,6 +364,7 @@ export const memoryScanAsync: MethodHandler = async ({ params }) => {
     throw new Error(`Failed to scan memory: ${e}`);
   } finally {
     scanAbortController = null;
+    endOperation(op);
   }
 };
 
@@ -330,3 +448,7 @@ export {
 } from "./valueScan";
 
 export { memoryWatchAdd, memoryWatchRemove, memoryWatchList, memoryWatchClear } from "./watch";
+
+export { readStruct } from "./struct";
+
+export { allocationTrackStart, allocationTrackStop, allocationSnapshot } from "./allocations";
@@ -0,0 +1,74 @@
+import type { MethodHandler } from "../../rpc/types";
+
+type FieldKind =
+  | "s8"
+  | "u8"
+  | "s16"
+  | "u16"
+  | "s32"
+  | "u32"
+  | "s64"
+  | "u64"
+  | "float"
+  | "double"
+  | "pointer"
+  | "utf8";
+
+type StructField = {
+  name: string;
+  kind: FieldKind;
+  offset: number;
+};
+
+function readField(ptr: NativePointer, kind: FieldKind): string {
+  switch (kind) {
+    case "s8":
+      return ptr.readS8().toString();
+    case "u8":
+      return ptr.readU8().toString();
+    case "s16":
+      return ptr.readS16().toString();
+    case "u16":
+      return ptr.readU16().toString();
+    case "s32":
+      return ptr.readS32().toString();
+    case "u32":
+      return ptr.readU32().toString();
+    case "s64":
+      return ptr.readS64().toString();
+    case "u64":
+      return ptr.readU64().toString();
+    case "float":
+      return ptr.readFloat().toString();
+    case "double":
+      return ptr.readDouble().toString();
+    case "pointer":
+      return ptr.readPointer().toString();
+    case "utf8":
+      return ptr.readUtf8String() ?? "";
+  }
+}
+
+// Read every field of a struct in one call. A field that faults is reported in `errors`
+// and the rest are still read.
+export const readStruct: MethodHandler = ({ params }) => {
+  const { address, fields } = (params || {}) as { address?: string; fields?: StructField[] };
+
+  if (!address || !Array.isArray(fields)) {
+    throw new Error("address and fields parameters are required");
+  }
+
+  const base = new NativePointer(address);
+  const values: Record<string, string> = {};
+  const errors: Record<string, string> = {};
+
+  for (const field of fields) {
+    try {
+      values[field.name] = readField(base.add(field.offset), field.kind);
+    } catch (e) {
+      errors[field.name] = String(e);
+    }
+  }
+
+  return { address: base.toString(), values, errors };
+};
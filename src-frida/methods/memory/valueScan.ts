@@ -1,3 +1,4 @@
+import { beginOperation, endOperation, yieldToHost } from "../../rpc/operations";
 import type { MethodHandler } from "../../rpc/types";
 
 type ValueType = "s8" | "u8" | "s16" | "u16" | "s32" | "u32" | "s64" | "u64" | "float" | "double" | "utf8";
@@ -136,20 +137,23 @@ export const memoryValueScanStart: MethodHandler = async ({ params }) => {
 
   const { emitEvent } = await import("../../rpc/reply");
 
+  const patternBytes = encodeValueToBytes(valueType, value);
+  if (patternBytes.length === 0) throw new Error("Failed to encode value");
+  const pattern = bytesToPattern(patternBytes);
+
   valueScanAbortController = { abort: false };
   const controller = valueScanAbortController;
 
   const scanId = createId("value-scan");
   const matches: string[] = [];
 
-  const patternBytes = encodeValueToBytes(valueType, value);
-  if (patternBytes.length === 0) throw new Error("Failed to encode value");
-  const pattern = bytesToPattern(patternBytes);
-
   const ranges = Process.enumerateRanges(protection);
   const totalRanges = ranges.length;
+  const op = beginOperation("value_scan", `${valueType} ${value} in ${protection}`, () => {
+    controller.abort = true;
+  });
 
-  emitEvent("memory_value_scan_started", { scanId, valueType, value, protection, totalRanges });
+  emitEvent("memory_value_scan_started", { scanId, valueType, value, protection, totalRanges, opId: op.id });
 
   let scannedRanges = 0;
   for (const range of ranges) {
@@ -175,6 +179,7 @@ export const memoryValueScanStart: MethodHandler = async ({ params }) => {
         matchCount: matches.length,
         progress: Math.round((scannedRanges / totalRanges) * 100),
       });
+      await yieldToHost();
     }
   }
 
@@ -196,6 +201,7 @@ export const memoryValueScanStart: MethodHandler = async ({ params }) => {
   });
 
   valueScanAbortController = null;
+  endOperation(op);
 
   return { scanId, totalMatches: matches.length, scannedRanges, aborted: controller.abort };
 };
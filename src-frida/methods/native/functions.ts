@@ -189,6 +189,31 @@ export const getDebugSymbol: MethodHandler = ({ params }) => {
   }
 };
 
+// Symbolicate many addresses in one call; unresolved entries get null fields instead of failing
+export const symbolicate: MethodHandler = ({ params }) => {
+  const { addresses } = (params || {}) as { addresses?: string[] };
+
+  if (!Array.isArray(addresses)) {
+    throw new Error("addresses parameter is required");
+  }
+
+  return addresses.map((address) => {
+    try {
+      const ptr = new NativePointer(address);
+      const sym = DebugSymbol.fromAddress(ptr);
+      const mod = Process.findModuleByAddress(ptr);
+      return {
+        address: ptr.toString(),
+        name: sym.name || null,
+        module: mod ? mod.name : sym.moduleName || null,
+        offset: mod ? "0x" + ptr.sub(mod.base).toString(16) : null,
+      };
+    } catch {
+      return { address, name: null, module: null, offset: null };
+    }
+  });
+};
+
 // Get function by address
 export const getFunctionByAddress: MethodHandler = ({ params }) => {
   const { address } = (params || {}) as { address?: string };
@@ -92,6 +92,40 @@ export const enumerateSymbols: MethodHandler = ({ params }) => {
   }
 };
 
+// Paged debug symbols (not just exports) in address order. A stripped module has an empty
+// table; that comes back as `available: false` rather than an error.
+export const listSymbols: MethodHandler = ({ params }) => {
+  const { module: moduleName, offset = 0, limit = 1000 } = (params || {}) as {
+    module?: string;
+    offset?: number;
+    limit?: number;
+  };
+
+  if (!moduleName) {
+    throw new Error("module parameter is required");
+  }
+
+  const mod = Process.findModuleByName(moduleName);
+  if (!mod) {
+    throw new Error(`Module '${moduleName}' not found`);
+  }
+
+  const symbols = mod.enumerateSymbols().sort((a, b) => a.address.compare(b.address));
+  return {
+    module: mod.name,
+    available: symbols.length > 0,
+    total: symbols.length,
+    offset,
+    symbols: symbols.slice(offset, offset + limit).map((sym) => ({
+      type: sym.type,
+      name: sym.name,
+      address: sym.address.toString(),
+      size: sym.size ?? null,
+      isGlobal: sym.isGlobal,
+    })),
+  };
+};
+
 // Find module by address
 export const findModuleByAddress: MethodHandler = ({ params }) => {
   const { address } = (params || {}) as { address?: string };
@@ -261,3 +295,138 @@ export const findGlobalExportByName: MethodHandler = ({ params }) => {
     throw new Error(`Failed to find global export: ${e}`);
   }
 };
+
+// Express an address as module + offset so it survives ASLR. Addresses outside any module
+// come back with `module: null`; `mapped` says whether anything is mapped there at all.
+export const addressToOffset: MethodHandler = ({ params }) => {
+  const { address } = (params || {}) as { address?: string };
+
+  if (!address) {
+    throw new Error("address parameter is required");
+  }
+
+  try {
+    const ptr = new NativePointer(address);
+    const mod = Process.findModuleByAddress(ptr);
+
+    if (!mod) {
+      return {
+        address: ptr.toString(),
+        module: null,
+        offset: null,
+        mapped: Process.findRangeByAddress(ptr) !== null,
+      };
+    }
+
+    return {
+      address: ptr.toString(),
+      module: mod.name,
+      path: mod.path,
+      offset: "0x" + ptr.sub(mod.base).toString(16),
+      mapped: true,
+    };
+  } catch (e) {
+    throw new Error(`Failed to resolve address: ${e}`);
+  }
+};
+
+// Inverse of address_to_offset for the current run's load address
+export const offsetToAddress: MethodHandler = ({ params }) => {
+  const { module: moduleName, offset } = (params || {}) as { module?: string; offset?: string };
+
+  if (!moduleName || !offset) {
+    throw new Error("module and offset parameters are required");
+  }
+
+  const mod = Process.findModuleByName(moduleName);
+  if (!mod) {
+    throw new Error(`Module '${moduleName}' not loaded`);
+  }
+
+  const delta = uint64(offset);
+  if (delta.compare(mod.size) >= 0) {
+    throw new Error(`Offset ${offset} is outside ${mod.name} (size 0x${mod.size.toString(16)})`);
+  }
+
+  return {
+    address: mod.base.add(delta).toString(),
+    module: mod.name,
+    path: mod.path,
+    offset: "0x" + delta.toString(16),
+    mapped: true,
+  };
+};
+
+// Everything cheaply knowable about one address: range, module, nearest symbol at or below it.
+// Addresses in no range come back with `mapped: false` and everything else null.
+export const describeAddress: MethodHandler = ({ params }) => {
+  const { address } = (params || {}) as { address?: string };
+
+  if (!address) {
+    throw new Error("address parameter is required");
+  }
+
+  const ptr = new NativePointer(address);
+  const range = Process.findRangeByAddress(ptr);
+
+  if (!range) {
+    return { address: ptr.toString(), mapped: false, range: null, module: null, symbol: null, inMainModule: false };
+  }
+
+  const mod = Process.findModuleByAddress(ptr);
+  const debugSymbol = DebugSymbol.fromAddress(ptr);
+  const symbol =
+    debugSymbol.name && !debugSymbol.address.isNull()
+      ? {
+          name: debugSymbol.name,
+          address: debugSymbol.address.toString(),
+          offset: "0x" + ptr.sub(debugSymbol.address).toString(16),
+          fileName: debugSymbol.fileName || null,
+          lineNumber: debugSymbol.lineNumber || null,
+        }
+      : null;
+
+  return {
+    address: ptr.toString(),
+    mapped: true,
+    range: {
+      base: range.base.toString(),
+      size: range.size,
+      protection: range.protection,
+      file: range.file ? range.file.path : null,
+    },
+    module: mod
+      ? { name: mod.name, path: mod.path, base: mod.base.toString(), offset: "0x" + ptr.sub(mod.base).toString(16) }
+      : null,
+    symbol,
+    inMainModule: mod !== null && mod.base.equals(Process.mainModule.base),
+  };
+};
+
+// Resolve each import of a module to the module that actually provides it. Lazy or weak
+// imports that aren't bound yet come back with `resolvedModule: null`.
+export const moduleDependencies: MethodHandler = ({ params }) => {
+  const { module: moduleName } = (params || {}) as { module?: string };
+
+  if (!moduleName) {
+    throw new Error("module parameter is required");
+  }
+
+  const mod = Process.findModuleByName(moduleName);
+  if (!mod) {
+    throw new Error(`Module '${moduleName}' not found`);
+  }
+
+  try {
+    const map = new ModuleMap();
+    return mod.enumerateImports().map((imp) => ({
+      import: imp.name,
+      type: imp.type ?? null,
+      declaredModule: imp.module ?? null,
+      resolvedModule: imp.address ? map.findName(imp.address) : null,
+      address: imp.address?.toString() ?? null,
+    }));
+  } catch (e) {
+    throw new Error(`Failed to resolve module dependencies: ${e}`);
+  }
+};
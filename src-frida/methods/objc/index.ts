@@ -170,3 +170,48 @@ export const objcScheduleOnMainThread: MethodHandler = ({ params }) => {
     throw new Error(`Failed to schedule on main thread: ${e}`);
   }
 };
+
+// Search methods across classes. Glob patterns are `class!method` (e.g. `*Crypt*!*encrypt*`)
+// and go through ApiResolver; regex patterns are tested against `class!method` after
+// enumerating everything.
+export const objcSearchMethods: MethodHandler = ({ params }) => {
+  if (!ObjC.available) {
+    throw new Error("ObjC runtime not available");
+  }
+
+  const {
+    pattern,
+    syntax = "glob",
+    limit = 200,
+  } = (params || {}) as { pattern?: string; syntax?: "glob" | "regex"; limit?: number };
+
+  if (!pattern) {
+    throw new Error("pattern parameter is required");
+  }
+
+  try {
+    const regex = syntax === "regex" ? new RegExp(pattern, "i") : null;
+    const [cls, method = "*"] = regex ? ["*", "*"] : pattern.split("!", 2);
+    const matches = new ApiResolver("objc").enumerateMatches(`*[${cls} ${method}]/i`);
+
+    const results: { class: string; method: string }[] = [];
+    let truncated = false;
+
+    for (const match of matches) {
+      // Names look like "-[NSString initWithFormat:]"
+      const parsed = /^([-+])\[(\S+) (.+)\]$/.exec(match.name);
+      if (!parsed) continue;
+      const [, kind, className, selector] = parsed;
+      if (regex && !regex.test(`${className}!${selector}`)) continue;
+      if (results.length >= limit) {
+        truncated = true;
+        break;
+      }
+      results.push({ class: className, method: `${kind}${selector}` });
+    }
+
+    return { results, count: results.length, truncated };
+  } catch (e) {
+    throw new Error(`Failed to search methods: ${e}`);
+  }
+};
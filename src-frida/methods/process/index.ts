@@ -1,5 +1,6 @@
 import type { MethodHandler } from "../../rpc/types";
 import { emitEvent } from "../../rpc/reply";
+import { registerWatch, unregisterWatch } from "../../rpc/watches";
 
 // Get current working directory
 export const getCurrentDir: MethodHandler = () => {
@@ -116,15 +117,61 @@ export const findRangeByAddress: MethodHandler = ({ params }) => {
 // Module observer state
 let moduleObserver: ModuleObserver | null = null;
 
+// Agent load time; attach loads the agent, so timeline offsets are effectively since attach.
+const agentLoadedAt = Date.now();
+
+type TimelineEntry = {
+  event: "added" | "removed";
+  name: string;
+  base: string;
+  path: string;
+  t: number; // ms since the agent loaded
+};
+
+// Load/unload order while the observer runs, oldest first. Modules already loaded when the
+// observer starts are reported (and recorded) first. Past the cap the oldest entries go.
+const DEFAULT_TIMELINE_CAP = 1000;
+let moduleTimeline: TimelineEntry[] = [];
+let timelineCap = DEFAULT_TIMELINE_CAP;
+let timelineDropped = 0;
+
+function recordTimeline(event: TimelineEntry["event"], module: Module) {
+  moduleTimeline.push({
+    event,
+    name: module.name,
+    base: module.base.toString(),
+    path: module.path,
+    t: Math.max(0, Date.now() - agentLoadedAt),
+  });
+  if (moduleTimeline.length > timelineCap) {
+    timelineDropped += moduleTimeline.length - timelineCap;
+    moduleTimeline = moduleTimeline.slice(-timelineCap);
+  }
+}
+
+function stopModuleObserver() {
+  moduleObserver?.detach();
+  moduleObserver = null;
+  unregisterWatch("module-observer");
+}
+
 // Start module observer
-export const attachModuleObserver: MethodHandler = () => {
+export const attachModuleObserver: MethodHandler = ({ params }) => {
+  const { timelineCap: cap } = (params || {}) as { timelineCap?: number };
+
   if (moduleObserver) {
     return { status: "already_running" };
   }
 
+  if (cap !== undefined) {
+    if (!Number.isInteger(cap) || cap < 1) throw new Error("timelineCap must be a positive integer");
+    timelineCap = cap;
+  }
+
   try {
     moduleObserver = Process.attachModuleObserver({
       onAdded(module) {
+        recordTimeline("added", module);
         emitEvent("module_added", {
           name: module.name,
           base: module.base.toString(),
@@ -133,6 +180,7 @@ export const attachModuleObserver: MethodHandler = () => {
         });
       },
       onRemoved(module) {
+        recordTimeline("removed", module);
         emitEvent("module_removed", {
           name: module.name,
           base: module.base.toString(),
@@ -141,8 +189,9 @@ export const attachModuleObserver: MethodHandler = () => {
         });
       },
     });
+    registerWatch("module-observer", "module", "module load/unload observer", stopModuleObserver);
 
-    return { status: "started" };
+    return { status: "started", timelineCap };
   } catch (e) {
     throw new Error(`Failed to attach module observer: ${e}`);
   }
@@ -155,17 +204,39 @@ export const detachModuleObserver: MethodHandler = () => {
   }
 
   try {
-    moduleObserver.detach();
-    moduleObserver = null;
+    stopModuleObserver();
     return { status: "stopped" };
   } catch (e) {
     throw new Error(`Failed to detach module observer: ${e}`);
   }
 };
 
+// Hand back the recorded timeline and start a fresh one (unless `drain` is false).
+export const moduleTimelineGet: MethodHandler = ({ params }) => {
+  const { drain = true } = (params || {}) as { drain?: boolean };
+
+  const result = {
+    entries: drain ? moduleTimeline : moduleTimeline.slice(),
+    dropped: timelineDropped,
+    cap: timelineCap,
+    observing: moduleObserver !== null,
+  };
+  if (drain) {
+    moduleTimeline = [];
+    timelineDropped = 0;
+  }
+  return result;
+};
+
 // Thread observer state
 let threadObserver: ThreadObserver | null = null;
 
+function stopThreadObserver() {
+  threadObserver?.detach();
+  threadObserver = null;
+  unregisterWatch("thread-observer");
+}
+
 // Start thread observer
 export const attachThreadObserver: MethodHandler = () => {
   if (threadObserver) {
@@ -194,6 +265,7 @@ export const attachThreadObserver: MethodHandler = () => {
         });
       },
     });
+    registerWatch("thread-observer", "thread", "thread start/exit/rename observer", stopThreadObserver);
 
     return { status: "started" };
   } catch (e) {
@@ -208,8 +280,7 @@ export const detachThreadObserver: MethodHandler = () => {
   }
 
   try {
-    threadObserver.detach();
-    threadObserver = null;
+    stopThreadObserver();
     return { status: "stopped" };
   } catch (e) {
     throw new Error(`Failed to detach thread observer: ${e}`);
@@ -253,17 +324,151 @@ export const setExceptionHandler: MethodHandler = () => {
   }
 };
 
+const ELF_TYPE_DYN = 3;
+const MACHO_LC_MAIN = 0x80000028;
+
+// Read the entrypoint from the in-memory image header (ELF, Mach-O or PE).
+function resolveEntrypoint(mod: Module): NativePointer | null {
+  const base = mod.base;
+  try {
+    if (Process.platform === "linux" || Process.platform === "qnx") {
+      const is64 = base.add(4).readU8() === 2;
+      const entry = is64 ? base.add(0x18).readU64() : uint64(base.add(0x18).readU32());
+      // PIE executables store a base-relative entry.
+      const isPie = base.add(0x10).readU16() === ELF_TYPE_DYN;
+      return isPie ? base.add(entry) : ptr(entry.toString());
+    }
+    if (Process.platform === "darwin") {
+      const is64 = base.readU32() === 0xfeedfacf;
+      const ncmds = base.add(0x10).readU32();
+      let cmd = base.add(is64 ? 0x20 : 0x1c);
+      for (let i = 0; i < ncmds; i++) {
+        if (cmd.readU32() === MACHO_LC_MAIN) {
+          return base.add(cmd.add(8).readU64());
+        }
+        cmd = cmd.add(cmd.add(4).readU32());
+      }
+      return null;
+    }
+    if (Process.platform === "windows") {
+      const nt = base.add(base.add(0x3c).readU32());
+      return base.add(nt.add(0x28).readU32());
+    }
+  } catch {
+    // Header not readable or not in a format we know
+  }
+  return null;
+}
+
 // Get main module info
 export const getMainModule: MethodHandler = () => {
   try {
     const mod = Process.mainModule;
+    const entrypoint = resolveEntrypoint(mod);
     return {
       name: mod.name,
       base: mod.base.toString(),
       size: mod.size,
       path: mod.path,
+      entrypoint: entrypoint ? entrypoint.toString() : null,
     };
   } catch (e) {
     throw new Error(`Failed to get main module: ${e}`);
   }
 };
+
+// Linux dirent64: d_ino (8) + d_off (8) + d_reclen (2) + d_type (1), then d_name.
+const DIRENT64_NAME_OFFSET = 19;
+// Darwin fcntl commands / limits
+const F_GETFD = 1;
+const F_GETPATH = 50;
+const MAXPATHLEN = 1024;
+
+function libcFunction(name: string, ret: NativeFunctionReturnType, args: NativeFunctionArgumentType[]) {
+  const address = Module.findGlobalExportByName(name);
+  if (!address) {
+    throw new Error(`${name} not found`);
+  }
+  return new NativeFunction(address, ret, args);
+}
+
+function readlinkPath(path: string): string | null {
+  const readlink = libcFunction("readlink", "long", ["pointer", "pointer", "ulong"]);
+  const buf = Memory.alloc(4096);
+  const len = readlink(Memory.allocUtf8String(path), buf, 4096) as number;
+  return len < 0 ? null : buf.readUtf8String(len);
+}
+
+function enumerateLinuxFds(): { fd: number; path: string | null }[] {
+  const opendir = libcFunction("opendir", "pointer", ["pointer"]);
+  // Plain `struct dirent` only shares dirent64's layout on 64-bit targets; a 32-bit one puts
+  // d_name at 11, so without readdir64 there we'd read garbage names.
+  const hasReaddir64 = Module.findGlobalExportByName("readdir64") !== null;
+  if (!hasReaddir64 && Process.pointerSize !== 8) {
+    throw new Error("unsupported libc: no readdir64 on a 32-bit target");
+  }
+  const readdir = libcFunction(hasReaddir64 ? "readdir64" : "readdir", "pointer", ["pointer"]);
+  const closedir = libcFunction("closedir", "int", ["pointer"]);
+
+  const dir = opendir(Memory.allocUtf8String("/proc/self/fd")) as NativePointer;
+  if (dir.isNull()) {
+    throw new Error("Failed to open /proc/self/fd");
+  }
+
+  const fds: { fd: number; path: string | null }[] = [];
+  try {
+    for (;;) {
+      const entry = readdir(dir) as NativePointer;
+      if (entry.isNull()) {
+        break;
+      }
+      const name = entry.add(DIRENT64_NAME_OFFSET).readUtf8String();
+      if (!name || !/^\d+$/.test(name)) {
+        continue;
+      }
+      fds.push({ fd: parseInt(name, 10), path: readlinkPath(`/proc/self/fd/${name}`) });
+    }
+  } finally {
+    closedir(dir);
+  }
+  return fds;
+}
+
+function enumerateDarwinFds(): { fd: number; path: string | null }[] {
+  const getdtablesize = libcFunction("getdtablesize", "int", []);
+  // fcntl is variadic; the marker matters on arm64 where varargs go on the stack.
+  const fcntl = libcFunction("fcntl", "int", ["int", "int", "...", "pointer"]);
+
+  const fds: { fd: number; path: string | null }[] = [];
+  const buf = Memory.alloc(MAXPATHLEN);
+  const limit = getdtablesize() as number;
+  for (let fd = 0; fd < limit; fd++) {
+    if ((fcntl(fd, F_GETFD, NULL) as number) === -1) {
+      continue;
+    }
+    // Sockets and pipes have no path; they're still reported.
+    const path = (fcntl(fd, F_GETPATH, buf) as number) === -1 ? null : buf.readUtf8String();
+    fds.push({ fd, path });
+  }
+  return fds;
+}
+
+// Enumerate open file descriptors of the target
+export const enumerateFds: MethodHandler = () => {
+  if (Process.platform === "linux") {
+    try {
+      // /proc/self/fd includes the descriptor opendir itself holds; it's harmless to report.
+      return enumerateLinuxFds().sort((a, b) => a.fd - b.fd);
+    } catch (e) {
+      throw new Error(`Failed to enumerate fds: ${e}`);
+    }
+  }
+  if (Process.platform === "darwin") {
+    try {
+      return enumerateDarwinFds();
+    } catch (e) {
+      throw new Error(`Failed to enumerate fds: ${e}`);
+    }
+  }
+  throw new Error(`enumerate_fds is not supported on this platform (${Process.platform})`);
+};
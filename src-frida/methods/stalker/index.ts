@@ -1,7 +1,16 @@
+import { beginOperation, endOperation, type Operation } from "../../rpc/operations";
 import type { MethodHandler } from "../../rpc/types";
 
-// Active stalker sessions
-const activeStalkers: Set<number> = new Set();
+// Active stalker sessions, each registered as a cancellable operation
+const activeStalkers: Map<number, Operation> = new Map();
+
+function stopFollowing(tid: number) {
+  const op = activeStalkers.get(tid);
+  if (!op) return;
+  Stalker.unfollow(tid);
+  activeStalkers.delete(tid);
+  endOperation(op);
+}
 
 type FollowParams = {
   threadId?: number;
@@ -57,8 +66,10 @@ export const stalkerFollow: MethodHandler = ({ params }) => {
       },
     });
 
-    activeStalkers.add(tid);
-    return { success: true, threadId: tid };
+    // Cancelling the operation is the same as stalker_unfollow.
+    const op = beginOperation("stalker_follow", `thread ${tid}`, () => stopFollowing(tid));
+    activeStalkers.set(tid, op);
+    return { success: true, threadId: tid, opId: op.id };
   } catch (e) {
     throw new Error(`Failed to follow thread: ${e}`);
   }
@@ -75,8 +86,7 @@ export const stalkerUnfollow: MethodHandler = ({ params }) => {
   }
 
   try {
-    Stalker.unfollow(tid);
-    activeStalkers.delete(tid);
+    stopFollowing(tid);
     return { success: true, threadId: tid };
   } catch (e) {
     throw new Error(`Failed to unfollow thread: ${e}`);
@@ -126,7 +136,7 @@ export const stalkerSetTrustThreshold: MethodHandler = ({ params }) => {
 
 // List active stalker sessions
 export const stalkerList: MethodHandler = () => {
-  return Array.from(activeStalkers);
+  return Array.from(activeStalkers.keys());
 };
 
 // Parse stalker events (helper)
@@ -19,6 +19,49 @@ export const enumerateThreads: MethodHandler = () => {
   }
 };
 
+// General-purpose registers per architecture, in conventional order.
+const REGISTER_NAMES: Record<string, string[]> = {
+  x64: [
+    "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp",
+    "r8", "r9", "r10", "r11", "r12", "r13", "r14", "r15", "rip",
+  ],
+  ia32: ["eax", "ebx", "ecx", "edx", "esi", "edi", "ebp", "esp", "eip"],
+  arm64: [...Array.from({ length: 29 }, (_, i) => `x${i}`), "fp", "lr", "sp", "pc"],
+  arm: [...Array.from({ length: 13 }, (_, i) => `r${i}`), "sp", "lr", "pc"],
+};
+
+// Register snapshot of one thread. Values are hex strings keyed by the architecture's names.
+export const threadContext: MethodHandler = ({ params }) => {
+  const { threadId } = (params || {}) as { threadId?: number };
+
+  if (threadId === undefined) {
+    throw new Error("threadId parameter is required");
+  }
+
+  const thread = Process.enumerateThreads().find((t) => t.id === threadId);
+  if (!thread) {
+    throw new Error(`Thread ${threadId} not found`);
+  }
+
+  const context = thread.context as unknown as Record<string, NativePointer | undefined>;
+  const registers: Record<string, string> = {};
+  for (const name of REGISTER_NAMES[Process.arch] ?? []) {
+    const value = context[name];
+    if (value !== undefined) {
+      registers[name] = value.toString();
+    }
+  }
+
+  return {
+    threadId,
+    arch: Process.arch,
+    state: thread.state,
+    pc: thread.context.pc.toString(),
+    sp: thread.context.sp.toString(),
+    registers,
+  };
+};
+
 // Get backtrace for a thread
 export const getBacktrace: MethodHandler = ({ params }) => {
   const { threadId } = (params || {}) as { threadId?: number };
@@ -160,3 +203,87 @@ export const threadSleep: MethodHandler = ({ params }) => {
     throw new Error(`Failed to sleep: ${e}`);
   }
 };
+
+// Threads frozen by threadSuspendAll, so resume only touches what we suspended.
+let suspendedThreads: number[] = [];
+
+const THREAD_SUSPEND_RESUME = 0x0002;
+
+type ThreadControl = (id: number) => boolean;
+
+function nativeThreadControl(action: "suspend" | "resume"): ThreadControl {
+  if (Process.platform === "darwin") {
+    // Frida thread ids are mach thread ports on Darwin.
+    const name = action === "suspend" ? "thread_suspend" : "thread_resume";
+    const address = Module.findGlobalExportByName(name);
+    if (!address) {
+      throw new Error(`${name} not found`);
+    }
+    const fn = new NativeFunction(address, "int", ["uint"]);
+    return (id) => (fn(id) as number) === 0;
+  }
+
+  if (Process.platform === "windows") {
+    const kernel32 = Process.getModuleByName("kernel32.dll");
+    const openThread = new NativeFunction(kernel32.getExportByName("OpenThread"), "pointer", [
+      "uint",
+      "int",
+      "uint",
+    ]);
+    const closeHandle = new NativeFunction(kernel32.getExportByName("CloseHandle"), "int", ["pointer"]);
+    const control = new NativeFunction(
+      kernel32.getExportByName(action === "suspend" ? "SuspendThread" : "ResumeThread"),
+      "uint",
+      ["pointer"],
+    );
+    return (id) => {
+      const handle = openThread(THREAD_SUSPEND_RESUME, 0, id) as NativePointer;
+      if (handle.isNull()) {
+        return false;
+      }
+      try {
+        return (control(handle) as number) !== 0xffffffff;
+      } finally {
+        closeHandle(handle);
+      }
+    };
+  }
+
+  // Linux has no per-thread suspend; SIGSTOP would freeze the agent's own thread as well.
+  throw new Error(`Thread suspension is not supported on ${Process.platform}`);
+}
+
+// Suspend every thread except Frida's own. Threads holding locks the agent needs (e.g. malloc)
+// can deadlock the process until resumed.
+export const threadSuspendAll: MethodHandler = () => {
+  if (suspendedThreads.length > 0) {
+    throw new Error("Target is already suspended");
+  }
+
+  const suspend = nativeThreadControl("suspend");
+  const self = Process.getCurrentThreadId();
+  const suspended: number[] = [];
+  const failed: number[] = [];
+
+  for (const thread of Process.enumerateThreads()) {
+    if (thread.id === self) continue;
+    (suspend(thread.id) ? suspended : failed).push(thread.id);
+  }
+
+  suspendedThreads = suspended;
+  return { suspended, failed };
+};
+
+// Resume the threads suspended by threadSuspendAll
+export const threadResumeAll: MethodHandler = () => {
+  const resume = nativeThreadControl("resume");
+  const resumed: number[] = [];
+  const failed: number[] = [];
+
+  for (const id of suspendedThreads) {
+    (resume(id) ? resumed : failed).push(id);
+  }
+
+  suspendedThreads = [];
+  return { resumed, failed };
+};
@@ -0,0 +1,12 @@
+// Host-supplied configuration, injected as `globalThis.__CARF_CONFIG__` ahead of the bundle
+// when the default agent is loaded with a config object.
+
+export type AgentConfig = Record<string, unknown>;
+
+export function getAgentConfig(): AgentConfig {
+  const config = (globalThis as { __CARF_CONFIG__?: unknown }).__CARF_CONFIG__;
+  if (config && typeof config === "object" && !Array.isArray(config)) {
+    return config as AgentConfig;
+  }
+  return {};
+}
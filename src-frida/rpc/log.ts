@@ -0,0 +1,44 @@
+// Agent-wide verbosity control. Console output below the current level is dropped in the
+// agent so it never reaches the host's `frida_script_message` stream.
+
+export type LogLevel = "error" | "warn" | "info" | "debug";
+
+const LEVEL_ORDER: Record<LogLevel, number> = {
+  error: 0,
+  warn: 1,
+  info: 2,
+  debug: 3,
+};
+
+let currentLevel: LogLevel = "debug";
+
+export function isLogLevel(value: unknown): value is LogLevel {
+  return typeof value === "string" && value in LEVEL_ORDER;
+}
+
+export function getLogLevel(): LogLevel {
+  return currentLevel;
+}
+
+export function setLogLevel(level: LogLevel) {
+  currentLevel = level;
+}
+
+function enabled(level: LogLevel) {
+  return LEVEL_ORDER[level] <= LEVEL_ORDER[currentLevel];
+}
+
+// Wrap the console methods once at boot.
+export function installLogFilter() {
+  const wrap = (level: LogLevel, original: (...args: unknown[]) => void) => {
+    return (...args: unknown[]) => {
+      if (enabled(level)) original(...args);
+    };
+  };
+
+  console.error = wrap("error", console.error.bind(console));
+  console.warn = wrap("warn", console.warn.bind(console));
+  console.info = wrap("info", console.info.bind(console));
+  console.log = wrap("info", console.log.bind(console));
+  console.debug = wrap("debug", console.debug.bind(console));
+}
@@ -0,0 +1,60 @@
+// Registry of long-running agent operations (scans, allocation tracking) so the host can list
+// and cancel them. Cancellation is cooperative: loops poll `cancelled` and call
+// `yieldToHost()` now and then so a queued cancel request actually gets to run.
+
+export type Operation = {
+  id: string;
+  kind: string;
+  description: string;
+  startedAt: number;
+  cancelled: boolean;
+  // Called once on cancel, for operations that aren't a loop (e.g. hooks to detach).
+  onCancel?: () => void;
+};
+
+const operations = new Map<string, Operation>();
+let nextOperationId = 1;
+
+export function beginOperation(kind: string, description: string, onCancel?: () => void): Operation {
+  const op: Operation = {
+    id: `op-${nextOperationId++}`,
+    kind,
+    description,
+    startedAt: Date.now(),
+    cancelled: false,
+    onCancel,
+  };
+  operations.set(op.id, op);
+  return op;
+}
+
+export function endOperation(op: Operation) {
+  operations.delete(op.id);
+}
+
+export function listOperations() {
+  const now = Date.now();
+  return Array.from(operations.values()).map((op) => ({
+    id: op.id,
+    kind: op.kind,
+    description: op.description,
+    startedAt: op.startedAt,
+    elapsedMs: now - op.startedAt,
+    cancelled: op.cancelled,
+  }));
+}
+
+// Returns false for unknown (or already finished) operations.
+export function cancelOperation(id: string): boolean {
+  const op = operations.get(id);
+  if (!op) return false;
+  if (!op.cancelled) {
+    op.cancelled = true;
+    op.onCancel?.();
+  }
+  return true;
+}
+
+export function yieldToHost(): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, 0));
+}
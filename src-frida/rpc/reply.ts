@@ -5,6 +5,20 @@ export function replyOk(id: number, returns: unknown) {
   send(payload);
 }
 
+// Reply with a raw binary attachment (delivered as the message `data`, not JSON).
+export function replyOkWithData(id: number, returns: unknown, data: ArrayBuffer) {
+  const payload: RpcResponsePayload = { type: "carf:response", id, result: "ok", returns };
+  send(payload, data);
+}
+
+// Handlers return this to ship bytes alongside the JSON result.
+export class BinaryResult {
+  constructor(
+    public returns: unknown,
+    public data: ArrayBuffer,
+  ) {}
+}
+
 export function replyError(id: number, message: string, stack?: string) {
   const payload: RpcResponsePayload = {
     type: "carf:response",
@@ -1,5 +1,5 @@
-import type { MethodHandler, RequestMessage } from "./types";
-import { replyError, replyOk } from "./reply";
+import type { FlushMessage, FlushReplyPayload, MethodHandler, RequestMessage } from "./types";
+import { BinaryResult, replyError, replyOk, replyOkWithData } from "./reply";
 
 export type RpcRouter = {
   start: () => void;
@@ -17,7 +17,11 @@ export function createRpcRouter(handlers: Record<string, MethodHandler>): RpcRou
       // Support both sync and async handlers
       const result = handler({ params });
       const returns = result instanceof Promise ? await result : result;
-      replyOk(id, returns);
+      if (returns instanceof BinaryResult) {
+        replyOkWithData(id, returns.returns, returns.data);
+      } else {
+        replyOk(id, returns);
+      }
     } catch (e) {
       const message = e instanceof Error ? e.message : String(e);
       const stack = e instanceof Error ? e.stack : undefined;
@@ -35,9 +39,23 @@ export function createRpcRouter(handlers: Record<string, MethodHandler>): RpcRou
     recv("carf:request", onMessage);
   }
 
+  // Echo flush markers so the host knows every message sent before it was delivered.
+  function onFlush(message: FlushMessage) {
+    const payload: FlushReplyPayload = {
+      type: "carf:flush",
+      id: message.__carf_flush,
+      result: "ok",
+      returns: null,
+    };
+    send(payload);
+
+    recv("carf:flush", onFlush);
+  }
+
   return {
     start: () => {
       recv("carf:request", onMessage);
+      recv("carf:flush", onFlush);
     },
   };
 }
@@ -23,6 +23,18 @@ export type RpcEventPayload = {
   returns: unknown;
 };
 
+export type FlushMessage = {
+  type: "carf:flush";
+  __carf_flush: number;
+};
+
+export type FlushReplyPayload = {
+  type: "carf:flush";
+  id: number;
+  result: "ok";
+  returns: null;
+};
+
 // Method handler can be sync or async
 export type MethodHandler = (args: {
   params?: unknown;
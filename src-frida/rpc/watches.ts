@@ -0,0 +1,38 @@
+// Registry of active watchers (observers, file tails, allocation tracking) so the host can see
+// everything that is streaming and stop it in one go. Each feature registers when it starts and
+// unregisters from its own stop path; `stop` must do the latter too.
+
+export type Watch = {
+  id: string;
+  kind: string;
+  description: string;
+  stop: () => void;
+};
+
+const watches = new Map<string, Watch>();
+
+export function registerWatch(id: string, kind: string, description: string, stop: () => void) {
+  watches.set(id, { id, kind, description, stop });
+}
+
+export function unregisterWatch(id: string) {
+  watches.delete(id);
+}
+
+export function listWatches() {
+  return Array.from(watches.values()).map(({ id, kind, description }) => ({ id, kind, description }));
+}
+
+// Returns what was stopped. A watch whose stop throws is still dropped from the registry.
+export function stopAllWatches() {
+  const stopped = listWatches();
+  for (const watch of Array.from(watches.values())) {
+    try {
+      watch.stop();
+    } catch {
+      // Already gone
+    }
+    watches.delete(watch.id);
+  }
+  return stopped;
+}
@@ -1,5 +1,14 @@
-use crate::frida_service::{DeviceInfo, FridaWorker, ProcessInfo, ScriptInfo, SessionInfo};
-use tauri::State;
+use crate::frida_service::{
+    AllocationTrackOptions, AttachProbe, AuditedEvent, DebuggerInfo, DeviceCapabilities, DeviceInfo,
+    DeviceLabel, DumpInfo, FridaWorker, FunctionReplacement, FunctionReplacementSpec, HookCondition,
+    HostInfo, IdentifierAttachInfo, KillResult, LayoutImportResult, MemoryMapExport,
+    MemoryMapFormat, MessageStats, ModuleHookOptions, OtherMessageRouting, PidWatch, PingStats,
+    PinnedDevice, ProcessCount, ProcessInfo, QueuePolicy, ResetSummary, ScriptError, ScriptInfo,
+    ScriptLoadResult, ScriptLoadStats, ScriptSource, ScriptStats, ScriptValidation, SearchRange,
+    SessionInfo, SessionLayout, SpawnAttachInfo, SpawnConfig, SpawnStdioMode, StructField,
+    WatchInfo, WorkspaceInfo,
+};
+use tauri::{ipc::Channel, State};
 
 #[tauri::command]
 pub async fn frida_version(frida: State<'_, FridaWorker>) -> Result<String, String> {
@@ -7,16 +16,45 @@ pub async fn frida_version(frida: State<'_, FridaWorker>) -> Result<String, Stri
 }
 
 #[tauri::command]
-pub async fn frida_list_devices(frida: State<'_, FridaWorker>) -> Result<Vec<DeviceInfo>, String> {
-    frida.list_devices().await
+pub async fn carf_host_info(frida: State<'_, FridaWorker>) -> Result<HostInfo, String> {
+    frida.host_info().await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_devices(
+    frida: State<'_, FridaWorker>,
+    workspace_id: Option<u64>,
+) -> Result<Vec<DeviceInfo>, String> {
+    frida.list_devices(workspace_id).await
+}
+
+// `address` is `host` or `host:port` of a frida-server (e.g. an adb-forwarded port).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_add_remote_device(
+    frida: State<'_, FridaWorker>,
+    address: String,
+    workspace_id: Option<u64>,
+) -> Result<DeviceInfo, String> {
+    frida.add_remote_device(address, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_remove_remote_device(
+    frida: State<'_, FridaWorker>,
+    address: String,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.remove_remote_device(address, workspace_id).await
 }
 
 #[tauri::command(rename_all = "snake_case")]
 pub async fn frida_list_processes(
     frida: State<'_, FridaWorker>,
     device_id: String,
+    workspace_id: Option<u64>,
+    filter: Option<String>,
 ) -> Result<Vec<ProcessInfo>, String> {
-    frida.list_processes(device_id).await
+    frida.list_processes(device_id, workspace_id, filter).await
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -24,13 +62,50 @@ pub async fn frida_attach(
     frida: State<'_, FridaWorker>,
     device_id: String,
     pid: u32,
+    freeze_on_attach: Option<bool>,
+    silent: Option<bool>,
+    workspace_id: Option<u64>,
 ) -> Result<SessionInfo, String> {
-    frida.attach(device_id, pid).await
+    frida
+        .attach(
+            device_id,
+            pid,
+            freeze_on_attach.unwrap_or(false),
+            silent.unwrap_or(false),
+            workspace_id,
+        )
+        .await
 }
 
+// Fails unless exactly one process has this name (case-sensitive).
 #[tauri::command(rename_all = "snake_case")]
-pub async fn frida_detach(frida: State<'_, FridaWorker>, session_id: u64) -> Result<(), String> {
-    frida.detach(session_id).await
+pub async fn frida_attach_by_name(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    process_name: String,
+    workspace_id: Option<u64>,
+) -> Result<SessionInfo, String> {
+    frida.attach_by_name(device_id, process_name, workspace_id).await
+}
+
+// Attach and gather the inspector header in one round trip.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_attach_and_probe(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    pid: u32,
+    workspace_id: Option<u64>,
+) -> Result<AttachProbe, String> {
+    frida.attach_and_probe(device_id, pid, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_detach(
+    frida: State<'_, FridaWorker>,
+    session_id: u64,
+    silent: Option<bool>,
+) -> Result<(), String> {
+    frida.detach(session_id, silent.unwrap_or(false)).await
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -39,8 +114,31 @@ pub async fn frida_spawn(
     device_id: String,
     program: String,
     argv: Option<Vec<String>>,
+    env: Option<Vec<(String, String)>>,
+    cwd: Option<String>,
+    stdio: Option<SpawnStdioMode>,
+    workspace_id: Option<u64>,
 ) -> Result<u32, String> {
-    frida.spawn(device_id, program, argv).await
+    frida
+        .spawn(device_id, program, argv, SpawnConfig { env, cwd, stdio }, workspace_id)
+        .await
+}
+
+// Spawn, attach and load the default agent in one step; the target is left suspended.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_spawn_attach(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    program: String,
+    argv: Option<Vec<String>>,
+    env: Option<Vec<(String, String)>>,
+    cwd: Option<String>,
+    stdio: Option<SpawnStdioMode>,
+    workspace_id: Option<u64>,
+) -> Result<SpawnAttachInfo, String> {
+    frida
+        .spawn_attach(device_id, program, argv, SpawnConfig { env, cwd, stdio }, workspace_id)
+        .await
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -48,8 +146,9 @@ pub async fn frida_resume(
     frida: State<'_, FridaWorker>,
     device_id: String,
     pid: u32,
+    workspace_id: Option<u64>,
 ) -> Result<(), String> {
-    frida.resume(device_id, pid).await
+    frida.resume(device_id, pid, workspace_id).await
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -57,16 +156,23 @@ pub async fn frida_kill(
     frida: State<'_, FridaWorker>,
     device_id: String,
     pid: u32,
+    workspace_id: Option<u64>,
 ) -> Result<(), String> {
-    frida.kill(device_id, pid).await
+    frida.kill(device_id, pid, workspace_id).await
 }
 
+// Returns the session's existing default agent unless `force` is set.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn frida_load_default_script(
     frida: State<'_, FridaWorker>,
     session_id: u64,
+    config: Option<serde_json::Value>,
+    runtime: Option<String>,
+    force: Option<bool>,
 ) -> Result<ScriptInfo, String> {
-    frida.load_default_script(session_id).await
+    frida
+        .load_default_script(session_id, config, runtime, force.unwrap_or(false))
+        .await
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -83,3 +189,865 @@ pub async fn frida_script_post(
 ) -> Result<(), String> {
     frida.script_post(script_id, message, data).await
 }
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_enable_debugger(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    port: Option<u16>,
+) -> Result<DebuggerInfo, String> {
+    frida.enable_debugger(script_id, port).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_disable_debugger(frida: State<'_, FridaWorker>, script_id: u64) -> Result<(), String> {
+    frida.disable_debugger(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_device_capabilities(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    workspace_id: Option<u64>,
+) -> Result<DeviceCapabilities, String> {
+    frida.device_capabilities(device_id, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_agent_capabilities(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.agent_capabilities(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_load_script(
+    frida: State<'_, FridaWorker>,
+    session_id: u64,
+    source: String,
+    name: Option<String>,
+    runtime: Option<String>,
+) -> Result<ScriptInfo, String> {
+    frida.load_script(session_id, source, name, runtime).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_load_script_many(
+    frida: State<'_, FridaWorker>,
+    session_ids: Vec<u64>,
+    source: String,
+    name: Option<String>,
+    config: Option<serde_json::Value>,
+) -> Result<Vec<ScriptLoadResult>, String> {
+    frida.load_script_many(session_ids, source, name, config).await
+}
+
+// New processes on the device are held suspended and reported as `frida_spawn_added`;
+// attach, then `frida_resume` them.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_enable_spawn_gating(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.enable_spawn_gating(device_id, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_disable_spawn_gating(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.disable_spawn_gating(device_id, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_discard_spawn(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    pid: u32,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.discard_spawn(device_id, pid, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_pin_device(
+    frida: State<'_, FridaWorker>,
+    alias: String,
+    device_id: String,
+    workspace_id: Option<u64>,
+) -> Result<PinnedDevice, String> {
+    frida.pin_device(alias, device_id, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_unpin_device(
+    frida: State<'_, FridaWorker>,
+    alias: String,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.unpin_device(alias, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_pinned_devices(
+    frida: State<'_, FridaWorker>,
+    workspace_id: Option<u64>,
+) -> Result<Vec<PinnedDevice>, String> {
+    frida.list_pinned_devices(workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_tail_file(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    path: String,
+) -> Result<serde_json::Value, String> {
+    frida.tail_file(script_id, path).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_stop_tail(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    tail_id: String,
+) -> Result<(), String> {
+    frida.stop_tail(script_id, tail_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_get_agent_log_level(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<String, String> {
+    frida.get_agent_log_level(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_agent_log_level(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    level: String,
+) -> Result<(), String> {
+    frida.set_agent_log_level(script_id, level).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_recent_events(
+    frida: State<'_, FridaWorker>,
+    limit: Option<usize>,
+    workspace_id: Option<u64>,
+) -> Result<Vec<AuditedEvent>, String> {
+    frida.recent_events(limit, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_kill_many(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    pids: Vec<u32>,
+    workspace_id: Option<u64>,
+) -> Result<Vec<KillResult>, String> {
+    frida.kill_many(device_id, pids, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_ping_agent(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    samples: Option<u32>,
+) -> Result<PingStats, String> {
+    frida.ping_agent(script_id, samples).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_agent_autoreload(
+    frida: State<'_, FridaWorker>,
+    enabled: bool,
+    path: Option<String>,
+    workspace_id: Option<u64>,
+) -> Result<Option<String>, String> {
+    frida.set_agent_autoreload(enabled, path, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_enumerate_ranges(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    protection: Option<String>,
+) -> Result<serde_json::Value, String> {
+    frida.enumerate_ranges(script_id, protection).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_dump_region(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    address: String,
+    size: u64,
+    path: String,
+    overwrite: Option<bool>,
+) -> Result<DumpInfo, String> {
+    frida
+        .dump_region(script_id, address, size, path, overwrite.unwrap_or(false))
+        .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_export_memory_map(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    out_path: String,
+    format: MemoryMapFormat,
+    overwrite: Option<bool>,
+) -> Result<MemoryMapExport, String> {
+    frida
+        .export_memory_map(script_id, out_path, format, overwrite.unwrap_or(false))
+        .await
+}
+
+// Replaces a function's implementation. A wrong signature or a throwing body can crash the target.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_replace_function(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    target: String,
+    return_type: Option<String>,
+    arg_types: Option<Vec<String>>,
+    body: Option<String>,
+    return_value: Option<serde_json::Value>,
+) -> Result<FunctionReplacement, String> {
+    let spec = FunctionReplacementSpec {
+        target,
+        return_type,
+        arg_types,
+        body,
+        return_value,
+    };
+    frida.replace_function(script_id, spec).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_restore_function(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    id: String,
+) -> Result<(), String> {
+    frida.restore_function(script_id, id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_session_idle_timeout(
+    frida: State<'_, FridaWorker>,
+    session_id: u64,
+    timeout_ms: u64,
+) -> Result<(), String> {
+    frida.set_session_idle_timeout(session_id, timeout_ms).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_reload_on_reattach(
+    frida: State<'_, FridaWorker>,
+    session_id: u64,
+    enabled: bool,
+) -> Result<(), String> {
+    frida.set_reload_on_reattach(session_id, enabled).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_export_layout(
+    frida: State<'_, FridaWorker>,
+    workspace_id: Option<u64>,
+) -> Result<Vec<SessionLayout>, String> {
+    frida.export_layout(workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_import_layout(
+    frida: State<'_, FridaWorker>,
+    layout: Vec<SessionLayout>,
+    workspace_id: Option<u64>,
+) -> Result<Vec<LayoutImportResult>, String> {
+    frida.import_layout(layout, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_event_rate_limit(
+    frida: State<'_, FridaWorker>,
+    event_name: String,
+    max_per_sec: Option<u32>,
+) -> Result<(), String> {
+    frida.set_event_rate_limit(event_name, max_per_sec).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_fds(frida: State<'_, FridaWorker>, script_id: u64) -> Result<serde_json::Value, String> {
+    frida.list_fds(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_agent_load_retry(
+    frida: State<'_, FridaWorker>,
+    retries: u32,
+    delay_ms: u64,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.set_agent_load_retry(retries, delay_ms, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_device_label(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    label: DeviceLabel,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.set_device_label(device_id, label, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_validate_script(
+    frida: State<'_, FridaWorker>,
+    source: String,
+    workspace_id: Option<u64>,
+) -> Result<ScriptValidation, String> {
+    frida.validate_script(source, workspace_id).await
+}
+
+// Per workspace: other workspaces' queues keep their own policy (new ones start unbounded).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_queue_policy(
+    frida: State<'_, FridaWorker>,
+    policy: QueuePolicy,
+    capacity: Option<usize>,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.set_queue_policy(policy, capacity, workspace_id)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_find_pointers(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    address: String,
+    limit: Option<u32>,
+    ranges: Option<Vec<SearchRange>>,
+) -> Result<serde_json::Value, String> {
+    frida.find_pointers(script_id, address, limit, ranges).await
+}
+
+// Pass `null` to go back to inline `data`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_script_binary_channel(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    channel: Option<Channel>,
+) -> Result<(), String> {
+    frida.set_script_binary_channel(script_id, channel).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_hooks(frida: State<'_, FridaWorker>, script_id: u64) -> Result<serde_json::Value, String> {
+    frida.list_hooks(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_hook_enabled(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    hook_id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    frida.set_hook_enabled(script_id, hook_id, enabled).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_main_module(frida: State<'_, FridaWorker>, script_id: u64) -> Result<serde_json::Value, String> {
+    frida.main_module(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_agent_gc(frida: State<'_, FridaWorker>, script_id: u64) -> Result<serde_json::Value, String> {
+    frida.agent_gc(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_symbolicate(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    addresses: Vec<String>,
+) -> Result<serde_json::Value, String> {
+    frida.symbolicate(script_id, addresses).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_attach_by_identifier(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    identifier: String,
+    spawn: Option<bool>,
+    workspace_id: Option<u64>,
+) -> Result<IdentifierAttachInfo, String> {
+    frida
+        .attach_by_identifier(device_id, identifier, spawn.unwrap_or(false), workspace_id)
+        .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_last_script_error(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<Option<ScriptError>, String> {
+    frida.last_script_error(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_script_stats_enabled(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    enabled: bool,
+) -> Result<(), String> {
+    frida.set_script_stats_enabled(script_id, enabled).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_script_message_stats(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<Option<MessageStats>, String> {
+    frida.script_message_stats(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_reset_script_stats(frida: State<'_, FridaWorker>, script_id: u64) -> Result<(), String> {
+    frida.reset_script_stats(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_suspend_target(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.suspend_target(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_resume_target(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.resume_target(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_memory_summary(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.memory_summary(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_search_methods(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    runtime: String,
+    pattern: String,
+    regex: Option<bool>,
+    limit: Option<u32>,
+) -> Result<serde_json::Value, String> {
+    frida
+        .search_methods(script_id, runtime, pattern, regex.unwrap_or(false), limit)
+        .await
+}
+
+// Installs an onEnter/onLeave logging hook; with `condition`, only matching calls emit.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_hook_function(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    target: String,
+    condition: Option<HookCondition>,
+    paired: Option<bool>,
+    capture: Option<Vec<u32>>,
+) -> Result<serde_json::Value, String> {
+    frida
+        .hook_function(script_id, target, condition, paired.unwrap_or(false), capture.unwrap_or_default())
+        .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_hook_vtable(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    address: String,
+    index: u32,
+    arg_count: u32,
+    vtable: Option<bool>,
+) -> Result<serde_json::Value, String> {
+    frida
+        .hook_vtable(script_id, address, index, arg_count, vtable.unwrap_or(false))
+        .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_get_script_source(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    hash_only: Option<bool>,
+) -> Result<ScriptSource, String> {
+    frida.script_source(script_id, hash_only.unwrap_or(false)).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_address_to_offset(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    address: String,
+) -> Result<serde_json::Value, String> {
+    frida.address_to_offset(script_id, address).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_describe_address(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    address: String,
+) -> Result<serde_json::Value, String> {
+    frida.describe_address(script_id, address).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_offset_to_address(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    module: String,
+    offset: String,
+) -> Result<serde_json::Value, String> {
+    frida.offset_to_address(script_id, module, offset).await
+}
+
+#[tauri::command]
+pub async fn frida_create_workspace(
+    frida: State<'_, FridaWorker>,
+    label: Option<String>,
+) -> Result<WorkspaceInfo, String> {
+    frida.create_workspace(label)
+}
+
+#[tauri::command]
+pub async fn frida_list_workspaces(frida: State<'_, FridaWorker>) -> Result<Vec<WorkspaceInfo>, String> {
+    Ok(frida.list_workspaces())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_remove_workspace(frida: State<'_, FridaWorker>, workspace_id: u64) -> Result<(), String> {
+    frida.remove_workspace(workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_read_struct(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    address: String,
+    layout: Vec<StructField>,
+) -> Result<serde_json::Value, String> {
+    frida.read_struct(script_id, address, layout).await
+}
+
+// Streams `frida_module_loaded` / `frida_module_unloaded` until unwatched or unloaded.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_watch_modules(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    timeline_cap: Option<u32>,
+) -> Result<(), String> {
+    frida.watch_modules(script_id, timeline_cap).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_unwatch_modules(frida: State<'_, FridaWorker>, script_id: u64) -> Result<(), String> {
+    frida.unwatch_modules(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_module_timeline(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    drain: Option<bool>,
+) -> Result<serde_json::Value, String> {
+    frida.module_timeline(script_id, drain.unwrap_or(true)).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_reset(frida: State<'_, FridaWorker>, workspace_id: Option<u64>) -> Result<ResetSummary, String> {
+    frida.reset(workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_module_dependencies(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    module: String,
+) -> Result<serde_json::Value, String> {
+    frida.module_dependencies(script_id, module).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_hook_format(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    format: String,
+) -> Result<(), String> {
+    frida.set_hook_format(script_id, format).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_thread_context(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    thread_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.thread_context(script_id, thread_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_hook_module(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    module: String,
+    options: Option<ModuleHookOptions>,
+) -> Result<serde_json::Value, String> {
+    frida.hook_module(script_id, module, options.unwrap_or_default()).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_unhook_module(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    module: String,
+) -> Result<serde_json::Value, String> {
+    frida.unhook_module(script_id, module).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_process_count(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    workspace_id: Option<u64>,
+) -> Result<ProcessCount, String> {
+    frida.process_count(device_id, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_wait_and_attach(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    matcher: String,
+    timeout_ms: u64,
+    workspace_id: Option<u64>,
+) -> Result<SessionInfo, String> {
+    frida
+        .wait_and_attach(device_id, matcher, timeout_ms, workspace_id)
+        .await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_track_allocations(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    options: Option<AllocationTrackOptions>,
+) -> Result<serde_json::Value, String> {
+    frida.track_allocations(script_id, options.unwrap_or_default()).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_stop_tracking_allocations(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.stop_tracking_allocations(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_allocation_snapshot(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    limit: Option<u32>,
+) -> Result<serde_json::Value, String> {
+    frida.allocation_snapshot(script_id, limit).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_watch_pid(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    pid: u32,
+    workspace_id: Option<u64>,
+) -> Result<PidWatch, String> {
+    frida.watch_pid(device_id, pid, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_unwatch_pid(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    pid: u32,
+    workspace_id: Option<u64>,
+) -> Result<bool, String> {
+    frida.unwatch_pid(device_id, pid, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_symbols(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    module: String,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<serde_json::Value, String> {
+    frida.list_symbols(script_id, module, offset.unwrap_or(0), limit).await
+}
+
+// Off by default: eval runs arbitrary code inside the target.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_eval_enabled(frida: State<'_, FridaWorker>, enabled: bool) -> Result<(), String> {
+    frida.set_eval_enabled(enabled);
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_eval(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    expression: String,
+) -> Result<serde_json::Value, String> {
+    frida.eval(script_id, expression).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_agent_operations(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+) -> Result<serde_json::Value, String> {
+    frida.list_agent_operations(script_id).await
+}
+
+// Cooperative: a scan stops at its next checkpoint, so results may still trickle in briefly.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_cancel_agent_operation(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    op_id: String,
+) -> Result<serde_json::Value, String> {
+    frida.cancel_agent_operation(script_id, op_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_get_other_message_routing(frida: State<'_, FridaWorker>) -> Result<OtherMessageRouting, String> {
+    Ok(frida.other_message_routing())
+}
+
+// Omitted markers keep their current setting.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_other_message_routing(
+    frida: State<'_, FridaWorker>,
+    rpc_reply: Option<bool>,
+    ack: Option<bool>,
+    chunk: Option<bool>,
+) -> Result<OtherMessageRouting, String> {
+    Ok(frida.set_other_message_routing(rpc_reply, ack, chunk))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_script_load_stats(
+    frida: State<'_, FridaWorker>,
+    workspace_id: Option<u64>,
+) -> Result<ScriptLoadStats, String> {
+    frida.script_load_stats(workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_list_watches(frida: State<'_, FridaWorker>, script_id: u64) -> Result<Vec<WatchInfo>, String> {
+    frida.list_watches(script_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_stop_all_watches(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    include_pid_watches: Option<bool>,
+) -> Result<Vec<WatchInfo>, String> {
+    frida
+        .stop_all_watches(script_id, include_pid_watches.unwrap_or(false))
+        .await
+}
+
+// `args` is the export's positional argument list; rejects after 30s without a reply.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_script_rpc_call(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    method: String,
+    args: Option<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    frida
+        .script_rpc_call(script_id, method, args.unwrap_or(serde_json::Value::Null))
+        .await
+}
+
+// 0 disables process-list caching; the default is 2000.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_process_cache_ttl(
+    frida: State<'_, FridaWorker>,
+    millis: u64,
+    workspace_id: Option<u64>,
+) -> Result<(), String> {
+    frida.set_process_list_cache_ttl(millis, workspace_id).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_script_list_exports(frida: State<'_, FridaWorker>, script_id: u64) -> Result<Vec<String>, String> {
+    frida.script_list_exports(script_id).await
+}
+
+// Device/OS details as reported by the device itself (frida-server for remote devices).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_device_query_system_parameters(
+    frida: State<'_, FridaWorker>,
+    device_id: String,
+    workspace_id: Option<u64>,
+) -> Result<serde_json::Value, String> {
+    frida.query_system_parameters(device_id, workspace_id).await
+}
+
+// `json_text` is posted as given (it must be valid JSON); `data` is passed through untouched.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_script_post_raw(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    json_text: String,
+    data: Option<Vec<u8>>,
+) -> Result<(), String> {
+    frida.script_post_raw(script_id, json_text, data).await
+}
+
+// Coalesce a script's `frida_script_message`s into one `frida_script_message_batch` per
+// `window_ms` (at most 5000); 0 turns it off.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_set_message_batching(
+    frida: State<'_, FridaWorker>,
+    script_id: u64,
+    window_ms: u64,
+) -> Result<(), String> {
+    frida.set_message_batching(script_id, window_ms).await
+}
+
+// Lifetime message counts for a script, in both directions, and when it was loaded.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn frida_script_stats(frida: State<'_, FridaWorker>, script_id: u64) -> Result<ScriptStats, String> {
+    frida.script_stats(script_id).await
+}
@@ -0,0 +1,41 @@
+use crate::input_service;
+
+// Manual recovery when global hotkeys stop arriving (e.g. after a screen lock). Resets held
+// modifiers; a new hook is only installed if the old one has exited (returns whether it was).
+#[tauri::command]
+pub fn restart_key_listener(app: tauri::AppHandle) -> bool {
+    input_service::restart_key_listener(app, "manual")
+}
+
+// Only emit `rdev_key_event` for these keys (e.g. ["F1", "Escape"]); empty restores all keys.
+#[tauri::command]
+pub fn set_key_allowlist(keys: Vec<String>) {
+    input_service::set_key_allowlist(keys);
+}
+
+// Pause `rdev_key_event` (e.g. while a text field has focus); modifiers keep being tracked.
+#[tauri::command]
+pub fn input_disable() {
+    input_service::set_keys_enabled(false);
+}
+
+#[tauri::command]
+pub fn input_enable() {
+    input_service::set_keys_enabled(true);
+}
+
+#[tauri::command]
+pub fn input_is_enabled() -> bool {
+    input_service::keys_enabled()
+}
+
+// Emits `hotkey_triggered { id }` whenever `chord` (e.g. "Ctrl+Shift+P") is pressed.
+#[tauri::command]
+pub fn input_register_hotkey(id: String, chord: String) -> Result<(), String> {
+    input_service::register_hotkey(id, &chord)
+}
+
+#[tauri::command]
+pub fn input_unregister_hotkey(id: String) -> bool {
+    input_service::unregister_hotkey(&id)
+}
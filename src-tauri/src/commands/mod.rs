@@ -1,19 +1,127 @@
 pub mod frida;
+pub mod input;
 
 // Router-like command registration.
 // Add new command modules here and mount them in `handler()`.
 pub fn handler<R: tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
     tauri::generate_handler![
         frida::frida_version,
+        frida::carf_host_info,
         frida::frida_list_devices,
+        frida::frida_add_remote_device,
+        frida::frida_remove_remote_device,
         frida::frida_list_processes,
         frida::frida_attach,
+        frida::frida_attach_by_name,
+        frida::frida_attach_and_probe,
         frida::frida_detach,
         frida::frida_spawn,
+        frida::frida_spawn_attach,
         frida::frida_resume,
         frida::frida_kill,
         frida::frida_load_default_script,
         frida::frida_unload_script,
         frida::frida_script_post,
+        frida::frida_enable_debugger,
+        frida::frida_disable_debugger,
+        frida::frida_device_capabilities,
+        frida::frida_agent_capabilities,
+        frida::frida_load_script,
+        frida::frida_load_script_many,
+        frida::frida_enable_spawn_gating,
+        frida::frida_disable_spawn_gating,
+        frida::frida_discard_spawn,
+        frida::frida_pin_device,
+        frida::frida_unpin_device,
+        frida::frida_list_pinned_devices,
+        frida::frida_tail_file,
+        frida::frida_stop_tail,
+        frida::frida_get_agent_log_level,
+        frida::frida_set_agent_log_level,
+        frida::frida_recent_events,
+        frida::frida_kill_many,
+        frida::frida_ping_agent,
+        frida::frida_set_agent_autoreload,
+        frida::frida_enumerate_ranges,
+        frida::frida_dump_region,
+        frida::frida_export_memory_map,
+        frida::frida_replace_function,
+        frida::frida_restore_function,
+        frida::frida_set_session_idle_timeout,
+        frida::frida_set_reload_on_reattach,
+        frida::frida_export_layout,
+        frida::frida_import_layout,
+        frida::frida_set_event_rate_limit,
+        frida::frida_list_fds,
+        frida::frida_set_agent_load_retry,
+        frida::frida_set_device_label,
+        frida::frida_validate_script,
+        frida::frida_set_queue_policy,
+        frida::frida_find_pointers,
+        frida::frida_set_script_binary_channel,
+        frida::frida_list_hooks,
+        frida::frida_set_hook_enabled,
+        frida::frida_main_module,
+        frida::frida_agent_gc,
+        frida::frida_symbolicate,
+        frida::frida_attach_by_identifier,
+        frida::frida_last_script_error,
+        frida::frida_set_script_stats_enabled,
+        frida::frida_script_message_stats,
+        frida::frida_reset_script_stats,
+        frida::frida_suspend_target,
+        frida::frida_resume_target,
+        frida::frida_memory_summary,
+        frida::frida_search_methods,
+        frida::frida_hook_function,
+        frida::frida_hook_vtable,
+        frida::frida_get_script_source,
+        frida::frida_address_to_offset,
+        frida::frida_describe_address,
+        frida::frida_offset_to_address,
+        frida::frida_create_workspace,
+        frida::frida_list_workspaces,
+        frida::frida_remove_workspace,
+        frida::frida_read_struct,
+        frida::frida_watch_modules,
+        frida::frida_unwatch_modules,
+        frida::frida_module_timeline,
+        frida::frida_reset,
+        frida::frida_module_dependencies,
+        frida::frida_set_hook_format,
+        frida::frida_thread_context,
+        frida::frida_hook_module,
+        frida::frida_unhook_module,
+        frida::frida_process_count,
+        frida::frida_wait_and_attach,
+        frida::frida_track_allocations,
+        frida::frida_stop_tracking_allocations,
+        frida::frida_allocation_snapshot,
+        frida::frida_watch_pid,
+        frida::frida_unwatch_pid,
+        frida::frida_list_symbols,
+        frida::frida_set_eval_enabled,
+        frida::frida_eval,
+        frida::frida_list_agent_operations,
+        frida::frida_cancel_agent_operation,
+        frida::frida_get_other_message_routing,
+        frida::frida_set_other_message_routing,
+        frida::frida_script_load_stats,
+        frida::frida_list_watches,
+        frida::frida_stop_all_watches,
+        frida::frida_script_rpc_call,
+        frida::frida_set_process_cache_ttl,
+        frida::frida_script_list_exports,
+        frida::frida_device_query_system_parameters,
+        frida::frida_script_post_raw,
+        frida::frida_set_message_batching,
+        frida::frida_script_stats,
+        input::restart_key_listener,
+        input::set_key_allowlist,
+        input::input_enable,
+        input::input_disable,
+        input::input_is_enabled,
+        input::input_register_hotkey,
+        input::input_unregister_hotkey,
     ]
 }
@@ -46,6 +46,9 @@ pub enum FridaError {
     #[error("Operation timed out")]
     Timeout,
 
+    #[error("Frida worker is busy: {0}")]
+    WorkerBusy(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -76,6 +79,7 @@ impl From<FridaError> for ErrorResponse {
             FridaError::RpcFailed(_) => "RPC_FAILED",
             FridaError::InvalidInput(_) => "INVALID_INPUT",
             FridaError::Timeout => "TIMEOUT",
+            FridaError::WorkerBusy(_) => "WORKER_BUSY",
             FridaError::Internal(_) => "INTERNAL_ERROR",
         };
 
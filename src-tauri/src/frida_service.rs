@@ -1,14 +1,24 @@
 use crate::error::{validate_no_nul, FridaError};
-use frida::{Device, DeviceManager, Frida, Message, Script, ScriptHandler, ScriptOption, Session, SpawnOptions};
-use serde::Serialize;
+use frida::{
+    Device, DeviceManager, Frida, Message, Process, Script, ScriptHandler, ScriptOption, ScriptRuntime, Session,
+    SpawnOptions, SpawnStdio, Variant,
+};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    collections::{HashMap, HashSet, VecDeque},
+    ffi::{c_char, CStr},
     mem::ManuallyDrop,
+    path::PathBuf,
     sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender},
-    time::{Duration, Instant},
+    sync::{Arc, Condvar},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tauri::{
+    ipc::{Channel, InvokeResponseBody},
+    Emitter, Manager,
 };
-use tauri::Emitter;
 
 fn debug_log(_msg: &str) {
     #[cfg(debug_assertions)]
@@ -18,19 +28,300 @@ fn debug_log(_msg: &str) {
 // Run all Frida calls on a single dedicated thread because most frida-rust types are !Send/!Sync.
 type Job = Box<dyn FnOnce(&mut FridaContext) + Send + 'static>;
 
+// What happens when a bounded job queue is full.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuePolicy {
+    // No limit (the default, for compatibility).
+    Unbounded,
+    // Refuse the new request with `WorkerBusy`.
+    RejectNew,
+    // Discard the oldest queued request; its caller sees the worker as not responding.
+    DropOldest,
+}
+
+struct QueueState {
+    jobs: VecDeque<Job>,
+    policy: QueuePolicy,
+    capacity: usize,
+    closed: bool,
+}
+
+// Job queue between the IPC wrappers and the worker thread. Unlike an mpsc channel, it lets
+// overload policies look at and trim the backlog.
+struct JobQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+}
+
+impl JobQueue {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                jobs: VecDeque::new(),
+                policy: QueuePolicy::Unbounded,
+                capacity: 0,
+                closed: false,
+            }),
+            ready: Condvar::new(),
+        }
+    }
+
+    fn push(&self, job: Job) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.closed {
+            return Err("Frida worker thread closed".to_string());
+        }
+        if state.policy != QueuePolicy::Unbounded && state.jobs.len() >= state.capacity {
+            match state.policy {
+                QueuePolicy::RejectNew => {
+                    return Err(FridaError::WorkerBusy(format!(
+                        "{} requests already queued",
+                        state.jobs.len()
+                    ))
+                    .into());
+                }
+                _ => {
+                    state.jobs.pop_front();
+                }
+            }
+        }
+        state.jobs.push_back(job);
+        self.ready.notify_one();
+        Ok(())
+    }
+
+    fn pop_timeout(&self, timeout: Duration) -> Result<Job, RecvTimeoutError> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (mut state, _) = self
+            .ready
+            .wait_timeout_while(state, timeout, |state| state.jobs.is_empty() && !state.closed)
+            .unwrap_or_else(|e| e.into_inner());
+        match state.jobs.pop_front() {
+            Some(job) => Ok(job),
+            None if state.closed => Err(RecvTimeoutError::Disconnected),
+            None => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    fn set_policy(&self, policy: QueuePolicy, capacity: usize) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.policy = policy;
+        state.capacity = capacity;
+        if policy == QueuePolicy::DropOldest {
+            while state.jobs.len() > capacity {
+                state.jobs.pop_front();
+            }
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.closed = true;
+        self.ready.notify_all();
+    }
+
+    // For a worker that is gone: refuse new jobs and drop queued ones, whose callers then stop
+    // waiting on replies that would never come.
+    fn close_and_drain(&self) {
+        let jobs = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.closed = true;
+            self.ready.notify_all();
+            std::mem::take(&mut state.jobs)
+        };
+        drop(jobs);
+    }
+
+    fn is_closed(&self) -> bool {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).closed
+    }
+
+    // Queues `job` regardless of policy and closes the queue behind it, so it is the last job
+    // the worker runs before its loop exits. Fails only if the queue was already closed.
+    fn push_final(&self, job: Job) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.closed {
+            return Err("Frida worker thread closed".to_string());
+        }
+        state.jobs.push_back(job);
+        state.closed = true;
+        self.ready.notify_all();
+        Ok(())
+    }
+}
+
+// Script name used for the embedded default agent.
+const DEFAULT_AGENT_NAME: &str = "carf-agent";
+
+// How often the auto-reload watcher stats the agent bundle on disk.
+const AGENT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+// How often watched pids are checked against a process enumeration. Lists are shared through
+// the process-list cache, so an exit can surface up to one cache TTL late.
+const PID_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+const SPAWN_GATING_POLL_INTERVAL: Duration = Duration::from_millis(500);
+// How often the default workspace re-enumerates devices for hotplug events.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Frida's default inspector port, used when the caller doesn't pick one.
+const DEFAULT_DEBUGGER_PORT: u16 = 5858;
+
+// Number of emitted events kept for `frida_recent_events`.
+const EVENT_AUDIT_CAPACITY: usize = 256;
+
+// Round trips measured by `frida_ping_agent` unless the caller asks otherwise.
+const DEFAULT_PING_SAMPLES: u32 = 5;
+const MAX_PING_SAMPLES: u32 = 50;
+
+// Memory dumps are streamed from the agent in chunks of this size.
+const DUMP_CHUNK_SIZE: u64 = 1024 * 1024;
+const MAX_DUMP_SIZE: u64 = 256 * 1024 * 1024;
+
+// Verbosity levels understood by the default agent, most to least severe.
+const AGENT_LOG_LEVELS: [&str; 4] = ["error", "warn", "info", "debug"];
+
+// `device_id` arguments starting with this prefix name a pinned device alias instead.
+const PINNED_DEVICE_PREFIX: &str = "pin:";
+
+// Upper bound for waiting on a script's flush echo before detaching.
+const FLUSH_TIMEOUT: Duration = Duration::from_millis(250);
+
+// Upper bound for backend-originated agent RPC calls.
+const AGENT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+// `rpc.exports` calls run arbitrary user code, so they get far longer than internal requests.
+const RPC_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+// A pointer search scans every readable range in one agent call unless given `ranges`.
+const POINTER_SEARCH_TIMEOUT: Duration = Duration::from_secs(120);
+
+// How long a device's process list is served from cache.
+const DEFAULT_PROCESS_LIST_CACHE_TTL: Duration = Duration::from_secs(2);
+const MAX_PROCESS_LIST_CACHE_TTL: Duration = Duration::from_secs(300);
+
+// Retries for transient default-agent load failures right after attach.
+const DEFAULT_AGENT_LOAD_RETRIES: u32 = 2;
+const DEFAULT_AGENT_LOAD_RETRY_DELAY: Duration = Duration::from_millis(200);
+// Retries sleep on the worker thread, so keep the worst case short.
+const MAX_AGENT_LOAD_RETRIES: u32 = 5;
+const MAX_AGENT_LOAD_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+// Device labels are persisted here, under the app data dir.
+const DEVICE_LABELS_FILE: &str = "device_labels.json";
+
+// Capacity used when a bounded queue policy is chosen without one.
+const DEFAULT_QUEUE_CAPACITY: usize = 64;
+
+// Upper bound on addresses per `frida_symbolicate` call.
+const MAX_SYMBOLICATE_ADDRESSES: usize = 4096;
+
+// Larger scripts are only reported by size and hash from `frida_get_script_source`.
+const MAX_SCRIPT_SOURCE_LEN: usize = 8 * 1024 * 1024;
+
+// Bounds for `frida_read_struct` layouts.
+const MAX_STRUCT_FIELDS: usize = 256;
+const MAX_STRUCT_SPAN: u64 = 64 * 1024;
+
+// Largest module load timeline the agent keeps for `frida_watch_modules`.
+const MAX_MODULE_TIMELINE_CAP: u32 = 100_000;
+
+// Longest expression `frida_eval` sends to the agent.
+const MAX_EVAL_EXPRESSION_LEN: usize = 64 * 1024;
+
+// Page size bounds for `frida_list_symbols`.
+const DEFAULT_SYMBOL_PAGE_LIMIT: u32 = 1000;
+const MAX_SYMBOL_PAGE_LIMIT: u32 = 20_000;
+
+// Bounds for `frida_hook_module`; every hook costs a trampoline and an event per call.
+const DEFAULT_MODULE_HOOK_LIMIT: u32 = 500;
+const MAX_MODULE_HOOK_LIMIT: u32 = 5000;
+
+// Outstanding allocations returned per `frida_allocation_snapshot`.
+const DEFAULT_ALLOCATION_SNAPSHOT_LIMIT: u32 = 1000;
+const MAX_ALLOCATION_SNAPSHOT_LIMIT: u32 = 100_000;
+
+// `frida_wait_and_attach` polling cadence and the longest wait we accept.
+const WAIT_ATTACH_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const MAX_WAIT_ATTACH_TIMEOUT: Duration = Duration::from_secs(600);
+
+// Interceptor args beyond this index are rarely meaningful and not worth guarding against.
+const MAX_HOOK_ARG_INDEX: u32 = 15;
+
+// Vtables past a few thousand slots are almost certainly a wrong object pointer.
+const MAX_VTABLE_INDEX: u32 = 4096;
+
+// Bounds for `frida_search_methods`.
+const MAX_METHOD_PATTERN_LEN: usize = 512;
+const DEFAULT_METHOD_SEARCH_LIMIT: u32 = 200;
+const MAX_METHOD_SEARCH_LIMIT: u32 = 5000;
+
+// Workspace ids occupy the bits above this in session and script ids, which keeps ids unique
+// across workspaces (the handler registry is global) and lets id-scoped calls route themselves.
+const WORKSPACE_ID_SHIFT: u32 = 32;
+const MAX_WORKSPACES: usize = 16;
+
+// Backend-originated request ids start far above the frontend's counter so replies never collide.
+const INTERNAL_REPLY_ID_BASE: u64 = 1 << 40;
+
+// How long app exit waits for the workers to unload scripts and detach sessions.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
 #[derive(Debug, Serialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+    // "debug" or "release", whichever profile this backend was built with.
+    pub build_profile: String,
+    pub frida_core_version: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
 pub struct DeviceInfo {
     pub id: String,
     pub name: String,
     pub device_type: String,
+    // True when `name` had invalid UTF-8 (replaced with U+FFFD) or was empty (the id is used).
+    pub name_sanitized: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<DeviceLabel>,
+}
+
+// UI-only metadata for telling similar devices apart.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeviceLabel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
+    pub name_sanitized: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PinnedDevice {
+    pub alias: String,
+    pub name: String,
+    pub device_type: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AuditedEvent {
+    pub event: String,
+    // Top-level scalar fields of the payload (ids, reason, ...) plus the script message type.
+    pub summary: serde_json::Value,
+    pub timestamp_ms: u64,
 }
 
+// Agent RPC result plus the optional binary attachment sent alongside it.
+type AgentReply = Result<(serde_json::Value, Option<Vec<u8>>), String>;
+
+// Shared with the script message handlers, which emit from Frida's own thread.
+type EventAudit = Arc<Mutex<VecDeque<AuditedEvent>>>;
+
 #[derive(Clone)]
 struct ProcessListCache {
     device_id: String,
@@ -38,664 +329,4993 @@ struct ProcessListCache {
     processes: Vec<ProcessInfo>,
 }
 
+impl ProcessListCache {
+    fn is_fresh(&self, device_id: &str, now: Instant, ttl: Duration) -> bool {
+        self.device_id == device_id && now.saturating_duration_since(self.fetched_at) < ttl
+    }
+}
+
+// Time source for cache TTLs, so tests can drive expiry without sleeping.
+pub trait Clock: Send {
+    fn now(&self) -> Instant;
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+// Clock that only moves when told to. Clones share the same time.
+#[cfg(any(test, feature = "test-hooks"))]
+#[derive(Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+#[cfg(any(test, feature = "test-hooks"))]
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += by;
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-hooks"))]
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(test, feature = "test-hooks"))]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.lock().map(|now| *now).unwrap_or_else(|e| *e.into_inner())
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct SessionInfo {
     pub session_id: u64,
     pub script_id: u64,
+    pub pid: u32,
+    // `thread_suspend_all` result when attached with `freeze_on_attach`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frozen: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ScriptInfo {
+pub struct IdentifierAttachInfo {
+    pub session_id: u64,
     pub script_id: u64,
+    pub pid: u32,
+    // True when the app wasn't running and was spawned for this attach.
+    pub spawned: bool,
 }
 
-struct SessionRecord {
-    _device_id: String,
-    _pid: u32,
-    // Safety: `Session` may internally depend on the `Device` being alive while dropping.
-    // We manually control drop order to prevent potential use-after-free.
-    session: ManuallyDrop<Session<'static>>,
-    _device: ManuallyDrop<Device<'static>>,
-    script_ids: Vec<u64>,
+// The target is still suspended; nothing runs until `frida_resume`.
+#[derive(Debug, Serialize)]
+pub struct SpawnAttachInfo {
+    pub pid: u32,
+    pub session_id: u64,
+    pub script_id: u64,
 }
 
-impl Drop for SessionRecord {
-    fn drop(&mut self) {
-        // Drop `Session` first, then the keepalive `Device`.
-        unsafe {
-            ManuallyDrop::drop(&mut self.session);
-            ManuallyDrop::drop(&mut self._device);
-        }
-    }
+// One-shot environment snapshot taken right after attach. Every probe is best-effort: a failed
+// step leaves its field empty and adds "<step>: <error>" to `errors`.
+#[derive(Debug, Serialize)]
+pub struct AttachProbe {
+    pub session_id: u64,
+    pub script_id: u64,
+    pub pid: u32,
+    pub frida_core_version: String,
+    pub agent_version: Option<serde_json::Value>,
+    pub capabilities: Option<serde_json::Value>,
+    pub main_module: Option<serde_json::Value>,
+    pub arch: Option<String>,
+    pub module_count: Option<usize>,
+    pub errors: Vec<String>,
 }
 
-struct ScriptRecord {
-    session_id: u64,
-    // Safety: Script is leaked (Box::leak) to ensure the callback handler pointer remains valid
-    // for the lifetime of the Frida GLib main loop. We manually drop it via Box::from_raw.
-    script: *mut Script<'static>,
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct MessageTally {
+    pub count: u64,
+    pub bytes: u64,
 }
 
-pub struct FridaWorker {
-    tx: Sender<Job>,
+// What to install over a function with `frida_replace_function`: a JS `body`, run as
+// `(args, original) => ...`, or a constant `return_value`, never both.
+#[derive(Debug, Deserialize)]
+pub struct FunctionReplacementSpec {
+    pub target: String,
+    #[serde(default)]
+    pub return_type: Option<String>,
+    #[serde(default)]
+    pub arg_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub return_value: Option<serde_json::Value>,
 }
 
-impl FridaWorker {
-    pub fn new(app: tauri::AppHandle) -> Self {
-        let (tx, rx) = channel::<Job>();
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FunctionReplacement {
+    pub id: String,
+    pub target: String,
+}
 
-        std::thread::spawn(move || {
-            let mut ctx = FridaContext::new(app);
-            ctx.run(rx);
-        });
+// Lifetime message counts of one script: `messages_in` from the agent (every kind, including
+// replies the backend consumes), `messages_out` posted to it.
+#[derive(Debug, Serialize)]
+pub struct ScriptStats {
+    pub script_id: u64,
+    pub messages_in: u64,
+    pub messages_out: u64,
+    pub loaded_at_ms: u64,
+}
 
-        Self { tx }
-    }
+// Inbound messages per script by Frida message type. Byte totals are approximate: the
+// serialized payload plus any binary data.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct MessageStats {
+    pub send: MessageTally,
+    pub log: MessageTally,
+    pub error: MessageTally,
+    pub other: MessageTally,
+}
 
-    // IPC-friendly wrappers so the rest of the app doesn't need access to `FridaContext`.
-    pub async fn version(&self) -> Result<String, String> {
-        self.request(|ctx| Ok(ctx.version())).await
+impl MessageStats {
+    fn record(&mut self, message: &Message, data: Option<&[u8]>) {
+        let (tally, size) = match message {
+            Message::Send(m) => (&mut self.send, m.payload.returns.to_string().len()),
+            Message::Log(m) => (&mut self.log, m.payload.len()),
+            Message::Error(m) => (&mut self.error, m.description.len() + m.stack.len()),
+            Message::Other(v) => (&mut self.other, v.to_string().len()),
+        };
+        tally.count += 1;
+        tally.bytes += (size + data.map_or(0, |d| d.len())) as u64;
     }
+}
 
-    pub async fn list_devices(&self) -> Result<Vec<DeviceInfo>, String> {
-        self.request(|ctx| Ok(ctx.list_devices())).await
-    }
+// Most recent uncaught error reported by a script (`Message::Error`).
+#[derive(Debug, Serialize, Clone)]
+pub struct ScriptError {
+    pub description: String,
+    pub stack: String,
+    pub file: String,
+    pub line: u64,
+    pub column: u64,
+}
 
-    pub async fn list_processes(&self, device_id: String) -> Result<Vec<ProcessInfo>, String> {
-        self.request(move |ctx| ctx.list_processes(&device_id)).await
-    }
+#[derive(Debug, Serialize)]
+pub struct ScriptInfo {
+    pub script_id: u64,
+    // Wall-clock time spent creating, loading and wiring up the script.
+    pub load_ms: u64,
+    // The session's existing default agent was returned instead of loading another (`load_ms` 0).
+    pub already_loaded: bool,
+}
 
-    pub async fn attach(&self, device_id: String, pid: u32) -> Result<SessionInfo, String> {
-        self.request(move |ctx| ctx.attach(&device_id, pid)).await
-    }
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct LoadTimeStats {
+    pub count: u64,
+    pub total_ms: u64,
+    pub average_ms: f64,
+    pub max_ms: u64,
+}
 
-    pub async fn detach(&self, session_id: u64) -> Result<(), String> {
-        self.request(move |ctx| ctx.detach(session_id)).await
+impl LoadTimeStats {
+    fn record(&mut self, load_ms: u64) {
+        self.count += 1;
+        self.total_ms = self.total_ms.saturating_add(load_ms);
+        self.average_ms = self.total_ms as f64 / self.count as f64;
+        self.max_ms = self.max_ms.max(load_ms);
     }
+}
 
-    pub async fn spawn(
-        &self,
-        device_id: String,
-        program: String,
-        argv: Option<Vec<String>>,
-    ) -> Result<u32, String> {
-        self.request(move |ctx| ctx.spawn(&device_id, program, argv)).await
-    }
+// Successful script loads in a workspace since it started. The default agent is tracked on its
+// own since its cost is what snapshots and bundle trimming affect.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct ScriptLoadStats {
+    pub default_agent: LoadTimeStats,
+    pub other: LoadTimeStats,
+}
 
-    pub async fn resume(&self, device_id: String, pid: u32) -> Result<(), String> {
-        self.request(move |ctx| ctx.resume(&device_id, pid)).await
-    }
+#[derive(Debug, Serialize)]
+pub struct ScriptLoadResult {
+    pub session_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
 
-    pub async fn kill(&self, device_id: String, pid: u32) -> Result<(), String> {
-        self.request(move |ctx| ctx.kill(&device_id, pid)).await
-    }
+#[derive(Debug, Serialize)]
+pub struct KillResult {
+    pub pid: u32,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
 
-    pub async fn load_default_script(&self, session_id: u64) -> Result<ScriptInfo, String> {
-        self.request(move |ctx| ctx.load_default_script(session_id)).await
-    }
+// Predicate evaluated in the agent's onEnter; calls that don't match emit nothing.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HookCondition {
+    pub arg_index: u32,
+    pub op: String,
+    pub value: serde_json::Value,
+}
 
-    pub async fn unload_script(&self, script_id: u64) -> Result<(), String> {
-        self.request(move |ctx| ctx.unload_script(script_id)).await
-    }
+// A memory range to limit `frida_find_pointers` to; `base` is hex or decimal.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchRange {
+    pub base: String,
+    pub size: u64,
+}
 
-    pub async fn script_post(
-        &self,
-        script_id: u64,
-        message: serde_json::Value,
-        data: Option<Vec<u8>>,
-    ) -> Result<(), String> {
-        self.request(move |ctx| ctx.script_post(script_id, message, data)).await
-    }
+// One field of a `frida_read_struct` layout; `offset` is relative to the struct address.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StructField {
+    pub name: String,
+    pub kind: String,
+    pub offset: u64,
+}
 
-    async fn request<T, F>(&self, f: F) -> Result<T, String>
-    where
-        T: Send + 'static,
-        F: FnOnce(&mut FridaContext) -> Result<T, String> + Send + 'static,
-    {
-        let (reply_tx, reply_rx) = channel::<Result<T, String>>();
+#[derive(Debug, Serialize, Clone)]
+pub struct ResetSummary {
+    pub sessions: usize,
+    pub scripts: usize,
+    // Sessions that failed to detach cleanly; they're dropped regardless.
+    pub errors: Vec<String>,
+}
 
-        let job: Job = Box::new(move |ctx| {
-            let result = f(ctx);
-            let _ = reply_tx.send(result);
-        });
+// Options for `frida_track_allocations`. Allocations smaller than `min_size` are skipped in
+// the agent's onEnter, which is what keeps the hooks affordable on allocation-heavy targets.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AllocationTrackOptions {
+    #[serde(default)]
+    pub min_size: u32,
+    #[serde(default)]
+    pub interval_ms: Option<u32>,
+}
 
-        self.tx
-            .send(job)
-            .map_err(|_| "Frida worker thread closed".to_string())?;
+// Export filters for `frida_hook_module`; `include`/`exclude` are JS regexes over export names.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModuleHookOptions {
+    #[serde(default)]
+    pub include: Option<String>,
+    #[serde(default)]
+    pub exclude: Option<String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
 
-        let recv_result = tauri::async_runtime::spawn_blocking(move || reply_rx.recv())
-            .await
-            .map_err(|_| "Failed to wait for Frida worker response".to_string())?;
+// `approximate` counts come from a cached enumeration `age_ms` old, never older than the cache TTL.
+#[derive(Debug, Serialize, Clone)]
+pub struct ProcessCount {
+    pub device_id: String,
+    pub count: usize,
+    pub approximate: bool,
+    pub age_ms: u64,
+}
 
-        recv_result.map_err(|_| "Frida worker did not respond".to_string())?
-    }
+// Portable description of attached sessions and their scripts, for export/import.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionLayout {
+    pub device_id: String,
+    pub pid: u32,
+    // Pids don't survive a restart; on import the process is found by this name when the pid
+    // no longer belongs to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_name: Option<String>,
+    pub scripts: Vec<ScriptLayout>,
 }
 
-struct FridaContext {
-    app: tauri::AppHandle,
-    sessions: HashMap<u64, SessionRecord>,
-    scripts: HashMap<u64, ScriptRecord>,
-    next_session_id: u64,
-    next_script_id: u64,
-    process_list_cache: Option<ProcessListCache>,
-    device_manager: DeviceManager<'static>,
-    _frida: Frida,
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScriptLayout {
+    pub name: String,
+    // Omitted for the default agent, which is reloaded from the current bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<JsRuntime>,
 }
 
-impl FridaContext {
-    fn new(app: tauri::AppHandle) -> Self {
-        let frida = unsafe { Frida::obtain() };
-        let dm = DeviceManager::obtain(&frida);
+// JS runtime a script is created on. Unset leaves the choice to frida (QuickJS by default).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JsRuntime {
+    Qjs,
+    V8,
+}
 
-        // Safety: We keep `frida` alive for the lifetime of this context, so it's safe to extend
-        // the device manager lifetime.
-        let device_manager: DeviceManager<'static> = unsafe { std::mem::transmute(dm) };
+// Source text of a loaded script. `source` is omitted in hash-only mode or above
+// MAX_SCRIPT_SOURCE_LEN; `hash` is FNV-1a 64 of the UTF-8 bytes.
+#[derive(Debug, Serialize)]
+pub struct ScriptSource {
+    pub script_id: u64,
+    pub name: String,
+    pub is_default: bool,
+    pub size: usize,
+    pub hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
 
-        Self {
-            app,
-            sessions: HashMap::new(),
-            scripts: HashMap::new(),
-            next_session_id: 1,
-            next_script_id: 1,
-            process_list_cache: None,
-            device_manager,
-            _frida: frida,
-        }
-    }
+// Which `Message::Other` protocol markers the backend consumes (see `classify_other_message`).
+// A disabled marker's messages reach the frontend as plain `type: "other"` messages.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct OtherMessageRouting {
+    pub rpc_reply: bool,
+    pub ack: bool,
+    pub chunk: bool,
+}
 
-    fn run(&mut self, rx: Receiver<Job>) {
-        loop {
-            match rx.recv_timeout(Duration::from_millis(100)) {
-                Ok(job) => job(self),
-                Err(RecvTimeoutError::Timeout) => self.poll_detached_sessions(),
-                Err(RecvTimeoutError::Disconnected) => break,
-            }
-        }
-    }
+impl OtherMessageRouting {
+    const ALL: Self = Self {
+        rpc_reply: true,
+        ack: true,
+        chunk: true,
+    };
+}
 
-    fn poll_detached_sessions(&mut self) {
-        let detached_ids: Vec<u64> = self
-            .sessions
-            .iter()
-            .filter_map(|(session_id, record)| {
+// An active watcher: agent-side observers, tails and trackers, plus backend pid watches on the
+// script's device.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchInfo {
+    pub id: String,
+    pub kind: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LayoutImportResult {
+    pub device_id: String,
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<u64>,
+    pub script_ids: Vec<u64>,
+    // Per-script load failures don't abort the session; they're collected here.
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScriptValidation {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // Best effort: only set when Frida's message carries a location.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceCapabilities {
+    pub device_id: String,
+    pub device_type: String,
+    pub enumerate_processes: bool,
+    pub attach: bool,
+    pub spawn: bool,
+    pub kill: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PingStats {
+    pub script_id: u64,
+    pub samples: u32,
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PidWatch {
+    pub device_id: String,
+    pub pid: u32,
+    // False when the pid was already gone; `frida_pid_exited` has been emitted and nothing is watched.
+    pub watching: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DumpInfo {
+    pub path: String,
+    pub address: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpawnStdioMode {
+    Inherit,
+    Pipe,
+}
+
+// Optional process setup for `frida_spawn`. `env` entries are added to the inherited
+// environment rather than replacing it.
+#[derive(Debug, Clone, Default)]
+pub struct SpawnConfig {
+    pub env: Option<Vec<(String, String)>>,
+    pub cwd: Option<String>,
+    pub stdio: Option<SpawnStdioMode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryMapFormat {
+    // One aligned line per range: base, size, protection, backing file.
+    Text,
+    // Array of `{ base, size, protection, file }` as returned by range enumeration.
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryMapExport {
+    pub path: String,
+    pub format: MemoryMapFormat,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DebuggerInfo {
+    pub script_id: u64,
+    pub port: u16,
+}
+
+struct SessionRecord {
+    device_id: String,
+    pid: u32,
+    // Safety: `Session` may internally depend on the `Device` being alive while dropping.
+    // We manually control drop order to prevent potential use-after-free.
+    session: ManuallyDrop<Session<'static>>,
+    _device: ManuallyDrop<Device<'static>>,
+    script_ids: Vec<u64>,
+    // Last interaction (attach, script load, post/RPC, agent message); drives the idle timeout.
+    last_activity: Instant,
+    idle_timeout: Option<Duration>,
+    // Sum of the scripts' inbound message counters at the last idle check.
+    messages_seen: u64,
+    reload_on_reattach: bool,
+}
+
+impl Drop for SessionRecord {
+    fn drop(&mut self) {
+        // Drop `Session` first, then the keepalive `Device`.
+        unsafe {
+            ManuallyDrop::drop(&mut self.session);
+            ManuallyDrop::drop(&mut self._device);
+        }
+    }
+}
+
+struct ScriptRecord {
+    session_id: u64,
+    name: String,
+    // Exactly what was loaded, including any config prologue on default agents.
+    source: Arc<str>,
+    runtime: Option<JsRuntime>,
+    // Loaded through `load_default_script` (or an agent reload), as opposed to caller-supplied.
+    is_default: bool,
+    // Injected as `__CARF_CONFIG__`; kept so a reload can inject it again.
+    config: Option<serde_json::Value>,
+    // Shared with the script's message handler, which runs on Frida's thread.
+    messages_in: Arc<AtomicU64>,
+    messages_out: u64,
+    loaded_at_ms: u64,
+    // Safety: Script is leaked (Box::leak) to ensure the callback handler pointer remains valid
+    // for the lifetime of the Frida GLib main loop. We manually drop it via Box::from_raw.
+    script: *mut Script<'static>,
+}
+
+// Dev-loop helper: watch the agent bundle on disk and hot-swap default agents when it changes.
+struct AgentWatch {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    // Last successfully read bundle; used for new default-agent loads while watching.
+    source: Option<String>,
+    last_checked: Instant,
+    // Set when the bundle changed on the last check; the reload waits for a check that sees
+    // no further change, so a bundler's burst of writes reloads once.
+    settling: bool,
+}
+
+pub struct FridaWorker {
+    app: tauri::AppHandle,
+    // Default workspace (0).
+    queue: Arc<JobQueue>,
+    workspaces: Mutex<WorkspaceSet>,
+}
+
+// Extra isolated contexts, each with its own worker thread and DeviceManager, so a device
+// that hangs enumeration in one workspace doesn't stall the others.
+struct Workspace {
+    label: Option<String>,
+    queue: Arc<JobQueue>,
+}
+
+struct WorkspaceSet {
+    entries: HashMap<u64, Workspace>,
+    next_id: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceInfo {
+    pub workspace_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl Drop for FridaWorker {
+    fn drop(&mut self) {
+        self.queue.close();
+        if let Ok(workspaces) = self.workspaces.lock() {
+            for workspace in workspaces.entries.values() {
+                workspace.queue.close();
+            }
+        }
+    }
+}
+
+impl FridaWorker {
+    pub fn new(app: tauri::AppHandle) -> Self {
+        Self {
+            queue: spawn_context(app.clone(), 0),
+            app,
+            workspaces: Mutex::new(WorkspaceSet {
+                entries: HashMap::new(),
+                next_id: 1,
+            }),
+        }
+    }
+
+    pub fn create_workspace(&self, label: Option<String>) -> Result<WorkspaceInfo, String> {
+        if let Some(label) = &label {
+            validate_no_nul("label", label)?;
+        }
+        let mut workspaces = self.workspaces.lock().map_err(|e| e.to_string())?;
+        if workspaces.entries.len() >= MAX_WORKSPACES {
+            return Err(FridaError::InvalidInput(format!("at most {MAX_WORKSPACES} workspaces")).into());
+        }
+
+        // Ids are never reused so stale session/script ids can't route to a newer workspace.
+        let workspace_id = workspaces.next_id;
+        workspaces.next_id += 1;
+        workspaces.entries.insert(
+            workspace_id,
+            Workspace {
+                label: label.clone(),
+                queue: spawn_context(self.app.clone(), workspace_id),
+            },
+        );
+        debug_log(&format!("create_workspace: workspace_id={workspace_id}"));
+
+        Ok(WorkspaceInfo { workspace_id, label })
+    }
+
+    pub fn list_workspaces(&self) -> Vec<WorkspaceInfo> {
+        let mut list = vec![WorkspaceInfo {
+            workspace_id: 0,
+            label: Some("default".to_string()),
+        }];
+        if let Ok(workspaces) = self.workspaces.lock() {
+            list.extend(workspaces.entries.iter().map(|(&workspace_id, workspace)| WorkspaceInfo {
+                workspace_id,
+                label: workspace.label.clone(),
+            }));
+        }
+        list.sort_by_key(|workspace| workspace.workspace_id);
+        list
+    }
+
+    // Detaches every session in the workspace, then stops its worker thread.
+    pub async fn remove_workspace(&self, workspace_id: u64) -> Result<(), String> {
+        if workspace_id == 0 {
+            return Err(FridaError::InvalidInput("the default workspace cannot be removed".to_string()).into());
+        }
+        let workspace = self
+            .workspaces
+            .lock()
+            .map_err(|e| e.to_string())?
+            .entries
+            .remove(&workspace_id)
+            .ok_or_else(|| FridaError::InvalidInput(format!("unknown workspace {workspace_id}")))?;
+
+        let result = Self::dispatch(&workspace.queue, |ctx| {
+            let session_ids: Vec<u64> = ctx.sessions.keys().copied().collect();
+            for session_id in session_ids {
+                let _ = ctx.detach(session_id);
+            }
+            Ok(())
+        })
+        .await;
+        workspace.queue.close();
+        result
+    }
+
+    // Called on app exit. Every workspace unloads its scripts, detaches its sessions and stops
+    // its worker thread; waits up to `SHUTDOWN_TIMEOUT` overall so a hung device can't keep the
+    // app from quitting.
+    pub fn shutdown(&self) {
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        let mut queues = vec![self.queue.clone()];
+        if let Ok(mut workspaces) = self.workspaces.lock() {
+            queues.extend(workspaces.entries.drain().map(|(_, workspace)| workspace.queue));
+        }
+
+        let mut pending = Vec::new();
+        for queue in queues {
+            let (done_tx, done_rx) = channel::<()>();
+            let job: Job = Box::new(move |ctx| {
+                ctx.shutdown();
+                let _ = done_tx.send(());
+            });
+            if queue.push_final(job).is_ok() {
+                pending.push(done_rx);
+            }
+        }
+        for done_rx in pending {
+            if done_rx.recv_timeout(deadline.saturating_duration_since(Instant::now())).is_err() {
+                debug_log("shutdown: worker did not finish in time");
+            }
+        }
+    }
+
+    // IPC-friendly wrappers so the rest of the app doesn't need access to `FridaContext`.
+    pub async fn version(&self) -> Result<String, String> {
+        self.request(|ctx| Ok(ctx.version())).await
+    }
+
+    pub async fn host_info(&self) -> Result<HostInfo, String> {
+        let frida_core_version = self.version().await?;
+        Ok(HostInfo {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            build_profile: if cfg!(debug_assertions) { "debug" } else { "release" }.to_string(),
+            frida_core_version,
+        })
+    }
+
+    pub async fn list_devices(&self, workspace_id: Option<u64>) -> Result<Vec<DeviceInfo>, String> {
+        self.request_in(workspace_id, |ctx| Ok(ctx.list_devices())).await
+    }
+
+    pub async fn add_remote_device(&self, address: String, workspace_id: Option<u64>) -> Result<DeviceInfo, String> {
+        self.request_in(workspace_id, move |ctx| ctx.add_remote_device(&address)).await
+    }
+
+    pub async fn remove_remote_device(&self, address: String, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.remove_remote_device(&address)).await
+    }
+
+    pub async fn pin_device(
+        &self,
+        alias: String,
+        device_id: String,
+        workspace_id: Option<u64>,
+    ) -> Result<PinnedDevice, String> {
+        self.request_in(workspace_id, move |ctx| ctx.pin_device(alias, &device_id)).await
+    }
+
+    pub async fn unpin_device(&self, alias: String, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.unpin_device(&alias)).await
+    }
+
+    pub async fn list_pinned_devices(&self, workspace_id: Option<u64>) -> Result<Vec<PinnedDevice>, String> {
+        self.request_in(workspace_id, |ctx| Ok(ctx.list_pinned_devices())).await
+    }
+
+    pub async fn list_processes(
+        &self,
+        device_id: String,
+        workspace_id: Option<u64>,
+        filter: Option<String>,
+    ) -> Result<Vec<ProcessInfo>, String> {
+        self.request_in(workspace_id, move |ctx| {
+            let processes = ctx.list_processes(&device_id)?;
+            Ok(match filter {
+                Some(filter) => processes.into_iter().filter(|process| process_matches(process, &filter)).collect(),
+                None => processes,
+            })
+        })
+        .await
+    }
+
+    // With `freeze`, every target thread is suspended once the agent is up and stays that way
+    // until `frida_resume_target`. A thread frozen while holding a lock the agent needs (malloc,
+    // the loader) deadlocks further RPCs, so this is opt-in. `silent` skips the
+    // `frida_session_attached` event for callers that track session state from the return value.
+    pub async fn attach(
+        &self,
+        device_id: String,
+        pid: u32,
+        freeze: bool,
+        silent: bool,
+        workspace_id: Option<u64>,
+    ) -> Result<SessionInfo, String> {
+        self.request_in(workspace_id, move |ctx| {
+            let mut info = ctx.attach_with(&device_id, pid, silent)?;
+            if freeze {
+                match ctx.agent_request(info.script_id, "thread_suspend_all", json!({})) {
+                    Ok(frozen) => info.frozen = Some(frozen),
+                    Err(e) => {
+                        // The caller asked for a still target; don't hand back one that's running.
+                        let _ = ctx.detach_with_reason(info.session_id, "user", silent);
+                        return Err(format!("freeze_on_attach failed: {e}"));
+                    }
+                }
+            }
+            Ok(info)
+        })
+        .await
+    }
+
+    pub async fn attach_and_probe(
+        &self,
+        device_id: String,
+        pid: u32,
+        workspace_id: Option<u64>,
+    ) -> Result<AttachProbe, String> {
+        self.request_in(workspace_id, move |ctx| ctx.attach_and_probe(&device_id, pid))
+            .await
+    }
+
+    pub async fn detach(&self, session_id: u64, silent: bool) -> Result<(), String> {
+        self.request_for(session_id, move |ctx| ctx.detach_with_reason(session_id, "user", silent))
+            .await
+    }
+
+    pub async fn spawn(
+        &self,
+        device_id: String,
+        program: String,
+        argv: Option<Vec<String>>,
+        config: SpawnConfig,
+        workspace_id: Option<u64>,
+    ) -> Result<u32, String> {
+        self.request_in(workspace_id, move |ctx| ctx.spawn(&device_id, program, argv, config))
+            .await
+    }
+
+    pub async fn spawn_attach(
+        &self,
+        device_id: String,
+        program: String,
+        argv: Option<Vec<String>>,
+        config: SpawnConfig,
+        workspace_id: Option<u64>,
+    ) -> Result<SpawnAttachInfo, String> {
+        self.request_in(workspace_id, move |ctx| ctx.spawn_attach(&device_id, program, argv, config))
+            .await
+    }
+
+    pub async fn resume(&self, device_id: String, pid: u32, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.resume(&device_id, pid)).await
+    }
+
+    pub async fn kill(&self, device_id: String, pid: u32, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.kill(&device_id, pid)).await
+    }
+
+    pub async fn enable_spawn_gating(&self, device_id: String, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.enable_spawn_gating(&device_id)).await
+    }
+
+    pub async fn disable_spawn_gating(&self, device_id: String, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.disable_spawn_gating(&device_id)).await
+    }
+
+    pub async fn discard_spawn(
+        &self,
+        device_id: String,
+        pid: u32,
+        workspace_id: Option<u64>,
+    ) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.discard_spawn(&device_id, pid)).await
+    }
+
+    pub async fn kill_many(
+        &self,
+        device_id: String,
+        pids: Vec<u32>,
+        workspace_id: Option<u64>,
+    ) -> Result<Vec<KillResult>, String> {
+        self.request_in(workspace_id, move |ctx| ctx.kill_many(&device_id, &pids)).await
+    }
+
+    pub async fn load_default_script(
+        &self,
+        session_id: u64,
+        config: Option<serde_json::Value>,
+        runtime: Option<String>,
+        force: bool,
+    ) -> Result<ScriptInfo, String> {
+        let runtime = parse_js_runtime(runtime.as_deref())?;
+        self.request_for(session_id, move |ctx| {
+            ctx.load_default_script(session_id, config, runtime, force)
+        })
+        .await
+    }
+
+    pub async fn load_script(
+        &self,
+        session_id: u64,
+        source: String,
+        name: Option<String>,
+        runtime: Option<String>,
+    ) -> Result<ScriptInfo, String> {
+        let runtime = parse_js_runtime(runtime.as_deref())?;
+        self.request_for(session_id, move |ctx| {
+            ctx.load_custom_script(session_id, &source, name.as_deref(), runtime)
+        })
+        .await
+    }
+
+    pub async fn load_script_many(
+        &self,
+        session_ids: Vec<u64>,
+        source: String,
+        name: Option<String>,
+        config: Option<serde_json::Value>,
+    ) -> Result<Vec<ScriptLoadResult>, String> {
+        let workspace_id = session_ids.first().map_or(0, |id| id >> WORKSPACE_ID_SHIFT);
+        if session_ids.iter().any(|id| id >> WORKSPACE_ID_SHIFT != workspace_id) {
+            return Err(FridaError::InvalidInput("sessions must belong to one workspace".to_string()).into());
+        }
+        self.request_in(Some(workspace_id), move |ctx| {
+            ctx.load_script_many(&session_ids, &source, name.as_deref(), config.as_ref())
+        })
+        .await
+    }
+
+    pub async fn unload_script(&self, script_id: u64) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| ctx.unload_script(script_id)).await
+    }
+
+    pub async fn script_post(
+        &self,
+        script_id: u64,
+        message: serde_json::Value,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| ctx.script_post(script_id, message, data)).await
+    }
+
+    // For callers that already hold serialized JSON; `data` goes to the agent byte for byte.
+    pub async fn script_post_raw(
+        &self,
+        script_id: u64,
+        json_text: String,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| ctx.script_post_raw(script_id, &json_text, data))
+            .await
+    }
+
+    // Calls an `rpc.exports` function. The worker only posts the request; the reply is awaited
+    // here so a slow export doesn't hold up other jobs.
+    pub async fn script_rpc_call(
+        &self,
+        script_id: u64,
+        method: String,
+        args: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let name = method.clone();
+        let (id, reply_rx) = self
+            .request_for(script_id, move |ctx| ctx.post_rpc_call(script_id, &method, args))
+            .await?;
+        Self::await_rpc_reply(id, reply_rx, &format!("rpc call {name}")).await
+    }
+
+    // Names of the script's `rpc.exports`, via the protocol's `list` op.
+    pub async fn script_list_exports(&self, script_id: u64) -> Result<Vec<String>, String> {
+        let (id, reply_rx) = self
+            .request_for(script_id, move |ctx| ctx.post_rpc_request(script_id, vec![json!("list")]))
+            .await?;
+        let names = Self::await_rpc_reply(id, reply_rx, "rpc list").await?;
+        serde_json::from_value(names).map_err(|e| format!("Unexpected rpc list reply: {e}"))
+    }
+
+    async fn await_rpc_reply(
+        id: u64,
+        reply_rx: Receiver<AgentReply>,
+        what: &str,
+    ) -> Result<serde_json::Value, String> {
+        Self::await_reply(id, reply_rx, RPC_CALL_TIMEOUT, what).await
+    }
+
+    async fn await_reply(
+        id: u64,
+        reply_rx: Receiver<AgentReply>,
+        timeout: Duration,
+        what: &str,
+    ) -> Result<serde_json::Value, String> {
+        let reply = tauri::async_runtime::spawn_blocking(move || reply_rx.recv_timeout(timeout))
+            .await
+            .map_err(|e| e.to_string());
+        unregister_pending_reply(id);
+        match reply? {
+            Ok(reply) => reply.map(|(returns, _)| returns),
+            Err(_) => Err(format!("{what} timed out after {}s", timeout.as_secs())),
+        }
+    }
+
+    pub async fn enable_debugger(
+        &self,
+        script_id: u64,
+        port: Option<u16>,
+    ) -> Result<DebuggerInfo, String> {
+        self.request_for(script_id, move |ctx| ctx.enable_debugger(script_id, port)).await
+    }
+
+    pub async fn disable_debugger(&self, script_id: u64) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| ctx.disable_debugger(script_id)).await
+    }
+
+    pub async fn device_capabilities(
+        &self,
+        device_id: String,
+        workspace_id: Option<u64>,
+    ) -> Result<DeviceCapabilities, String> {
+        self.request_in(workspace_id, move |ctx| ctx.device_capabilities(&device_id)).await
+    }
+
+    pub async fn query_system_parameters(
+        &self,
+        device_id: String,
+        workspace_id: Option<u64>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_in(workspace_id, move |ctx| ctx.query_system_parameters(&device_id)).await
+    }
+
+    pub async fn agent_capabilities(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "get_capabilities", serde_json::Value::Null))
+            .await
+    }
+
+    pub async fn tail_file(&self, script_id: u64, path: String) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.tail_file(script_id, path)).await
+    }
+
+    pub async fn stop_tail(&self, script_id: u64, tail_id: String) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.agent_request(script_id, "file_tail_stop", json!({ "tailId": tail_id }))
+                .map(|_| ())
+        })
+        .await
+    }
+
+    pub async fn get_agent_log_level(&self, script_id: u64) -> Result<String, String> {
+        self.request_for(script_id, move |ctx| ctx.get_agent_log_level(script_id)).await
+    }
+
+    pub async fn set_agent_log_level(&self, script_id: u64, level: String) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| ctx.set_agent_log_level(script_id, &level)).await
+    }
+
+    pub async fn recent_events(
+        &self,
+        limit: Option<usize>,
+        workspace_id: Option<u64>,
+    ) -> Result<Vec<AuditedEvent>, String> {
+        let limit = limit.unwrap_or(EVENT_AUDIT_CAPACITY);
+        self.request_in(workspace_id, move |ctx| Ok(ctx.recent_events(limit))).await
+    }
+
+    pub async fn ping_agent(&self, script_id: u64, samples: Option<u32>) -> Result<PingStats, String> {
+        self.request_for(script_id, move |ctx| ctx.ping_agent(script_id, samples)).await
+    }
+
+    pub async fn set_agent_autoreload(
+        &self,
+        enabled: bool,
+        path: Option<String>,
+        workspace_id: Option<u64>,
+    ) -> Result<Option<String>, String> {
+        self.request_in(workspace_id, move |ctx| ctx.set_agent_autoreload(enabled, path)).await
+    }
+
+    pub async fn enumerate_ranges(
+        &self,
+        script_id: u64,
+        protection: Option<String>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let protection = protection.unwrap_or_else(|| "r--".to_string());
+            ctx.agent_request(script_id, "enumerate_ranges", json!({ "protection": protection }))
+        })
+        .await
+    }
+
+    pub async fn dump_region(
+        &self,
+        script_id: u64,
+        address: String,
+        size: u64,
+        path: String,
+        overwrite: bool,
+    ) -> Result<DumpInfo, String> {
+        self.request_for(script_id, move |ctx| ctx.dump_region(script_id, &address, size, path, overwrite))
+            .await
+    }
+
+    pub async fn export_memory_map(
+        &self,
+        script_id: u64,
+        path: String,
+        format: MemoryMapFormat,
+        overwrite: bool,
+    ) -> Result<MemoryMapExport, String> {
+        self.request_for(script_id, move |ctx| ctx.export_memory_map(script_id, path, format, overwrite))
+            .await
+    }
+
+    pub async fn replace_function(
+        &self,
+        script_id: u64,
+        spec: FunctionReplacementSpec,
+    ) -> Result<FunctionReplacement, String> {
+        self.request_for(script_id, move |ctx| ctx.replace_function(script_id, spec)).await
+    }
+
+    pub async fn restore_function(&self, script_id: u64, id: String) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            validate_no_nul("id", &id)?;
+            ctx.agent_request(script_id, "interceptor_restore_function", json!({ "id": id }))
+                .map(|_| ())
+        })
+        .await
+    }
+
+    pub async fn set_session_idle_timeout(&self, session_id: u64, timeout_ms: u64) -> Result<(), String> {
+        self.request_for(session_id, move |ctx| ctx.set_session_idle_timeout(session_id, timeout_ms))
+            .await
+    }
+
+    pub async fn set_reload_on_reattach(&self, session_id: u64, enabled: bool) -> Result<(), String> {
+        self.request_for(session_id, move |ctx| ctx.set_reload_on_reattach(session_id, enabled))
+            .await
+    }
+
+    pub async fn export_layout(&self, workspace_id: Option<u64>) -> Result<Vec<SessionLayout>, String> {
+        self.request_in(workspace_id, |ctx| Ok(ctx.export_layout())).await
+    }
+
+    pub async fn import_layout(
+        &self,
+        layout: Vec<SessionLayout>,
+        workspace_id: Option<u64>,
+    ) -> Result<Vec<LayoutImportResult>, String> {
+        self.request_in(workspace_id, move |ctx| Ok(ctx.import_layout(layout))).await
+    }
+
+    // Restart session/script ids at 1 so tests can assert on them. Refused while anything is
+    // attached, since live ids would then collide.
+    #[cfg(feature = "test-hooks")]
+    pub async fn reset_ids(&self) -> Result<(), String> {
+        self.request(|ctx| ctx.reset_ids()).await
+    }
+
+    #[cfg(feature = "test-hooks")]
+    pub async fn set_clock(&self, clock: impl Clock + 'static) -> Result<(), String> {
+        self.request(move |ctx| {
+            ctx.clock = Box::new(clock);
+            ctx.process_list_cache.clear();
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn set_event_rate_limit(&self, event: String, max_per_sec: Option<u32>) -> Result<(), String> {
+        self.request(move |ctx| ctx.set_event_rate_limit(&event, max_per_sec)).await
+    }
+
+    // Unset markers keep their current setting. Returns the routing now in effect.
+    pub fn set_other_message_routing(
+        &self,
+        rpc_reply: Option<bool>,
+        ack: Option<bool>,
+        chunk: Option<bool>,
+    ) -> OtherMessageRouting {
+        let mut routing = OTHER_MESSAGE_ROUTING.lock().unwrap_or_else(|e| e.into_inner());
+        routing.rpc_reply = rpc_reply.unwrap_or(routing.rpc_reply);
+        routing.ack = ack.unwrap_or(routing.ack);
+        routing.chunk = chunk.unwrap_or(routing.chunk);
+        *routing
+    }
+
+    pub fn other_message_routing(&self) -> OtherMessageRouting {
+        *OTHER_MESSAGE_ROUTING.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_eval_enabled(&self, enabled: bool) {
+        EVAL_ENABLED.store(enabled, Ordering::SeqCst);
+    }
+
+    // REPL primitive: evaluates `expression` in the default agent's global scope.
+    pub async fn eval(&self, script_id: u64, expression: String) -> Result<serde_json::Value, String> {
+        if !EVAL_ENABLED.load(Ordering::SeqCst) {
+            return Err(
+                FridaError::InvalidInput("eval is disabled; enable it with frida_set_eval_enabled".to_string()).into(),
+            );
+        }
+        self.request_for(script_id, move |ctx| {
+            if expression.trim().is_empty() {
+                return Err(FridaError::InvalidInput("expression cannot be empty".to_string()).into());
+            }
+            if expression.len() > MAX_EVAL_EXPRESSION_LEN {
+                return Err(FridaError::InvalidInput(format!(
+                    "expression exceeds {MAX_EVAL_EXPRESSION_LEN} bytes"
+                ))
+                .into());
+            }
+            ctx.agent_request(script_id, "eval", json!({ "expression": expression }))
+        })
+        .await
+    }
+
+    pub async fn list_agent_operations(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "list_operations", json!({})))
+            .await
+    }
+
+    pub async fn cancel_agent_operation(&self, script_id: u64, op_id: String) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if op_id.trim().is_empty() {
+                return Err(FridaError::InvalidInput("op_id cannot be empty".to_string()).into());
+            }
+            ctx.agent_request(script_id, "cancel_operation", json!({ "opId": op_id }))
+        })
+        .await
+    }
+
+    pub async fn list_fds(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "enumerate_fds", json!({})))
+            .await
+    }
+
+    pub async fn set_agent_load_retry(
+        &self,
+        retries: u32,
+        delay_ms: u64,
+        workspace_id: Option<u64>,
+    ) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| {
+            ctx.agent_load_retries = retries.min(MAX_AGENT_LOAD_RETRIES);
+            ctx.agent_load_retry_delay = Duration::from_millis(delay_ms).min(MAX_AGENT_LOAD_RETRY_DELAY);
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn set_device_label(
+        &self,
+        device_id: String,
+        label: DeviceLabel,
+        workspace_id: Option<u64>,
+    ) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.set_device_label(&device_id, label)).await
+    }
+
+    pub async fn validate_script(
+        &self,
+        source: String,
+        workspace_id: Option<u64>,
+    ) -> Result<ScriptValidation, String> {
+        self.request_in(workspace_id, move |ctx| ctx.validate_script(&source)).await
+    }
+
+    // Applied directly to the workspace's queue (not as a job) so it takes effect even when
+    // backed up. Each workspace has its own queue and new ones start unbounded.
+    pub fn set_queue_policy(
+        &self,
+        policy: QueuePolicy,
+        capacity: Option<usize>,
+        workspace_id: Option<u64>,
+    ) -> Result<(), String> {
+        let capacity = capacity.unwrap_or(DEFAULT_QUEUE_CAPACITY);
+        if policy != QueuePolicy::Unbounded && capacity == 0 {
+            return Err(FridaError::InvalidInput("capacity must be at least 1".to_string()).into());
+        }
+        self.queue_for(workspace_id)?.set_policy(policy, capacity);
+        Ok(())
+    }
+
+    pub async fn find_pointers(
+        &self,
+        script_id: u64,
+        address: String,
+        limit: Option<u32>,
+        ranges: Option<Vec<SearchRange>>,
+    ) -> Result<serde_json::Value, String> {
+        // Only the request is posted on the worker; the scan can run far past
+        // `AGENT_REQUEST_TIMEOUT`, so its reply is awaited here like an rpc call's.
+        let (id, reply_rx) = self
+            .request_for(script_id, move |ctx| {
+                let address = format!("{:#x}", parse_address(&address)?);
+                let mut params = json!({ "address": address });
+                if let Some(limit) = limit {
+                    params["limit"] = json!(limit);
+                }
+                if let Some(ranges) = ranges {
+                    let ranges = ranges
+                        .iter()
+                        .map(|range| {
+                            let base = parse_address(&range.base)?;
+                            Ok(json!({ "base": format!("{base:#x}"), "size": range.size }))
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    params["ranges"] = json!(ranges);
+                }
+                ctx.post_agent_request(script_id, "find_pointers", params)
+            })
+            .await?;
+        Self::await_reply(id, reply_rx, POINTER_SEARCH_TIMEOUT, "pointer search").await
+    }
+
+    pub async fn set_script_binary_channel(&self, script_id: u64, channel: Option<Channel>) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            if !ctx.scripts.contains_key(&script_id) {
+                return Err(FridaError::ScriptNotFound(script_id).into());
+            }
+            set_binary_channel(script_id, channel);
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn list_hooks(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "interceptor_list", json!({})))
+            .await
+    }
+
+    pub async fn set_hook_enabled(&self, script_id: u64, hook_id: String, enabled: bool) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.agent_request(
+                script_id,
+                "interceptor_set_enabled",
+                json!({ "id": hook_id, "enabled": enabled }),
+            )
+            .map(|_| ())
+        })
+        .await
+    }
+
+    pub async fn main_module(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "get_main_module", json!({})))
+            .await
+    }
+
+    pub async fn agent_gc(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "gc", json!({}))).await
+    }
+
+    pub async fn symbolicate(&self, script_id: u64, addresses: Vec<String>) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if addresses.len() > MAX_SYMBOLICATE_ADDRESSES {
+                return Err(FridaError::InvalidInput(format!(
+                    "at most {MAX_SYMBOLICATE_ADDRESSES} addresses per call"
+                ))
+                .into());
+            }
+            let addresses = addresses
+                .iter()
+                .map(|address| parse_address(address).map(|a| format!("{a:#x}")))
+                .collect::<Result<Vec<_>, _>>()?;
+            ctx.agent_request(script_id, "symbolicate", json!({ "addresses": addresses }))
+        })
+        .await
+    }
+
+    pub async fn attach_by_name(
+        &self,
+        device_id: String,
+        process_name: String,
+        workspace_id: Option<u64>,
+    ) -> Result<SessionInfo, String> {
+        self.request_in(workspace_id, move |ctx| ctx.attach_by_name(&device_id, &process_name))
+            .await
+    }
+
+    pub async fn attach_by_identifier(
+        &self,
+        device_id: String,
+        identifier: String,
+        spawn: bool,
+        workspace_id: Option<u64>,
+    ) -> Result<IdentifierAttachInfo, String> {
+        self.request_in(workspace_id, move |ctx| {
+            ctx.attach_by_identifier(&device_id, &identifier, spawn)
+        })
+        .await
+    }
+
+    pub async fn last_script_error(&self, script_id: u64) -> Result<Option<ScriptError>, String> {
+        self.request_for(script_id, move |ctx| {
+            if !ctx.scripts.contains_key(&script_id) {
+                return Err(FridaError::ScriptNotFound(script_id).into());
+            }
+            Ok(last_script_error(script_id))
+        })
+        .await
+    }
+
+    // Enabling starts from zero; disabling drops the tallies.
+    pub async fn set_script_stats_enabled(&self, script_id: u64, enabled: bool) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.live_script(script_id)?;
+            if let Ok(mut registry) = get_handler_registry().lock() {
+                if enabled {
+                    registry.message_stats.entry(script_id).or_default();
+                } else {
+                    registry.message_stats.remove(&script_id);
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn set_message_batching(&self, script_id: u64, window_ms: u64) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| ctx.set_message_batching(script_id, window_ms))
+            .await
+    }
+
+    pub async fn script_stats(&self, script_id: u64) -> Result<ScriptStats, String> {
+        self.request_for(script_id, move |ctx| {
+            let script = ctx.scripts.get(&script_id).ok_or(FridaError::ScriptNotFound(script_id))?;
+            Ok(ScriptStats {
+                script_id,
+                messages_in: script.messages_in.load(Ordering::Relaxed),
+                messages_out: script.messages_out,
+                loaded_at_ms: script.loaded_at_ms,
+            })
+        })
+        .await
+    }
+
+    pub async fn script_message_stats(&self, script_id: u64) -> Result<Option<MessageStats>, String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.live_script(script_id)?;
+            Ok(with_message_stats(script_id, |stats| *stats))
+        })
+        .await
+    }
+
+    pub async fn reset_script_stats(&self, script_id: u64) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.live_script(script_id)?;
+            with_message_stats(script_id, |stats| *stats = MessageStats::default());
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn suspend_target(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "thread_suspend_all", json!({})))
+            .await
+    }
+
+    pub async fn resume_target(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "thread_resume_all", json!({})))
+            .await
+    }
+
+    pub async fn memory_summary(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "memory_summary", json!({})))
+            .await
+    }
+
+    pub async fn search_methods(
+        &self,
+        script_id: u64,
+        runtime: String,
+        pattern: String,
+        regex: bool,
+        limit: Option<u32>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let method = match runtime.as_str() {
+                "java" => "java_search_methods",
+                "objc" => "objc_search_methods",
+                other => {
+                    return Err(FridaError::InvalidInput(format!("runtime must be java or objc, got {other}")).into())
+                }
+            };
+            if pattern.trim().is_empty() || pattern.len() > MAX_METHOD_PATTERN_LEN {
+                return Err(
+                    FridaError::InvalidInput(format!("pattern must be 1-{MAX_METHOD_PATTERN_LEN} characters")).into(),
+                );
+            }
+            // Regex syntax is checked by the agent's RegExp; globs only need the separator shape.
+            if !regex && pattern.matches('!').count() > 1 {
+                return Err(FridaError::InvalidInput("glob pattern must be class!method".to_string()).into());
+            }
+
+            let limit = limit.unwrap_or(DEFAULT_METHOD_SEARCH_LIMIT).clamp(1, MAX_METHOD_SEARCH_LIMIT);
+            ctx.agent_request(
+                script_id,
+                method,
+                json!({
+                    "pattern": pattern,
+                    "syntax": if regex { "regex" } else { "glob" },
+                    "limit": limit,
+                }),
+            )
+        })
+        .await
+    }
+
+    // `paired` hooks emit a single `frida_hook_call` per call, carrying the entry arguments,
+    // `capture`d argument strings and the return value together.
+    pub async fn hook_function(
+        &self,
+        script_id: u64,
+        target: String,
+        condition: Option<HookCondition>,
+        paired: bool,
+        capture: Vec<u32>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if target.is_empty() {
+                return Err(FridaError::InvalidInput("target cannot be empty".to_string()).into());
+            }
+            if !capture.is_empty() && !paired {
+                return Err(FridaError::InvalidInput("capture requires paired".to_string()).into());
+            }
+            if capture.iter().any(|&i| i > MAX_HOOK_ARG_INDEX) {
+                return Err(
+                    FridaError::InvalidInput(format!("capture indices must be at most {MAX_HOOK_ARG_INDEX}")).into(),
+                );
+            }
+            let mut params = json!({ "target": target, "paired": paired, "capture": capture });
+            if let Some(condition) = condition {
+                validate_hook_condition(&condition).map_err(FridaError::InvalidInput)?;
+                params["condition"] = json!({
+                    "argIndex": condition.arg_index,
+                    "op": condition.op,
+                    "value": condition.value,
+                });
+            }
+            ctx.agent_request(script_id, "interceptor_attach", params)
+        })
+        .await
+    }
+
+    // `address` is an object whose first word is its vtable pointer, or the vtable itself with
+    // `vtable`. Returns the hook id and the resolved function address.
+    pub async fn hook_vtable(
+        &self,
+        script_id: u64,
+        address: String,
+        index: u32,
+        arg_count: u32,
+        vtable: bool,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let address = parse_address(&address)?;
+            if address == 0 {
+                return Err(FridaError::InvalidInput("address cannot be null".to_string()).into());
+            }
+            if index > MAX_VTABLE_INDEX {
+                return Err(FridaError::InvalidInput(format!("index must be at most {MAX_VTABLE_INDEX}")).into());
+            }
+            if arg_count > MAX_HOOK_ARG_INDEX + 1 {
+                return Err(
+                    FridaError::InvalidInput(format!("arg_count must be at most {}", MAX_HOOK_ARG_INDEX + 1)).into(),
+                );
+            }
+            ctx.agent_request(
+                script_id,
+                "interceptor_hook_vtable",
+                json!({
+                    "address": format!("{address:#x}"),
+                    "index": index,
+                    "argCount": arg_count,
+                    "vtable": vtable,
+                }),
+            )
+        })
+        .await
+    }
+
+    pub async fn script_source(&self, script_id: u64, hash_only: bool) -> Result<ScriptSource, String> {
+        self.request_for(script_id, move |ctx| {
+            let script = ctx.scripts.get(&script_id).ok_or(FridaError::ScriptNotFound(script_id))?;
+            let size = script.source.len();
+            Ok(ScriptSource {
+                script_id,
+                name: script.name.clone(),
+                is_default: script.is_default,
+                size,
+                hash: source_hash(&script.source),
+                source: (!hash_only && size <= MAX_SCRIPT_SOURCE_LEN).then(|| script.source.to_string()),
+            })
+        })
+        .await
+    }
+
+    pub async fn address_to_offset(&self, script_id: u64, address: String) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let address = parse_address(&address)?;
+            ctx.agent_request(script_id, "address_to_offset", json!({ "address": format!("{address:#x}") }))
+        })
+        .await
+    }
+
+    pub async fn describe_address(&self, script_id: u64, address: String) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let address = parse_address(&address)?;
+            ctx.agent_request(script_id, "describe_address", json!({ "address": format!("{address:#x}") }))
+        })
+        .await
+    }
+
+    pub async fn offset_to_address(
+        &self,
+        script_id: u64,
+        module: String,
+        offset: String,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if module.is_empty() {
+                return Err(FridaError::InvalidInput("module cannot be empty".to_string()).into());
+            }
+            let offset = parse_address(&offset)?;
+            ctx.agent_request(
+                script_id,
+                "offset_to_address",
+                json!({ "module": module, "offset": format!("{offset:#x}") }),
+            )
+        })
+        .await
+    }
+
+    pub async fn read_struct(
+        &self,
+        script_id: u64,
+        address: String,
+        layout: Vec<StructField>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let address = parse_address(&address)?;
+            validate_struct_layout(&layout).map_err(FridaError::InvalidInput)?;
+            let fields: Vec<serde_json::Value> = layout
+                .iter()
+                .map(|field| json!({ "name": field.name, "kind": field.kind, "offset": field.offset }))
+                .collect();
+            ctx.agent_request(
+                script_id,
+                "read_struct",
+                json!({ "address": format!("{address:#x}"), "fields": fields }),
+            )
+        })
+        .await
+    }
+
+    // The agent also records a load/unload timeline of up to `timeline_cap` entries
+    // (default 1000), drained with `module_timeline`.
+    pub async fn watch_modules(&self, script_id: u64, timeline_cap: Option<u32>) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            if timeline_cap.is_some_and(|cap| cap == 0 || cap > MAX_MODULE_TIMELINE_CAP) {
+                return Err(
+                    FridaError::InvalidInput(format!("timeline_cap must be 1-{MAX_MODULE_TIMELINE_CAP}")).into(),
+                );
+            }
+            let mut params = json!({});
+            if let Some(cap) = timeline_cap {
+                params["timelineCap"] = json!(cap);
+            }
+            ctx.agent_request(script_id, "attach_module_observer", params).map(|_| ())
+        })
+        .await
+    }
+
+    pub async fn module_timeline(&self, script_id: u64, drain: bool) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.agent_request(script_id, "module_timeline", json!({ "drain": drain }))
+        })
+        .await
+    }
+
+    pub async fn unwatch_modules(&self, script_id: u64) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.agent_request(script_id, "detach_module_observer", json!({})).map(|_| ())
+        })
+        .await
+    }
+
+    pub async fn reset(&self, workspace_id: Option<u64>) -> Result<ResetSummary, String> {
+        self.request_in(workspace_id, |ctx| Ok(ctx.reset())).await
+    }
+
+    pub async fn list_symbols(
+        &self,
+        script_id: u64,
+        module: String,
+        offset: u32,
+        limit: Option<u32>,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if module.is_empty() {
+                return Err(FridaError::InvalidInput("module cannot be empty".to_string()).into());
+            }
+            let limit = limit.unwrap_or(DEFAULT_SYMBOL_PAGE_LIMIT);
+            if limit == 0 || limit > MAX_SYMBOL_PAGE_LIMIT {
+                return Err(FridaError::InvalidInput(format!("limit must be 1-{MAX_SYMBOL_PAGE_LIMIT}")).into());
+            }
+            ctx.agent_request(
+                script_id,
+                "list_symbols",
+                json!({ "module": module, "offset": offset, "limit": limit }),
+            )
+        })
+        .await
+    }
+
+    pub async fn module_dependencies(&self, script_id: u64, module: String) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if module.is_empty() {
+                return Err(FridaError::InvalidInput("module cannot be empty".to_string()).into());
+            }
+            ctx.agent_request(script_id, "module_dependencies", json!({ "module": module }))
+        })
+        .await
+    }
+
+    pub async fn set_hook_format(&self, script_id: u64, format: String) -> Result<(), String> {
+        self.request_for(script_id, move |ctx| {
+            if format != "verbose" && format != "compact" {
+                return Err(FridaError::InvalidInput(format!("format must be verbose or compact, got {format}")).into());
+            }
+            ctx.agent_request(script_id, "interceptor_set_format", json!({ "format": format }))
+                .map(|_| ())
+        })
+        .await
+    }
+
+    pub async fn thread_context(&self, script_id: u64, thread_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.agent_request(script_id, "thread_context", json!({ "threadId": thread_id }))
+        })
+        .await
+    }
+
+    pub async fn hook_module(
+        &self,
+        script_id: u64,
+        module: String,
+        options: ModuleHookOptions,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            if module.is_empty() {
+                return Err(FridaError::InvalidInput("module cannot be empty".to_string()).into());
+            }
+            let limit = options.limit.unwrap_or(DEFAULT_MODULE_HOOK_LIMIT);
+            if limit == 0 || limit > MAX_MODULE_HOOK_LIMIT {
+                return Err(FridaError::InvalidInput(format!("limit must be 1-{MAX_MODULE_HOOK_LIMIT}")).into());
+            }
+            ctx.agent_request(
+                script_id,
+                "interceptor_hook_module",
+                json!({
+                    "module": module,
+                    "include": options.include,
+                    "exclude": options.exclude,
+                    "limit": limit,
+                    "dryRun": options.dry_run,
+                }),
+            )
+        })
+        .await
+    }
+
+    pub async fn unhook_module(&self, script_id: u64, module: String) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            ctx.agent_request(script_id, "interceptor_unhook_module", json!({ "module": module }))
+        })
+        .await
+    }
+
+    pub async fn track_allocations(
+        &self,
+        script_id: u64,
+        options: AllocationTrackOptions,
+    ) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            // An absent interval must be omitted, not null, or the agent's default doesn't apply.
+            let mut params = json!({ "minSize": options.min_size });
+            if let Some(interval_ms) = options.interval_ms {
+                params["intervalMs"] = json!(interval_ms);
+            }
+            ctx.agent_request(script_id, "allocation_track_start", params)
+        })
+        .await
+    }
+
+    pub async fn stop_tracking_allocations(&self, script_id: u64) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| ctx.agent_request(script_id, "allocation_track_stop", json!({})))
+            .await
+    }
+
+    pub async fn allocation_snapshot(&self, script_id: u64, limit: Option<u32>) -> Result<serde_json::Value, String> {
+        self.request_for(script_id, move |ctx| {
+            let limit = limit.unwrap_or(DEFAULT_ALLOCATION_SNAPSHOT_LIMIT);
+            if limit == 0 || limit > MAX_ALLOCATION_SNAPSHOT_LIMIT {
+                return Err(
+                    FridaError::InvalidInput(format!("limit must be 1-{MAX_ALLOCATION_SNAPSHOT_LIMIT}")).into(),
+                );
+            }
+            ctx.agent_request(script_id, "allocation_snapshot", json!({ "limit": limit }))
+        })
+        .await
+    }
+
+    // Per workspace; 0 disables the cache.
+    pub async fn set_process_list_cache_ttl(&self, millis: u64, workspace_id: Option<u64>) -> Result<(), String> {
+        self.request_in(workspace_id, move |ctx| ctx.set_process_list_cache_ttl(Duration::from_millis(millis)))
+            .await
+    }
+
+    pub async fn process_count(&self, device_id: String, workspace_id: Option<u64>) -> Result<ProcessCount, String> {
+        self.request_in(workspace_id, move |ctx| ctx.process_count(&device_id)).await
+    }
+
+    pub async fn script_load_stats(&self, workspace_id: Option<u64>) -> Result<ScriptLoadStats, String> {
+        self.request_in(workspace_id, |ctx| Ok(ctx.script_load_stats)).await
+    }
+
+    pub async fn list_watches(&self, script_id: u64) -> Result<Vec<WatchInfo>, String> {
+        self.request_for(script_id, move |ctx| ctx.list_watches(script_id)).await
+    }
+
+    pub async fn stop_all_watches(
+        &self,
+        script_id: u64,
+        include_pid_watches: bool,
+    ) -> Result<Vec<WatchInfo>, String> {
+        self.request_for(script_id, move |ctx| ctx.stop_all_watches(script_id, include_pid_watches))
+            .await
+    }
+
+    pub async fn watch_pid(&self, device_id: String, pid: u32, workspace_id: Option<u64>) -> Result<PidWatch, String> {
+        self.request_in(workspace_id, move |ctx| ctx.watch_pid(&device_id, pid)).await
+    }
+
+    pub async fn unwatch_pid(&self, device_id: String, pid: u32, workspace_id: Option<u64>) -> Result<bool, String> {
+        self.request_in(workspace_id, move |ctx| ctx.unwatch_pid(&device_id, pid)).await
+    }
+
+    // Frida has no "process appeared" signal outside spawn gating, which only sees spawns on
+    // devices that support it, so poll fresh enumerations. Each poll is its own worker job and
+    // the sleeps happen off the worker, so other commands keep flowing while we wait.
+    pub async fn wait_and_attach(
+        &self,
+        device_id: String,
+        matcher: String,
+        timeout_ms: u64,
+        workspace_id: Option<u64>,
+    ) -> Result<SessionInfo, String> {
+        validate_no_nul("matcher", &matcher)?;
+        if matcher.is_empty() {
+            return Err(FridaError::InvalidInput("matcher cannot be empty".to_string()).into());
+        }
+        let timeout = Duration::from_millis(timeout_ms);
+        if timeout > MAX_WAIT_ATTACH_TIMEOUT {
+            return Err(FridaError::InvalidInput(format!(
+                "timeout_ms must be at most {}",
+                MAX_WAIT_ATTACH_TIMEOUT.as_millis()
+            ))
+            .into());
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let (device, name) = (device_id.clone(), matcher.clone());
+            let found = self
+                .request_in(workspace_id, move |ctx| ctx.find_process_fresh(&device, &name))
+                .await?;
+            if let Some(pid) = found {
+                let device = device_id.clone();
+                return self.request_in(workspace_id, move |ctx| ctx.attach(&device, pid)).await;
+            }
+            if Instant::now() >= deadline {
+                return Err(FridaError::Timeout.into());
+            }
+            tauri::async_runtime::spawn_blocking(|| std::thread::sleep(WAIT_ATTACH_POLL_INTERVAL))
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    async fn request<T, F>(&self, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut FridaContext) -> Result<T, String> + Send + 'static,
+    {
+        Self::dispatch(&self.queue, f).await
+    }
+
+    async fn request_in<T, F>(&self, workspace_id: Option<u64>, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut FridaContext) -> Result<T, String> + Send + 'static,
+    {
+        let queue = self.queue_for(workspace_id)?;
+        Self::dispatch(&queue, f).await
+    }
+
+    // `None` and 0 are the default workspace.
+    fn queue_for(&self, workspace_id: Option<u64>) -> Result<Arc<JobQueue>, String> {
+        match workspace_id {
+            None | Some(0) => Ok(self.queue.clone()),
+            Some(workspace_id) => self
+                .workspaces
+                .lock()
+                .map_err(|e| e.to_string())?
+                .entries
+                .get(&workspace_id)
+                .map(|workspace| workspace.queue.clone())
+                .ok_or_else(|| {
+                    FridaError::InvalidInput(format!("unknown workspace {workspace_id}")).to_string()
+                }),
+        }
+    }
+
+    // Session and script ids carry their workspace in the high bits (see `FridaContext::new`).
+    async fn request_for<T, F>(&self, id: u64, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut FridaContext) -> Result<T, String> + Send + 'static,
+    {
+        self.request_in(Some(id >> WORKSPACE_ID_SHIFT), f).await
+    }
+
+    async fn dispatch<T, F>(queue: &JobQueue, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut FridaContext) -> Result<T, String> + Send + 'static,
+    {
+        let (reply_tx, reply_rx) = channel::<Result<T, String>>();
+
+        let job: Job = Box::new(move |ctx| {
+            let result = f(ctx);
+            let _ = reply_tx.send(result);
+        });
+
+        queue.push(job)?;
+
+        let recv_result = tauri::async_runtime::spawn_blocking(move || reply_rx.recv())
+            .await
+            .map_err(|_| "Failed to wait for Frida worker response".to_string())?;
+
+        recv_result.map_err(|_| {
+            if queue.is_closed() {
+                "Frida worker thread closed".to_string()
+            } else {
+                "Frida worker did not respond".to_string()
+            }
+        })?
+    }
+}
+
+struct FridaContext {
+    app: tauri::AppHandle,
+    sessions: HashMap<u64, SessionRecord>,
+    scripts: HashMap<u64, ScriptRecord>,
+    next_session_id: u64,
+    next_script_id: u64,
+    next_reply_id: u64,
+    // Spawned-but-not-yet-resumed processes, keyed by (device_id, pid).
+    pending_spawns: HashSet<(String, u32)>,
+    // alias -> stable device properties, so saved workflows survive id changes on reconnect.
+    pinned_devices: HashMap<String, PinnedDevice>,
+    // device_id -> label, mirrored to DEVICE_LABELS_FILE.
+    device_labels: HashMap<String, DeviceLabel>,
+    event_audit: EventAudit,
+    agent_watch: Option<AgentWatch>,
+    // device_id -> last enumeration, so badges for several devices don't evict each other.
+    process_list_cache: HashMap<String, ProcessListCache>,
+    process_list_cache_ttl: Duration,
+    // Scripts of sessions that dropped with reload-on-reattach enabled, keyed by (device_id, pid)
+    // and replayed by the next attach to that process. `poll_pid_watches` drops exited pids.
+    reattach_scripts: HashMap<(String, u32), Vec<ScriptLayout>>,
+    // Pids to report `frida_pid_exited` for, keyed by (device_id, pid); no session needed.
+    pid_watches: HashSet<(String, u32)>,
+    pid_watch_checked: Instant,
+    // device_id -> pids seen at the last poll, for devices with spawn gating enabled.
+    spawn_gating: HashMap<String, HashSet<u32>>,
+    spawn_gating_checked: Instant,
+    // Devices seen at the last hotplug poll; `None` until the first poll (default workspace only).
+    known_devices: Option<HashMap<String, DeviceInfo>>,
+    device_poll_checked: Instant,
+    // address -> device id for devices added with `frida_add_remote_device`.
+    remote_devices: HashMap<String, String>,
+    script_load_stats: ScriptLoadStats,
+    agent_load_retries: u32,
+    agent_load_retry_delay: Duration,
+    clock: Box<dyn Clock>,
+    // Session/script ids start at `workspace_id << WORKSPACE_ID_SHIFT`.
+    id_base: u64,
+    device_manager: DeviceManager<'static>,
+    // Never dropped: dropping deinitializes the process-wide Frida runtime that other
+    // workspaces are still using.
+    _frida: ManuallyDrop<Frida>,
+}
+
+// Closes a worker's queue when its thread exits, so requests fail instead of blocking forever.
+struct QueueCloser(Arc<JobQueue>);
+
+impl Drop for QueueCloser {
+    fn drop(&mut self) {
+        self.0.close_and_drain();
+    }
+}
+
+fn spawn_context(app: tauri::AppHandle, workspace_id: u64) -> Arc<JobQueue> {
+    let queue = Arc::new(JobQueue::new());
+    let worker_queue = queue.clone();
+
+    std::thread::spawn(move || {
+        // Runs however the thread ends, including a panic in a job.
+        let _closer = QueueCloser(worker_queue.clone());
+        let mut ctx = FridaContext::new(app, workspace_id);
+        ctx.run(&worker_queue);
+    });
+
+    queue
+}
+
+impl FridaContext {
+    fn new(app: tauri::AppHandle, workspace_id: u64) -> Self {
+        let frida = unsafe { Frida::obtain() };
+        let dm = DeviceManager::obtain(&frida);
+
+        // Safety: We keep `frida` alive for the lifetime of this context, so it's safe to extend
+        // the device manager lifetime.
+        let device_manager: DeviceManager<'static> = unsafe { std::mem::transmute(dm) };
+        let id_base = workspace_id << WORKSPACE_ID_SHIFT;
+
+        Self {
+            app,
+            sessions: HashMap::new(),
+            scripts: HashMap::new(),
+            next_session_id: id_base + 1,
+            next_script_id: id_base + 1,
+            next_reply_id: INTERNAL_REPLY_ID_BASE + id_base,
+            pending_spawns: HashSet::new(),
+            pinned_devices: HashMap::new(),
+            device_labels: load_device_labels(&app),
+            event_audit: Arc::new(Mutex::new(VecDeque::with_capacity(EVENT_AUDIT_CAPACITY))),
+            agent_watch: None,
+            process_list_cache: HashMap::new(),
+            process_list_cache_ttl: DEFAULT_PROCESS_LIST_CACHE_TTL,
+            reattach_scripts: HashMap::new(),
+            pid_watches: HashSet::new(),
+            pid_watch_checked: Instant::now(),
+            spawn_gating: HashMap::new(),
+            spawn_gating_checked: Instant::now(),
+            known_devices: None,
+            device_poll_checked: Instant::now(),
+            remote_devices: HashMap::new(),
+            script_load_stats: ScriptLoadStats::default(),
+            agent_load_retries: DEFAULT_AGENT_LOAD_RETRIES,
+            agent_load_retry_delay: DEFAULT_AGENT_LOAD_RETRY_DELAY,
+            clock: Box::new(SystemClock),
+            id_base,
+            device_manager,
+            _frida: ManuallyDrop::new(frida),
+        }
+    }
+
+    fn run(&mut self, queue: &JobQueue) {
+        loop {
+            match queue.pop_timeout(Duration::from_millis(100)) {
+                Ok(job) => job(self),
+                Err(RecvTimeoutError::Timeout) => {
+                    self.poll_detached_sessions();
+                    self.poll_inactive_sessions();
+                    self.poll_agent_watch();
+                    self.poll_pid_watches();
+                    self.poll_event_drops();
+                    self.poll_devices();
+                    self.poll_spawn_gating();
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            // Batch windows run on their own clock; a busy queue mustn't hold them back.
+            self.poll_message_batches();
+        }
+    }
+
+    // Leaves nothing behind in the targets: scripts are unloaded before their session detaches,
+    // and spawn gating is switched off so devices don't keep holding new processes.
+    fn shutdown(&mut self) {
+        debug_log(&format!(
+            "shutdown: sessions={} scripts={}",
+            self.sessions.len(),
+            self.scripts.len()
+        ));
+
+        let session_ids: Vec<u64> = self.sessions.keys().copied().collect();
+        for session_id in session_ids {
+            let _ = self.detach_with_reason(session_id, "shutdown", true);
+        }
+        // Anything left belongs to a session that is already gone.
+        let script_ids: Vec<u64> = self.scripts.keys().copied().collect();
+        for script_id in script_ids {
+            let _ = self.unload_script(script_id);
+        }
+
+        let gated: Vec<String> = self.spawn_gating.keys().cloned().collect();
+        for device_id in gated {
+            let _ = self.disable_spawn_gating(&device_id);
+        }
+    }
+
+    fn poll_detached_sessions(&mut self) {
+        let detached_ids: Vec<u64> = self
+            .sessions
+            .iter()
+            .filter_map(|(session_id, record)| {
                 if record.session.is_detached() {
                     Some(*session_id)
                 } else {
-                    None
+                    None
+                }
+            })
+            .collect();
+
+        for session_id in detached_ids {
+            let script_ids = self
+                .sessions
+                .get(&session_id)
+                .map(|r| r.script_ids.clone())
+                .unwrap_or_default();
+
+            debug_log(&format!(
+                "poll_detached_sessions: session_id={} detached, cleaning scripts={}",
+                session_id,
+                script_ids.len()
+            ));
+
+            if let Some(record) = self.sessions.get(&session_id).filter(|r| r.reload_on_reattach) {
+                let key = (record.device_id.clone(), record.pid);
+                let scripts = self.script_layout(record);
+                self.reattach_scripts.insert(key, scripts);
+            }
+
+            for script_id in script_ids {
+                let _ = self.unload_script(script_id);
+            }
+
+            let _ = self.sessions.remove(&session_id);
+
+            self.emit(
+                "frida_session_detached",
+                json!({ "session_id": session_id, "reason": "disposed" }),
+            );
+        }
+    }
+
+    fn emit(&self, event: &str, payload: serde_json::Value) {
+        emit_audited(&self.app, &self.event_audit, event, payload);
+    }
+
+    // Oldest first, at most `limit` of the most recent entries.
+    fn recent_events(&self, limit: usize) -> Vec<AuditedEvent> {
+        let audit = self.event_audit.lock().unwrap_or_else(|e| e.into_inner());
+        let skip = audit.len().saturating_sub(limit);
+        audit.iter().skip(skip).cloned().collect()
+    }
+
+    // Returns the watched path when enabled. Defaults to the dev bundle in `src-frida/dist`.
+    fn set_agent_autoreload(&mut self, enabled: bool, path: Option<String>) -> Result<Option<String>, String> {
+        if !enabled {
+            self.agent_watch = None;
+            return Ok(None);
+        }
+        if !cfg!(feature = "agent-autoreload") {
+            return Err("Agent auto-reload requires a build with the agent-autoreload feature".to_string());
+        }
+
+        let path = path
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../src-frida/dist/index.js")));
+        let modified = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .map_err(|e| format!("Cannot watch {}: {e}", path.display()))?;
+        let source = read_agent_bundle(&path)?;
+
+        debug_log(&format!("set_agent_autoreload: watching {}", path.display()));
+        let display = path.display().to_string();
+        self.agent_watch = Some(AgentWatch {
+            path,
+            modified: Some(modified),
+            source: Some(source),
+            last_checked: Instant::now(),
+            settling: false,
+        });
+
+        Ok(Some(display))
+    }
+
+    fn poll_agent_watch(&mut self) {
+        let Some(watch) = self.agent_watch.as_mut() else {
+            return;
+        };
+        if watch.last_checked.elapsed() < AGENT_WATCH_INTERVAL {
+            return;
+        }
+        watch.last_checked = Instant::now();
+
+        let modified = std::fs::metadata(&watch.path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if modified.is_none() {
+            return;
+        }
+        if modified != watch.modified {
+            watch.modified = modified;
+            watch.settling = true;
+            return;
+        }
+        if !watch.settling {
+            return;
+        }
+        watch.settling = false;
+
+        // The bundler may still be writing; keep the previous source if this read is unusable.
+        let source = match read_agent_bundle(&watch.path) {
+            Ok(source) => source,
+            Err(e) => {
+                debug_log(&format!("poll_agent_watch: {e}"));
+                return;
+            }
+        };
+        watch.source = Some(source.clone());
+
+        self.reload_default_agents(&source);
+    }
+
+    // Swap every loaded default agent for `source`, keeping its runtime and config. Script ids
+    // change; listeners get the mapping. A session whose new agent fails to load keeps the old one.
+    fn reload_default_agents(&mut self, source: &str) {
+        let targets: Vec<(u64, u64, Option<JsRuntime>, Option<serde_json::Value>)> = self
+            .scripts
+            .iter()
+            .filter(|(_, record)| record.is_default)
+            .map(|(script_id, record)| (*script_id, record.session_id, record.runtime, record.config.clone()))
+            .collect();
+
+        for (old_script_id, session_id, runtime, config) in targets {
+            match self.load_agent(session_id, source, runtime, config) {
+                Ok(info) => {
+                    let _ = self.unload_script(old_script_id);
+                    self.emit(
+                        "frida_agent_reloaded",
+                        json!({ "session_id": session_id, "old_script_id": old_script_id, "script_id": info.script_id }),
+                    );
+                }
+                Err(e) => self.emit(
+                    "frida_agent_reloaded",
+                    json!({ "session_id": session_id, "old_script_id": old_script_id, "error": e }),
+                ),
+            }
+        }
+    }
+
+    // 0 disables the timeout. Host posts/RPC and messages from the session's scripts all count
+    // as activity.
+    fn set_session_idle_timeout(&mut self, session_id: u64, timeout_ms: u64) -> Result<(), String> {
+        let record = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id).to_string())?;
+        record.idle_timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        record.last_activity = Instant::now();
+
+        Ok(())
+    }
+
+    fn touch_session(&mut self, session_id: u64) {
+        if let Some(record) = self.sessions.get_mut(&session_id) {
+            record.last_activity = Instant::now();
+        }
+    }
+
+    // Agent messages arrive on Frida's thread, so they're picked up here from the scripts'
+    // inbound counters rather than touching the session from the handler.
+    fn touch_sessions_with_agent_messages(&mut self) {
+        for record in self.sessions.values_mut().filter(|record| record.idle_timeout.is_some()) {
+            let messages: u64 = record
+                .script_ids
+                .iter()
+                .filter_map(|script_id| self.scripts.get(script_id))
+                .map(|script| script.messages_in.load(Ordering::Relaxed))
+                .sum();
+            if messages != record.messages_seen {
+                record.messages_seen = messages;
+                record.last_activity = Instant::now();
+            }
+        }
+    }
+
+    fn poll_inactive_sessions(&mut self) {
+        self.touch_sessions_with_agent_messages();
+
+        let expired: Vec<u64> = self
+            .sessions
+            .iter()
+            .filter(|(_, record)| {
+                record
+                    .idle_timeout
+                    .is_some_and(|timeout| record.last_activity.elapsed() >= timeout)
+            })
+            .map(|(session_id, _)| *session_id)
+            .collect();
+
+        for session_id in expired {
+            debug_log(&format!("poll_inactive_sessions: session_id={} timed out", session_id));
+            if let Err(e) = self.detach_with_reason(session_id, "idle-timeout", false) {
+                debug_log(&format!("poll_inactive_sessions: detach failed: {}", e));
+                // Don't retry every tick; wait out another full timeout first.
+                self.touch_session(session_id);
+            }
+        }
+    }
+
+    #[cfg(feature = "test-hooks")]
+    fn reset_ids(&mut self) -> Result<(), String> {
+        if !self.sessions.is_empty() || !self.scripts.is_empty() {
+            return Err("Cannot reset ids while sessions or scripts are live".to_string());
+        }
+        self.next_session_id = self.id_base + 1;
+        self.next_script_id = self.id_base + 1;
+        Ok(())
+    }
+
+    // `None` or 0 removes the limit. Changing a limit starts a fresh window and forgets
+    // unreported drops.
+    fn set_event_rate_limit(&self, event: &str, max_per_sec: Option<u32>) -> Result<(), String> {
+        if event.is_empty() {
+            return Err(FridaError::InvalidInput("event cannot be empty".to_string()).into());
+        }
+        let mut limits = EVENT_RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+        let limits = limits.get_or_insert_with(HashMap::new);
+        match max_per_sec.filter(|&max| max > 0) {
+            Some(max) => {
+                limits.insert(event.to_string(), EventRateLimit::new(max, Instant::now()));
+            }
+            None => {
+                limits.remove(event);
+            }
+        }
+        Ok(())
+    }
+
+    fn poll_message_batches(&self) {
+        for (&script_id, record) in &self.scripts {
+            if let Some(messages) = take_message_batch(script_id, false) {
+                self.emit_message_batch(script_id, record.session_id, messages);
+            }
+        }
+    }
+
+    fn emit_message_batch(&self, script_id: u64, session_id: u64, messages: Vec<serde_json::Value>) {
+        self.emit(
+            "frida_script_message_batch",
+            json!({ "session_id": session_id, "script_id": script_id, "messages": messages }),
+        );
+    }
+
+    // 0 turns batching off, emitting whatever is still queued first.
+    fn set_message_batching(&mut self, script_id: u64, window_ms: u64) -> Result<(), String> {
+        let window = Duration::from_millis(window_ms);
+        if window > MAX_MESSAGE_BATCH_WINDOW {
+            return Err(FridaError::InvalidInput(format!(
+                "window_ms must be at most {}",
+                MAX_MESSAGE_BATCH_WINDOW.as_millis()
+            ))
+            .into());
+        }
+        let session_id = self
+            .scripts
+            .get(&script_id)
+            .ok_or(FridaError::ScriptNotFound(script_id))?
+            .session_id;
+
+        if let Some(messages) = take_message_batch(script_id, true) {
+            self.emit_message_batch(script_id, session_id, messages);
+        }
+        let mut registry = get_handler_registry().lock().map_err(|e| e.to_string())?;
+        if window.is_zero() {
+            registry.message_batches.remove(&script_id);
+        } else {
+            registry.message_batches.insert(script_id, MessageBatch::new(window));
+        }
+        Ok(())
+    }
+
+    fn poll_event_drops(&self) {
+        let now = Instant::now();
+        let reports: Vec<(String, u32, u64)> = {
+            let mut limits = EVENT_RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(limits) = limits.as_mut() else {
+                return;
+            };
+            limits
+                .iter_mut()
+                .filter_map(|(event, limit)| Some((event.clone(), limit.max_per_sec, limit.take_dropped(now)?)))
+                .collect()
+        };
+        for (event, max_per_sec, dropped) in reports {
+            self.emit(
+                "frida_events_dropped",
+                json!({ "event": event, "max_per_sec": max_per_sec, "dropped": dropped }),
+            );
+        }
+    }
+
+    // frida-rust doesn't expose DeviceManager's `added`/`removed` signals, so hotplug is
+    // detected by diffing enumerations on the worker thread. Only the default workspace polls:
+    // devices are process-wide and every workspace would otherwise report the same change.
+    // The first poll just records what's there.
+    fn poll_devices(&mut self) {
+        if self.id_base != 0 || self.device_poll_checked.elapsed() < DEVICE_POLL_INTERVAL {
+            return;
+        }
+        self.device_poll_checked = Instant::now();
+
+        let current = self.list_devices();
+        if let Some(known) = &self.known_devices {
+            let (added, removed) = device_changes(known, &current);
+            for device in added {
+                self.emit("frida_device_added", json!(device));
+            }
+            for device in removed {
+                self.emit("frida_device_removed", json!(device));
+            }
+        }
+        self.known_devices = Some(current.into_iter().map(|device| (device.id.clone(), device)).collect());
+    }
+
+    fn version(&self) -> String {
+        Frida::version().to_string()
+    }
+
+    fn list_devices(&self) -> Vec<DeviceInfo> {
+        debug_log("list_devices");
+
+        self.device_manager
+            .enumerate_all_devices()
+            .iter()
+            .map(|device| self.device_info(device))
+            .collect()
+    }
+
+    fn device_info(&self, device: &Device) -> DeviceInfo {
+        let id = device.get_id().to_string();
+        let (name, name_sanitized) = read_name(device_name_ptr(device), &id);
+        DeviceInfo {
+            name,
+            name_sanitized,
+            device_type: device.get_type().to_string(),
+            label: self.device_labels.get(&id).cloned(),
+            id,
+        }
+    }
+
+    // Connects to a frida-server at `host[:port]` (default port 27042) and keeps the resulting
+    // socket device around under its address.
+    fn add_remote_device(&mut self, address: &str) -> Result<DeviceInfo, String> {
+        validate_no_nul("address", address)?;
+        validate_remote_address(address)?;
+
+        let device = self
+            .device_manager
+            .get_remote_device(address)
+            .map_err(|e| format!("Failed to connect to {address}: {e}"))?;
+        let info = self.device_info(&device);
+        self.remote_devices.insert(address.to_string(), info.id.clone());
+        self.process_list_cache.clear();
+        Ok(info)
+    }
+
+    // frida-rust has no call to drop a remote device from the DeviceManager, so "removing"
+    // detaches every session on it and forgets the address; the socket device itself goes away
+    // with the manager (`frida_reset`) or when the server hangs up.
+    fn remove_remote_device(&mut self, address: &str) -> Result<(), String> {
+        let device_id = self
+            .remote_devices
+            .remove(address)
+            .ok_or_else(|| FridaError::InvalidInput(format!("no remote device added for {address}")))?;
+        let session_ids: Vec<u64> = self
+            .sessions
+            .iter()
+            .filter(|(_, record)| record.device_id == device_id)
+            .map(|(session_id, _)| *session_id)
+            .collect();
+        for session_id in session_ids {
+            if let Err(e) = self.detach_with_reason(session_id, "device_removed", false) {
+                debug_log(&format!("remove_remote_device: detach {session_id} failed: {e}"));
+            }
+        }
+        self.pid_watches.retain(|(watched_device, _)| *watched_device != device_id);
+        self.process_list_cache.clear();
+        Ok(())
+    }
+
+    // An empty label and color clears the entry.
+    fn set_device_label(&mut self, device_id: &str, label: DeviceLabel) -> Result<(), String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+
+        let label = DeviceLabel {
+            label: label.label.filter(|l| !l.trim().is_empty()),
+            color: label.color.filter(|c| !c.trim().is_empty()),
+        };
+        if label.label.is_none() && label.color.is_none() {
+            self.device_labels.remove(&resolved);
+        } else {
+            self.device_labels.insert(resolved, label);
+        }
+
+        save_device_labels(&self.app, &self.device_labels)
+    }
+
+    // Remember the current device's name/type under `alias`; use it later as `pin:<alias>`.
+    fn pin_device(&mut self, alias: String, device_id: &str) -> Result<PinnedDevice, String> {
+        validate_no_nul("alias", &alias)?;
+        if alias.is_empty() {
+            return Err(FridaError::InvalidInput("alias cannot be empty".to_string()).into());
+        }
+
+        validate_no_nul("device_id", device_id)?;
+
+        let resolved = self.resolve_device_id(device_id)?;
+        let device = self
+            .device_manager
+            .get_device_by_id(&resolved)
+            .map_err(|e| e.to_string())?;
+
+        let pinned = PinnedDevice {
+            alias: alias.clone(),
+            name: device.get_name().to_string(),
+            device_type: device.get_type().to_string(),
+        };
+        debug_log(&format!(
+            "pin_device: alias={} name={} type={}",
+            pinned.alias, pinned.name, pinned.device_type
+        ));
+
+        self.pinned_devices.insert(alias, pinned.clone());
+        Ok(pinned)
+    }
+
+    fn unpin_device(&mut self, alias: &str) -> Result<(), String> {
+        self.pinned_devices
+            .remove(alias)
+            .map(|_| ())
+            .ok_or_else(|| FridaError::DeviceNotFound(format!("{PINNED_DEVICE_PREFIX}{alias}")).into())
+    }
+
+    fn list_pinned_devices(&self) -> Vec<PinnedDevice> {
+        self.pinned_devices.values().cloned().collect()
+    }
+
+    // Map `pin:<alias>` to whichever connected device currently matches the pinned properties.
+    // Plain device ids pass through untouched.
+    fn resolve_device_id(&self, device_id: &str) -> Result<String, String> {
+        let Some(alias) = device_id.strip_prefix(PINNED_DEVICE_PREFIX) else {
+            return Ok(device_id.to_string());
+        };
+
+        let pinned = self
+            .pinned_devices
+            .get(alias)
+            .ok_or_else(|| FridaError::DeviceNotFound(device_id.to_string()))?;
+
+        self.device_manager
+            .enumerate_all_devices()
+            .into_iter()
+            .find(|device| {
+                device.get_name() == pinned.name && device.get_type().to_string() == pinned.device_type
+            })
+            .map(|device| device.get_id().to_string())
+            .ok_or_else(|| FridaError::DeviceNotFound(device_id.to_string()).into())
+    }
+
+    // Frida has no cheaper count than a full enumeration, so reuse the process-list cache: a
+    // cached list for this device within its TTL answers (flagged approximate), and anything
+    // older is re-enumerated.
+    fn process_count(&mut self, device_id: &str) -> Result<ProcessCount, String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+
+        let now = self.clock.now();
+        let ttl = self.process_list_cache_ttl;
+        let cached = self.process_list_cache.get(&resolved).filter(|cache| cache.is_fresh(&resolved, now, ttl));
+        if let Some(cache) = cached {
+            return Ok(ProcessCount {
+                device_id: resolved,
+                count: cache.processes.len(),
+                approximate: true,
+                age_ms: now.saturating_duration_since(cache.fetched_at).as_millis() as u64,
+            });
+        }
+
+        let count = self.list_processes(&resolved)?.len();
+        Ok(ProcessCount {
+            device_id: resolved,
+            count,
+            approximate: false,
+            age_ms: 0,
+        })
+    }
+
+    fn set_process_list_cache_ttl(&mut self, ttl: Duration) -> Result<(), String> {
+        if ttl > MAX_PROCESS_LIST_CACHE_TTL {
+            return Err(FridaError::InvalidInput(format!(
+                "ttl exceeds {}ms",
+                MAX_PROCESS_LIST_CACHE_TTL.as_millis()
+            ))
+            .into());
+        }
+        self.process_list_cache_ttl = ttl;
+        self.process_list_cache.clear();
+        Ok(())
+    }
+
+    fn watch_pid(&mut self, device_id: &str, pid: u32) -> Result<PidWatch, String> {
+        validate_no_nul("device_id", device_id)?;
+        let device_id = self.resolve_device_id(device_id)?;
+        if !supports_processes(&device_id) {
+            return Err(FridaError::InvalidInput(format!("device {device_id} has no process list")).into());
+        }
+
+        self.process_list_cache.clear();
+        let running = self.list_processes(&device_id)?.iter().any(|process| process.pid == pid);
+        if running {
+            self.pid_watches.insert((device_id.clone(), pid));
+        } else {
+            self.emit("frida_pid_exited", json!({ "device_id": device_id, "pid": pid }));
+        }
+
+        Ok(PidWatch {
+            device_id,
+            pid,
+            watching: running,
+        })
+    }
+
+    fn unwatch_pid(&mut self, device_id: &str, pid: u32) -> Result<bool, String> {
+        let device_id = self.resolve_device_id(device_id)?;
+        Ok(self.pid_watches.remove(&(device_id, pid)))
+    }
+
+    fn script_device_id(&self, script_id: u64) -> Result<String, String> {
+        let record = self.scripts.get(&script_id).ok_or(FridaError::ScriptNotFound(script_id))?;
+        self.sessions
+            .get(&record.session_id)
+            .map(|session| session.device_id.clone())
+            .ok_or_else(|| FridaError::SessionNotFound(record.session_id).to_string())
+    }
+
+    fn device_pid_watches(&self, device_id: &str) -> Vec<WatchInfo> {
+        let mut watches: Vec<WatchInfo> = self
+            .pid_watches
+            .iter()
+            .filter(|(watched_device, _)| watched_device == device_id)
+            .map(|(_, pid)| WatchInfo {
+                id: format!("pid-{pid}"),
+                kind: "pid".to_string(),
+                description: format!("pid {pid} on {device_id}"),
+            })
+            .collect();
+        watches.sort_by(|a, b| a.id.cmp(&b.id));
+        watches
+    }
+
+    fn list_watches(&mut self, script_id: u64) -> Result<Vec<WatchInfo>, String> {
+        let device_id = self.script_device_id(script_id)?;
+        let agent = self.agent_request(script_id, "list_watches", json!({}))?;
+        let mut watches: Vec<WatchInfo> = serde_json::from_value(agent).map_err(|e| e.to_string())?;
+        watches.extend(self.device_pid_watches(&device_id));
+        Ok(watches)
+    }
+
+    // Pid watches aren't tied to a script and may belong to other callers, so they are only
+    // dropped (every one on the script's device) when `include_pid_watches` asks for it.
+    fn stop_all_watches(
+        &mut self,
+        script_id: u64,
+        include_pid_watches: bool,
+    ) -> Result<Vec<WatchInfo>, String> {
+        let device_id = self.script_device_id(script_id)?;
+        let agent = self.agent_request(script_id, "stop_all_watches", json!({}))?;
+        let mut stopped: Vec<WatchInfo> = agent
+            .get("stopped")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+        if include_pid_watches {
+            stopped.extend(self.device_pid_watches(&device_id));
+            self.pid_watches.retain(|(watched_device, _)| *watched_device != device_id);
+        }
+        Ok(stopped)
+    }
+
+    // One enumeration per device per tick. A device that fails to enumerate keeps its watches
+    // and is retried next tick. The same enumeration evicts `reattach_scripts` entries whose
+    // process has exited; a reused pid shouldn't inherit their scripts.
+    fn poll_pid_watches(&mut self) {
+        if self.pid_watches.is_empty() && self.reattach_scripts.is_empty() {
+            return;
+        }
+        if self.pid_watch_checked.elapsed() < PID_WATCH_INTERVAL {
+            return;
+        }
+        self.pid_watch_checked = Instant::now();
+
+        let devices: HashSet<String> = self
+            .pid_watches
+            .iter()
+            .chain(self.reattach_scripts.keys())
+            .map(|(device_id, _)| device_id.clone())
+            .collect();
+        for device_id in devices {
+            let running: HashSet<u32> = match self.list_processes(&device_id) {
+                Ok(processes) => processes.iter().map(|process| process.pid).collect(),
+                Err(e) => {
+                    debug_log(&format!("poll_pid_watches: device_id={device_id} enumerate failed: {e}"));
+                    continue;
+                }
+            };
+
+            let exited: Vec<(String, u32)> = self
+                .pid_watches
+                .iter()
+                .filter(|(watched_device, pid)| *watched_device == device_id && !running.contains(pid))
+                .cloned()
+                .collect();
+            for key in exited {
+                self.pid_watches.remove(&key);
+                self.emit("frida_pid_exited", json!({ "device_id": key.0, "pid": key.1 }));
+            }
+
+            self.reattach_scripts
+                .retain(|(saved_device, pid), _| *saved_device != device_id || running.contains(pid));
+        }
+    }
+
+    // Bypasses the process-list cache; a 2s-old list is useless when racing a launch. Matches
+    // the exact process name only: identifiers need application enumeration, which frida-rust
+    // doesn't offer.
+    fn find_process_fresh(&mut self, device_id: &str, name: &str) -> Result<Option<u32>, String> {
+        self.process_list_cache.clear();
+        Ok(self
+            .list_processes(device_id)?
+            .into_iter()
+            .find(|process| process.name == name)
+            .map(|process| process.pid))
+    }
+
+    fn list_processes(&mut self, device_id: &str) -> Result<Vec<ProcessInfo>, String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
+
+        debug_log(&format!("list_processes: device_id={device_id}"));
+
+        // Skip devices that don't support process enumeration
+        if !supports_processes(device_id) {
+            debug_log(&format!("list_processes: skipping unsupported device {device_id}"));
+            return Ok(Vec::new());
+        }
+
+        if let Some(cache) = self.process_list_cache.get(device_id) {
+            if cache.is_fresh(device_id, self.clock.now(), self.process_list_cache_ttl) {
+                debug_log(&format!(
+                    "list_processes: returning cached results ({} processes)",
+                    cache.processes.len()
+                ));
+                return Ok(cache.processes.clone());
+            }
+        }
+
+        let device = self
+            .device_manager
+            .get_device_by_id(device_id)
+            .map_err(|e| e.to_string())?;
+
+        debug_log(&format!(
+            "list_processes: resolved device name={} type={}",
+            device.get_name(),
+            device.get_type()
+        ));
+
+        // Wrap enumerate_processes in catch_unwind to prevent crashes from propagating
+        let processes = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            device.enumerate_processes()
+        }))
+        .map_err(|_| "enumerate_processes panicked - device may not support this operation".to_string())?;
+        
+        debug_log(&format!(
+            "list_processes: enumerated {} processes",
+            processes.len()
+        ));
+
+        debug_log("list_processes: mapping begin");
+        let infos: Vec<ProcessInfo> = processes
+            .into_iter()
+            .map(|process| {
+                let pid = process.get_pid();
+                let fallback = format!("<pid {pid}>");
+                let (name, name_sanitized) = read_name(process_name_ptr(&process), &fallback);
+                ProcessInfo {
+                    pid,
+                    name,
+                    name_sanitized,
+                }
+            })
+            .collect();
+        debug_log(&format!("list_processes: mapping done ({} processes)", infos.len()));
+
+        // A zero TTL disables caching. Expired entries for other devices go on every store.
+        if !self.process_list_cache_ttl.is_zero() {
+            let now = self.clock.now();
+            let ttl = self.process_list_cache_ttl;
+            self.process_list_cache.retain(|id, cache| cache.is_fresh(id, now, ttl));
+            self.process_list_cache.insert(
+                device_id.to_string(),
+                ProcessListCache {
+                    device_id: device_id.to_string(),
+                    fetched_at: now,
+                    processes: infos.clone(),
+                },
+            );
+        }
+
+        Ok(infos)
+    }
+
+    fn device_capabilities(&self, device_id: &str) -> Result<DeviceCapabilities, String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
+
+        let device = self
+            .device_manager
+            .get_device_by_id(device_id)
+            .map_err(|e| e.to_string())?;
+
+        let processes = supports_processes(device_id);
+
+        Ok(DeviceCapabilities {
+            device_id: device_id.to_string(),
+            device_type: device.get_type().to_string(),
+            enumerate_processes: processes,
+            attach: processes,
+            spawn: processes,
+            kill: processes,
+        })
+    }
+
+    // Whatever the device (frida-server for remote ones) reports: typically `os`, `platform`,
+    // `arch`, `access` and `name`, plus platform-specific extras.
+    fn query_system_parameters(&self, device_id: &str) -> Result<serde_json::Value, String> {
+        validate_no_nul("device_id", device_id)?;
+        let device_id = self.resolve_device_id(device_id)?;
+
+        let device = self
+            .device_manager
+            .get_device_by_id(&device_id)
+            .map_err(|e| e.to_string())?;
+        let parameters = device.query_system_parameters().map_err(|e| e.to_string())?;
+        Ok(variant_map_json(parameters))
+    }
+
+    fn attach(&mut self, device_id: &str, pid: u32) -> Result<SessionInfo, String> {
+        self.attach_with(device_id, pid, false)
+    }
+
+    fn attach_with(&mut self, device_id: &str, pid: u32, silent: bool) -> Result<SessionInfo, String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
+
+        debug_log(&format!("attach: device_id={} pid={} - begin", device_id, pid));
+
+        let device = self
+            .device_manager
+            .get_device_by_id(device_id)
+            .map_err(|e| e.to_string())?;
+
+        debug_log("attach: about to call device.attach");
+        let session = device.attach(pid).map_err(|e| e.to_string())?;
+        debug_log("attach: device.attach succeeded");
+
+        // Keep a ref to the underlying device alive for the lifetime of the session.
+        // This prevents potential use-after-free inside Frida when creating/loading scripts.
+        //
+        // Safety: Frida objects are reference-counted internally. We keep the Frida runtime alive
+        // for the lifetime of this context, so it's safe to extend lifetimes to 'static.
+        debug_log("attach: about to transmute session and device");
+        let session: Session<'static> = unsafe { std::mem::transmute(session) };
+        let device_keepalive: Device<'static> = unsafe { std::mem::transmute(device) };
+        debug_log("attach: transmute succeeded");
+
+        let session_id = self.next_session_id;
+        self.next_session_id = self.next_session_id.saturating_add(1);
+
+        debug_log(&format!("attach: about to insert session_id={}", session_id));
+        self.sessions.insert(
+            session_id,
+            SessionRecord {
+                device_id: device_id.to_string(),
+                pid,
+                session: ManuallyDrop::new(session),
+                _device: ManuallyDrop::new(device_keepalive),
+                script_ids: Vec::new(),
+                last_activity: Instant::now(),
+                idle_timeout: None,
+                messages_seen: 0,
+                reload_on_reattach: false,
+            },
+        );
+        debug_log("attach: session inserted");
+
+        // Load the default agent as part of attach so the caller doesn't need a separate step.
+        debug_log("attach: about to load_default_script");
+        let script_info = match self.load_default_script(session_id, None, None, false) {
+            Ok(info) => {
+                debug_log(&format!("attach: load_default_script succeeded script_id={}", info.script_id));
+                info
+            },
+            Err(e) => {
+                debug_log(&format!("attach: load_default_script failed: {}", e));
+                // Best-effort cleanup so callers don't end up with a half-attached session.
+                let _ = self.sessions.remove(&session_id);
+                return Err(e);
+            }
+        };
+
+        debug_log(&format!(
+            "attach: device_id={} pid={} => session_id={} script_id={}",
+            device_id, pid, session_id, script_info.script_id
+        ));
+
+        if !silent {
+            debug_log("attach: about to emit frida_session_attached event");
+            self.emit(
+                "frida_session_attached",
+                json!({ "session_id": session_id, "script_id": script_info.script_id, "device_id": device_id, "pid": pid }),
+            );
+            debug_log("attach: event emitted");
+        }
+
+        let session = SessionInfo {
+            session_id,
+            script_id: script_info.script_id,
+            pid,
+            frozen: None,
+        };
+        self.reload_after_reattach(device_id, &session, pid);
+
+        Ok(session)
+    }
+
+    // Matches against a fresh enumeration rather than the process-list cache.
+    fn attach_by_name(&mut self, device_id: &str, process_name: &str) -> Result<SessionInfo, String> {
+        validate_no_nul("process_name", process_name)?;
+        if process_name.is_empty() {
+            return Err(FridaError::InvalidInput("process_name cannot be empty".to_string()).into());
+        }
+
+        self.process_list_cache.clear();
+        let processes = self.list_processes(device_id)?;
+        let pid = unique_process_named(&processes, process_name)?;
+        self.attach(device_id, pid)
+    }
+
+    // Only the attach itself can fail; the probes run one agent request each so a hung or
+    // throwing probe costs its own field and nothing else.
+    fn attach_and_probe(&mut self, device_id: &str, pid: u32) -> Result<AttachProbe, String> {
+        let session = self.attach(device_id, pid)?;
+        let script_id = session.script_id;
+        let mut errors = Vec::new();
+
+        let agent_version = self.probe_step(script_id, "agent_version", "get_version", &mut errors);
+        let capabilities = self.probe_step(script_id, "capabilities", "get_capabilities", &mut errors);
+        let main_module = self.probe_step(script_id, "main_module", "get_main_module", &mut errors);
+        let arch = self
+            .probe_step(script_id, "arch", "get_arch", &mut errors)
+            .and_then(|value| value.get("arch").and_then(|arch| arch.as_str()).map(str::to_string));
+        let module_count = self
+            .probe_step(script_id, "module_count", "enumerate_modules", &mut errors)
+            .and_then(|value| value.as_array().map(Vec::len));
+
+        Ok(AttachProbe {
+            session_id: session.session_id,
+            script_id,
+            pid,
+            frida_core_version: self.version(),
+            agent_version,
+            capabilities,
+            main_module,
+            arch,
+            module_count,
+            errors,
+        })
+    }
+
+    fn probe_step(
+        &mut self,
+        script_id: u64,
+        step: &str,
+        method: &str,
+        errors: &mut Vec<String>,
+    ) -> Option<serde_json::Value> {
+        match self.agent_request(script_id, method, json!({})) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                errors.push(format!("{step}: {e}"));
+                None
+            }
+        }
+    }
+
+    // frida-rust can't enumerate applications, so the running instance is matched by process
+    // name. That is the package name on Android, but a display name on iOS, where a running app
+    // is missed and `spawn` launches a second instance by identifier instead.
+    fn attach_by_identifier(
+        &mut self,
+        device_id: &str,
+        identifier: &str,
+        spawn: bool,
+    ) -> Result<IdentifierAttachInfo, String> {
+        validate_no_nul("identifier", identifier)?;
+        if identifier.is_empty() {
+            return Err(FridaError::InvalidInput("identifier cannot be empty".to_string()).into());
+        }
+
+        let running = self
+            .list_processes(device_id)?
+            .into_iter()
+            .find(|process| process.name == identifier)
+            .map(|process| process.pid);
+
+        if let Some(pid) = running {
+            let session = self.attach(device_id, pid)?;
+            return Ok(IdentifierAttachInfo {
+                session_id: session.session_id,
+                script_id: session.script_id,
+                pid,
+                spawned: false,
+            });
+        }
+
+        if !spawn {
+            return Err(format!("No running process for identifier {identifier}"));
+        }
+
+        // Attach while still suspended so the agent sees startup, then resume.
+        let pid = self.spawn(device_id, identifier.to_string(), None, SpawnConfig::default())?;
+        let session = match self.attach(device_id, pid) {
+            Ok(session) => session,
+            Err(e) => {
+                let _ = self.discard_spawn(device_id, pid);
+                return Err(e);
+            }
+        };
+        // A process left suspended with a live session would just hang; take both down.
+        if let Err(e) = self.resume(device_id, pid) {
+            if let Err(detach_error) = self.detach_with_reason(session.session_id, "disposed", false) {
+                debug_log(&format!("attach_by_identifier: detach failed: {detach_error}"));
+            }
+            let _ = self.discard_spawn(device_id, pid);
+            return Err(e);
+        }
+
+        Ok(IdentifierAttachInfo {
+            session_id: session.session_id,
+            script_id: session.script_id,
+            pid,
+            spawned: true,
+        })
+    }
+
+    // `frida -f` minus the resume: the default agent is loaded while the target is still
+    // suspended, so the caller can hook startup before resuming it.
+    fn spawn_attach(
+        &mut self,
+        device_id: &str,
+        program: String,
+        argv: Option<Vec<String>>,
+        config: SpawnConfig,
+    ) -> Result<SpawnAttachInfo, String> {
+        let pid = self.spawn(device_id, program, argv, config)?;
+        let session = match self.attach(device_id, pid) {
+            Ok(session) => session,
+            Err(e) => {
+                let _ = self.discard_spawn(device_id, pid);
+                return Err(e);
+            }
+        };
+
+        Ok(SpawnAttachInfo {
+            pid,
+            session_id: session.session_id,
+            script_id: session.script_id,
+        })
+    }
+
+    // Heavy hammer for wedged state: detach every session (which unloads its scripts first),
+    // drop whatever didn't let go, then swap in a fresh DeviceManager. The Frida runtime itself
+    // stays initialized since other workspaces share it. Ids keep counting up so stale ids
+    // held by the frontend can't hit new sessions.
+    fn reset(&mut self) -> ResetSummary {
+        let session_ids: Vec<u64> = self.sessions.keys().copied().collect();
+        let script_ids: Vec<u64> = self.scripts.keys().copied().collect();
+        debug_log(&format!(
+            "reset: sessions={} scripts={}",
+            session_ids.len(),
+            script_ids.len()
+        ));
+
+        let mut errors = Vec::new();
+        for &session_id in &session_ids {
+            if let Err(e) = self.detach_with_reason(session_id, "reset", false) {
+                errors.push(format!("session {session_id}: {e}"));
+            }
+        }
+
+        // Scripts must go before their sessions: unload_script skips the Frida call for
+        // sessions that are gone but still reclaims the leaked Script box.
+        let leftover: Vec<u64> = self.scripts.keys().copied().collect();
+        for script_id in leftover {
+            let _ = self.unload_script(script_id);
+        }
+        self.sessions.clear();
+        for &script_id in &script_ids {
+            unregister_handler(script_id);
+        }
+
+        // Gating is device state that would outlive the reset, so turn it off while the old
+        // manager can still reach the devices.
+        let gated: Vec<String> = self.spawn_gating.keys().cloned().collect();
+        for device_id in gated {
+            if let Err(e) = self.disable_spawn_gating(&device_id) {
+                errors.push(format!("spawn gating on {device_id}: {e}"));
+            }
+        }
+        self.spawn_gating.clear();
+
+        self.pending_spawns.clear();
+        self.reattach_scripts.clear();
+        self.pid_watches.clear();
+        self.process_list_cache.clear();
+        // Remote devices belong to the old manager, and the next hotplug poll re-baselines
+        // against the new one instead of reporting the swap as removals.
+        self.remote_devices.clear();
+        self.known_devices = None;
+
+        // Every Device we kept alive belonged to a session, so nothing references the old
+        // manager anymore; dropping it closes it.
+        let device_manager: DeviceManager<'static> =
+            unsafe { std::mem::transmute(DeviceManager::obtain(&self._frida)) };
+        self.device_manager = device_manager;
+
+        let summary = ResetSummary {
+            sessions: session_ids.len(),
+            scripts: script_ids.len(),
+            errors,
+        };
+        self.emit("frida_reset_complete", json!(summary));
+        summary
+    }
+
+    fn detach(&mut self, session_id: u64) -> Result<(), String> {
+        self.detach_with_reason(session_id, "user", false)
+    }
+
+    fn detach_with_reason(&mut self, session_id: u64, reason: &str, silent: bool) -> Result<(), String> {
+        debug_log(&format!("detach: session_id={} reason={}", session_id, reason));
+
+        let script_ids = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(|| "Unknown session_id".to_string())?
+            .script_ids
+            .clone();
+
+        // Give in-flight agent output a chance to arrive before tearing scripts down.
+        for script_id in &script_ids {
+            self.flush_script(*script_id);
+        }
+
+        for script_id in script_ids {
+            let _ = self.unload_script(script_id);
+        }
+
+        let record = self
+            .sessions
+            .remove(&session_id)
+            .ok_or_else(|| "Unknown session_id".to_string())?;
+
+        match record.session.detach() {
+            Ok(()) => {
+                if !silent {
+                    self.emit(
+                        "frida_session_detached",
+                        json!({ "session_id": session_id, "reason": reason }),
+                    );
+                }
+                Ok(())
+            }
+            Err(e) => {
+                if (&*record.session).is_detached() {
+                    if !silent {
+                        self.emit(
+                            "frida_session_detached",
+                            json!({ "session_id": session_id, "reason": "disposed" }),
+                        );
+                    }
+                    Ok(())
+                } else {
+                    self.sessions.insert(session_id, record);
+                    Err(e.to_string())
+                }
+            }
+        }
+    }
+
+    // Best-effort drain: post a flush marker and wait (bounded) for the agent to echo it back.
+    // Frida delivers messages in order, so once the echo arrives everything sent before it has too.
+    fn flush_script(&mut self, script_id: u64) {
+        if self.live_script(script_id).is_err() {
+            return;
+        }
+
+        let flush_id = self.alloc_reply_id();
+
+        let (reply_tx, reply_rx) = channel();
+        register_pending_reply(flush_id, reply_tx);
+
+        let message = json!({ "type": "carf:flush", "__carf_flush": flush_id });
+        if self.script_post(script_id, message, None).is_ok() {
+            let waited = reply_rx.recv_timeout(FLUSH_TIMEOUT);
+            debug_log(&format!(
+                "flush_script: script_id={} flushed={}",
+                script_id,
+                waited.is_ok()
+            ));
+        }
+
+        unregister_pending_reply(flush_id);
+    }
+
+    fn alloc_reply_id(&mut self) -> u64 {
+        let id = self.next_reply_id;
+        self.next_reply_id = self.next_reply_id.saturating_add(1);
+        id
+    }
+
+    // Posts a standard Frida RPC call; the reply is matched in `on_message` by id.
+    fn post_rpc_call(
+        &mut self,
+        script_id: u64,
+        method: &str,
+        args: serde_json::Value,
+    ) -> Result<(u64, Receiver<AgentReply>), String> {
+        validate_no_nul("method", method)?;
+        if method.is_empty() {
+            return Err(FridaError::InvalidInput("method cannot be empty".to_string()).into());
+        }
+        let args = match args {
+            serde_json::Value::Null => json!([]),
+            serde_json::Value::Array(_) => args,
+            _ => return Err(FridaError::InvalidInput("args must be an array".to_string()).into()),
+        };
+
+        self.post_rpc_request(script_id, vec![json!("call"), json!(method), args])
+    }
+
+    // `op` follows the request id: `["call", method, args]` or `["list"]`.
+    fn post_rpc_request(
+        &mut self,
+        script_id: u64,
+        op: Vec<serde_json::Value>,
+    ) -> Result<(u64, Receiver<AgentReply>), String> {
+        let id = self.alloc_reply_id();
+        let (reply_tx, reply_rx) = channel();
+        register_pending_reply(id, reply_tx);
+
+        let mut message = vec![json!("frida:rpc"), json!(id)];
+        message.extend(op);
+        if let Err(e) = self.script_post(script_id, serde_json::Value::Array(message), None) {
+            unregister_pending_reply(id);
+            return Err(e);
+        }
+        Ok((id, reply_rx))
+    }
+
+    // Posts a default-agent RPC request; the reply is matched in `on_message` by id.
+    fn post_agent_request(
+        &mut self,
+        script_id: u64,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<(u64, Receiver<AgentReply>), String> {
+        let id = self.alloc_reply_id();
+        let (reply_tx, reply_rx) = channel();
+        register_pending_reply(id, reply_tx);
+
+        let message = json!({
+            "type": "carf:request",
+            "payload": { "id": id, "method": method, "params": params },
+        });
+        if let Err(e) = self.script_post(script_id, message, None) {
+            unregister_pending_reply(id);
+            return Err(e);
+        }
+        Ok((id, reply_rx))
+    }
+
+    // Call a default-agent RPC method from the backend and block (bounded) for its response.
+    // The reply is intercepted in `on_message` and never forwarded to the frontend.
+    fn agent_request(
+        &mut self,
+        script_id: u64,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.agent_request_with_data(script_id, method, params)
+            .map(|(returns, _)| returns)
+    }
+
+    // Same as `agent_request`, but also returns binary data the agent attached to its reply.
+    fn agent_request_with_data(
+        &mut self,
+        script_id: u64,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<(serde_json::Value, Option<Vec<u8>>), String> {
+        let (id, reply_rx) = self.post_agent_request(script_id, method, params)?;
+        let reply = reply_rx.recv_timeout(AGENT_REQUEST_TIMEOUT);
+        unregister_pending_reply(id);
+
+        debug_log(&format!(
+            "agent_request: script_id={} method={} replied={}",
+            script_id,
+            method,
+            reply.is_ok()
+        ));
+
+        reply.unwrap_or_else(|_| Err(FridaError::Timeout.into()))
+    }
+
+    // Lines arrive as `file_tail` agent events on `frida_script_message`.
+    fn tail_file(&mut self, script_id: u64, path: String) -> Result<serde_json::Value, String> {
+        if path.is_empty() {
+            return Err(FridaError::InvalidInput("path cannot be empty".to_string()).into());
+        }
+
+        self.agent_request(script_id, "file_tail_start", json!({ "path": path }))
+    }
+
+    fn get_agent_log_level(&mut self, script_id: u64) -> Result<String, String> {
+        let reply = self.agent_request(script_id, "get_log_level", serde_json::Value::Null)?;
+
+        reply
+            .get("level")
+            .and_then(|level| level.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| "Agent returned no log level".to_string())
+    }
+
+    fn set_agent_log_level(&mut self, script_id: u64, level: &str) -> Result<(), String> {
+        if !AGENT_LOG_LEVELS.contains(&level) {
+            return Err(FridaError::InvalidInput(format!(
+                "level must be one of {}",
+                AGENT_LOG_LEVELS.join(", ")
+            ))
+            .into());
+        }
+
+        self.agent_request(script_id, "set_log_level", json!({ "level": level }))
+            .map(|_| ())
+    }
+
+    // Measures the full host -> agent -> host round trip through the RPC router.
+    fn ping_agent(&mut self, script_id: u64, samples: Option<u32>) -> Result<PingStats, String> {
+        let samples = samples
+            .unwrap_or(DEFAULT_PING_SAMPLES)
+            .clamp(1, MAX_PING_SAMPLES);
+
+        let mut timings = Vec::with_capacity(samples as usize);
+        for _ in 0..samples {
+            let started = Instant::now();
+            self.agent_request(script_id, "ping", serde_json::Value::Null)?;
+            timings.push(started.elapsed().as_secs_f64() * 1000.0);
+        }
+
+        let min_ms = timings.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_ms = timings.iter().copied().fold(0.0, f64::max);
+        let avg_ms = timings.iter().sum::<f64>() / timings.len() as f64;
+
+        Ok(PingStats {
+            script_id,
+            samples,
+            min_ms,
+            avg_ms,
+            max_ms,
+        })
+    }
+
+    // Stream `size` bytes at `address` into a file on the host, chunk by chunk.
+    // Existing files are only replaced with `overwrite`; a dump that fails part way removes
+    // what it wrote rather than leaving a truncated file behind.
+    fn dump_region(
+        &mut self,
+        script_id: u64,
+        address: &str,
+        size: u64,
+        path: String,
+        overwrite: bool,
+    ) -> Result<DumpInfo, String> {
+        use std::io::Write;
+
+        validate_no_nul("path", &path)?;
+        let base = parse_address(address)?;
+        if size == 0 || size > MAX_DUMP_SIZE {
+            return Err(FridaError::InvalidInput(format!(
+                "size must be between 1 and {MAX_DUMP_SIZE} bytes"
+            ))
+            .into());
+        }
+
+        let mut file = create_output_file(&path, overwrite)?;
+
+        let mut offset = 0;
+        while offset < size {
+            let chunk = (size - offset).min(DUMP_CHUNK_SIZE);
+            let chunk_address = format!("{:#x}", base.wrapping_add(offset));
+            let written = self
+                .agent_request_with_data(
+                    script_id,
+                    "read_memory_raw",
+                    json!({ "address": chunk_address, "size": chunk }),
+                )
+                .and_then(|(_, data)| data.ok_or_else(|| format!("Agent returned no data for {chunk_address}")))
+                .and_then(|data| file.write_all(&data).map_err(|e| format!("Failed to write {path}: {e}")));
+            if let Err(e) = written {
+                drop(file);
+                if let Err(remove_error) = std::fs::remove_file(&path) {
+                    debug_log(&format!("dump_region: failed to remove {path}: {remove_error}"));
+                }
+                return Err(e);
+            }
+            offset += chunk;
+        }
+
+        debug_log(&format!("dump_region: {} bytes at {} -> {}", size, address, path));
+
+        Ok(DumpInfo {
+            path,
+            address: format!("{:#x}", base),
+            size,
+        })
+    }
+
+    // Every mapped range of the target, written straight to a host file so large maps never
+    // travel over IPC. Existing files are only replaced with `overwrite`.
+    fn export_memory_map(
+        &mut self,
+        script_id: u64,
+        path: String,
+        format: MemoryMapFormat,
+        overwrite: bool,
+    ) -> Result<MemoryMapExport, String> {
+        use std::io::Write;
+
+        validate_no_nul("path", &path)?;
+        let ranges = self.agent_request(script_id, "process_enumerate_ranges", json!({ "protection": "---" }))?;
+        let ranges = ranges
+            .as_array()
+            .ok_or_else(|| "Agent returned no range list".to_string())?;
+
+        let mut out = std::io::BufWriter::new(create_output_file(&path, overwrite)?);
+
+        let written = match format {
+            MemoryMapFormat::Json => serde_json::to_writer_pretty(&mut out, ranges).map_err(|e| e.to_string()),
+            MemoryMapFormat::Text => ranges
+                .iter()
+                .try_for_each(|range| writeln!(out, "{}", memory_map_line(range)))
+                .map_err(|e| e.to_string()),
+        };
+        written
+            .and_then(|()| out.flush().map_err(|e| e.to_string()))
+            .map_err(|e| format!("Failed to write {path}: {e}"))?;
+
+        debug_log(&format!("export_memory_map: {} ranges -> {}", ranges.len(), path));
+
+        Ok(MemoryMapExport {
+            path,
+            format,
+            count: ranges.len(),
+        })
+    }
+
+    // Interceptor.replace with a NativeCallback running `body` or returning a constant. The agent
+    // compiles `body` before installing anything. This can easily destabilize the target.
+    fn replace_function(
+        &mut self,
+        script_id: u64,
+        spec: FunctionReplacementSpec,
+    ) -> Result<FunctionReplacement, String> {
+        let params = replacement_params(spec)?;
+        let reply = self.agent_request(script_id, "interceptor_replace_function", params)?;
+        serde_json::from_value(reply).map_err(|e| format!("Unexpected replace reply: {e}"))
+    }
+
+    fn export_layout(&mut self) -> Vec<SessionLayout> {
+        let mut session_ids: Vec<u64> = self.sessions.keys().copied().collect();
+        session_ids.sort_unstable();
+
+        let mut process_names: HashMap<String, HashMap<u32, String>> = HashMap::new();
+        let mut layout = Vec::new();
+        for session_id in session_ids {
+            let Some(record) = self.sessions.get(&session_id) else {
+                continue;
+            };
+            let (device_id, pid) = (record.device_id.clone(), record.pid);
+            let scripts = self.script_layout(record);
+
+            if !process_names.contains_key(&device_id) {
+                // A device that can't enumerate just exports pids.
+                let names = self
+                    .list_processes(&device_id)
+                    .map(|processes| processes.into_iter().map(|process| (process.pid, process.name)).collect())
+                    .unwrap_or_default();
+                process_names.insert(device_id.clone(), names);
+            }
+            let process_name = process_names.get(&device_id).and_then(|names| names.get(&pid)).cloned();
+
+            layout.push(SessionLayout {
+                device_id,
+                pid,
+                process_name,
+                scripts,
+            });
+        }
+        layout
+    }
+
+    // The exported pid if it still runs the same process, otherwise the one process now
+    // running under the exported name.
+    fn layout_target_pid(&mut self, entry: &SessionLayout) -> Result<u32, String> {
+        let Some(process_name) = entry.process_name.as_deref() else {
+            return Ok(entry.pid);
+        };
+        let processes = self.list_processes(&entry.device_id)?;
+        if processes
+            .iter()
+            .any(|process| process.pid == entry.pid && process.name == process_name)
+        {
+            return Ok(entry.pid);
+        }
+        unique_process_named(&processes, process_name)
+    }
+
+    // Scripts of a session in load order; default agents are recorded by name only.
+    fn script_layout(&self, record: &SessionRecord) -> Vec<ScriptLayout> {
+        record
+            .script_ids
+            .iter()
+            .filter_map(|script_id| self.scripts.get(script_id))
+            .map(|script| ScriptLayout {
+                name: script.name.clone(),
+                source: (!script.is_default).then(|| script.source.to_string()),
+                runtime: script.runtime,
+            })
+            .collect()
+    }
+
+    // Attach already loads one default agent, so the first default entry is satisfied by it.
+    fn load_layout_scripts(
+        &mut self,
+        session: &SessionInfo,
+        scripts: Vec<ScriptLayout>,
+        script_ids: &mut Vec<u64>,
+        errors: &mut Vec<String>,
+    ) {
+        let mut attach_agent = Some(session.script_id);
+        for script in scripts {
+            let loaded = match script.source {
+                None => match attach_agent.take() {
+                    Some(script_id) => Ok(script_id),
+                    None => self
+                        .load_default_script(session.session_id, None, script.runtime, true)
+                        .map(|info| info.script_id),
+                },
+                Some(source) => self
+                    .load_script(session.session_id, &source, &script.name, script.runtime, false)
+                    .map(|info| info.script_id),
+            };
+            match loaded {
+                Ok(script_id) => script_ids.push(script_id),
+                Err(e) => errors.push(format!("{}: {}", script.name, e)),
+            }
+        }
+    }
+
+    fn set_reload_on_reattach(&mut self, session_id: u64, enabled: bool) -> Result<(), String> {
+        let record = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id).to_string())?;
+        record.reload_on_reattach = enabled;
+        if !enabled {
+            let key = (record.device_id.clone(), record.pid);
+            self.reattach_scripts.remove(&key);
+        }
+        Ok(())
+    }
+
+    // Replay the scripts a dropped session left behind. The new session keeps reloading on
+    // later drops too. Pids can be reused, so this is only as good as the (device, pid) match.
+    fn reload_after_reattach(&mut self, device_id: &str, session: &SessionInfo, pid: u32) {
+        let Some(scripts) = self.reattach_scripts.remove(&(device_id.to_string(), pid)) else {
+            return;
+        };
+        if let Some(record) = self.sessions.get_mut(&session.session_id) {
+            record.reload_on_reattach = true;
+        }
+
+        let mut script_ids = Vec::new();
+        let mut errors = Vec::new();
+        self.load_layout_scripts(session, scripts, &mut script_ids, &mut errors);
+
+        self.emit(
+            "frida_scripts_reloaded",
+            json!({ "session_id": session.session_id, "script_ids": script_ids, "errors": errors }),
+        );
+    }
+
+    // Re-attach and reload scripts from an exported layout. The layout replaces anything a
+    // dropped session left for reload-on-reattach, so scripts aren't loaded twice.
+    // `pid` in each result is the one actually attached to.
+    fn import_layout(&mut self, layout: Vec<SessionLayout>) -> Vec<LayoutImportResult> {
+        // Name lookups must see the processes running now, not a list from before a restart.
+        self.process_list_cache.clear();
+
+        layout
+            .into_iter()
+            .map(|entry| {
+                let mut result = LayoutImportResult {
+                    device_id: entry.device_id.clone(),
+                    pid: entry.pid,
+                    session_id: None,
+                    script_ids: Vec::new(),
+                    errors: Vec::new(),
+                };
+
+                result.pid = match self.layout_target_pid(&entry) {
+                    Ok(pid) => pid,
+                    Err(e) => {
+                        result.errors.push(e);
+                        return result;
+                    }
+                };
+
+                if let Ok(device_id) = self.resolve_device_id(&entry.device_id) {
+                    self.reattach_scripts.remove(&(device_id, result.pid));
+                }
+
+                let session = match self.attach(&entry.device_id, result.pid) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        result.errors.push(e);
+                        return result;
+                    }
+                };
+                result.session_id = Some(session.session_id);
+
+                self.load_layout_scripts(&session, entry.scripts, &mut result.script_ids, &mut result.errors);
+
+                result
+            })
+            .collect()
+    }
+
+    // Compile `source` in a throwaway session on our own process. The script is never loaded,
+    // and the session is detached whether or not compilation succeeds.
+    fn validate_script(&self, source: &str) -> Result<ScriptValidation, String> {
+        validate_no_nul("source", source)?;
+
+        let device = self
+            .device_manager
+            .get_device_by_id("local")
+            .map_err(|e| e.to_string())?;
+        let session = device
+            .attach(std::process::id())
+            .map_err(|e| format!("Failed to open validation session: {e}"))?;
+
+        let mut options = ScriptOption::new().set_name("carf-validate");
+        let compiled = session.create_script(source, &mut options).map(drop);
+        if let Err(e) = session.detach() {
+            debug_log(&format!("validate_script: detach failed: {e}"));
+        }
+
+        Ok(match compiled {
+            Ok(()) => ScriptValidation {
+                valid: true,
+                error: None,
+                line: None,
+                column: None,
+            },
+            Err(e) => {
+                let error = e.to_string();
+                let (line, column) = parse_error_location(&error);
+                ScriptValidation {
+                    valid: false,
+                    error: Some(error),
+                    line,
+                    column,
+                }
+            }
+        })
+    }
+
+    fn load_default_script(
+        &mut self,
+        session_id: u64,
+        config: Option<serde_json::Value>,
+        runtime: Option<JsRuntime>,
+        force: bool,
+    ) -> Result<ScriptInfo, String> {
+        debug_log(&format!("load_default_script: session_id={} - begin", session_id));
+
+        // A second agent would double every hook, so reuse the loaded one unless forced. A
+        // config or runtime it wasn't loaded with can't be honoured by reuse, so that's an error.
+        if !force {
+            if let Some(script_id) = self.default_script_of(session_id)? {
+                let record = &self.scripts[&script_id];
+                let config_differs = config.as_ref().is_some_and(|c| record.config.as_ref() != Some(c));
+                let runtime_differs = runtime.is_some_and(|runtime| record.runtime != Some(runtime));
+                if config_differs || runtime_differs {
+                    return Err(FridaError::InvalidInput(format!(
+                        "session {session_id} already has a default agent with another config or \
+                         runtime; pass force to load a second one"
+                    ))
+                    .into());
+                }
+                return Ok(ScriptInfo {
+                    script_id,
+                    load_ms: 0,
+                    already_loaded: true,
+                });
+            }
+        }
+
+        let source = self.default_agent_source()?;
+        self.load_agent(session_id, &source, runtime, config)
+    }
+
+    // Loads `source` as the session's default agent, retrying transient failures.
+    fn load_agent(
+        &mut self,
+        session_id: u64,
+        source: &str,
+        runtime: Option<JsRuntime>,
+        config: Option<serde_json::Value>,
+    ) -> Result<ScriptInfo, String> {
+        let source = with_config_prologue(source, config.as_ref())?;
+
+        let mut attempt = 0;
+        loop {
+            match self.load_script(session_id, &source, DEFAULT_AGENT_NAME, runtime, true) {
+                Err(e) if attempt < self.agent_load_retries && is_transient_load_error(&e) => {
+                    attempt += 1;
+                    debug_log(&format!("load_default_script: retry {attempt} after: {e}"));
+                    self.emit(
+                        "frida_agent_load_retry",
+                        json!({
+                            "session_id": session_id,
+                            "attempt": attempt,
+                            "max_retries": self.agent_load_retries,
+                            "error": e,
+                        }),
+                    );
+                    std::thread::sleep(self.agent_load_retry_delay);
                 }
+                Ok(info) => {
+                    if let Some(record) = self.scripts.get_mut(&info.script_id) {
+                        record.config = config;
+                    }
+                    return Ok(info);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn default_script_of(&self, session_id: u64) -> Result<Option<u64>, String> {
+        let record = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(|| FridaError::SessionNotFound(session_id).to_string())?;
+        Ok(record
+            .script_ids
+            .iter()
+            .copied()
+            .find(|script_id| self.scripts.get(script_id).is_some_and(|script| script.is_default)))
+    }
+
+    fn default_agent_source(&self) -> Result<String, String> {
+        static DEFAULT_SCRIPT_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/carf_default_agent.js"));
+
+        // A watched on-disk bundle (auto-reload) takes precedence over the embedded one.
+        if let Some(source) = self.agent_watch.as_ref().and_then(|w| w.source.clone()) {
+            debug_log("load_default_script: using watched agent bundle");
+            return Ok(source);
+        }
+
+        debug_log("load_default_script: about to validate embedded script");
+        let default_script = std::str::from_utf8(DEFAULT_SCRIPT_BYTES).map_err(|e| {
+            format!(
+                "Embedded agent script (src-frida/dist/index.js) is not valid UTF-8: {e}"
+            )
+        })?;
+        if default_script.contains("__CARF_AGENT_MISSING__") {
+            return Err(
+                "Default agent bundle is missing. Build it first: `bun run compile`".to_string(),
+            );
+        }
+        if default_script.trim().is_empty() {
+            return Err("Embedded agent script (src-frida/dist/index.js) is empty".to_string());
+        }
+        validate_no_nul("default_script", default_script)?;
+        debug_log("load_default_script: embedded script validation succeeded");
+
+        Ok(default_script.to_string())
+    }
+
+    // Shared create/load/handler-registration path for every script we inject.
+    fn load_script(
+        &mut self,
+        session_id: u64,
+        source: &str,
+        name: &str,
+        runtime: Option<JsRuntime>,
+        is_default: bool,
+    ) -> Result<ScriptInfo, String> {
+        debug_log(&format!("load_script: session_id={} name={} - begin", session_id, name));
+
+        debug_log("load_script: about to get session record");
+        let record = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| "Unknown session_id".to_string())?;
+
+        debug_log("load_script: about to check if session is detached");
+        if (&*record.session).is_detached() {
+            return Err("Session is detached".to_string());
+        }
+        record.last_activity = Instant::now();
+
+        let script_id = self.next_script_id;
+        self.next_script_id = self.next_script_id.saturating_add(1);
+
+        debug_log("load_script: about to create script");
+        let load_started = Instant::now();
+        let mut options = ScriptOption::new().set_name(name);
+        if let Some(runtime) = runtime {
+            options = options.set_runtime(match runtime {
+                JsRuntime::Qjs => ScriptRuntime::QJS,
+                JsRuntime::V8 => ScriptRuntime::V8,
+            });
+        }
+
+        let script = record
+            .session
+            .create_script(source, &mut options)
+            .map_err(|e| e.to_string())?;
+        debug_log("load_script: create_script succeeded");
+
+        // Safety: frida-rust has a known bug where the ScriptHandler callback pointer becomes
+        // dangling after handle_message returns. We leak the Script to heap to ensure the
+        // internal callback_handler RefCell stays valid for the Frida GLib main loop.
+        debug_log("load_script: about to transmute script to 'static");
+        let script: Script<'static> = unsafe { std::mem::transmute(script) };
+
+        // Leak to heap so the callback handler pointer remains valid.
+        let script_ptr = Box::into_raw(Box::new(script));
+        debug_log("load_script: script leaked to heap");
+
+        // Load script FIRST, before registering message handler.
+        // This avoids the frida-rust bug where the callback pointer becomes dangling.
+        debug_log("load_script: about to script.load()");
+        unsafe {
+            (*script_ptr).load().map_err(|e| {
+                // Clean up on failure
+                let _ = Box::from_raw(script_ptr);
+                e.to_string()
+            })?;
+        }
+        debug_log("load_script: script.load() succeeded");
+
+        let messages_in = Arc::new(AtomicU64::new(0));
+
+        // Register message handler AFTER load succeeds.
+        // Use global registry to store handler data - workaround for frida-rust callback issues
+        debug_log("load_script: about to handle_message");
+        register_handler(script_id, session_id, self.app.clone(), self.event_audit.clone());
+        debug_log("load_script: handler registered in global registry");
+        
+        unsafe {
+            (*script_ptr)
+                .handle_message(TauriScriptHandler {
+                    script_id,
+                    messages_in: messages_in.clone(),
+                })
+                .map_err(|e| {
+                    // Try to unload on failure, but don't fail if unload fails
+                    let _ = (*script_ptr).unload();
+                    let _ = Box::from_raw(script_ptr);
+                    // Unregister handler on failure
+                    unregister_handler(script_id);
+                    e.to_string()
+                })?;
+        }
+        debug_log("load_script: handle_message succeeded");
+        let load_ms = load_started.elapsed().as_millis() as u64;
+
+        debug_log("load_script: about to insert script record");
+        self.scripts.insert(
+            script_id,
+            ScriptRecord {
+                session_id,
+                name: name.to_string(),
+                source: Arc::from(source),
+                runtime,
+                is_default,
+                config: None,
+                messages_in,
+                messages_out: 0,
+                loaded_at_ms: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+                script: script_ptr,
+            },
+        );
+        record.script_ids.push(script_id);
+        debug_log("load_script: script record inserted");
+
+        debug_log(&format!(
+            "load_script: name={} session_id={} => script_id={}",
+            name, session_id, script_id
+        ));
+
+        if is_default {
+            self.script_load_stats.default_agent.record(load_ms);
+        } else {
+            self.script_load_stats.other.record(load_ms);
+        }
+
+        Ok(ScriptInfo {
+            script_id,
+            load_ms,
+            already_loaded: false,
+        })
+    }
+
+    // Caller-supplied instrumentation, loaded through the same path as the default agent.
+    fn load_custom_script(
+        &mut self,
+        session_id: u64,
+        source: &str,
+        name: Option<&str>,
+        runtime: Option<JsRuntime>,
+    ) -> Result<ScriptInfo, String> {
+        let name = validate_custom_script(source, name)?;
+        self.load_script(session_id, source, name, runtime, false)
+    }
+
+    // Load the same source into several sessions; failures are reported per session.
+    // `config` gets the same `__CARF_CONFIG__` prologue as the default agent.
+    fn load_script_many(
+        &mut self,
+        session_ids: &[u64],
+        source: &str,
+        name: Option<&str>,
+        config: Option<&serde_json::Value>,
+    ) -> Result<Vec<ScriptLoadResult>, String> {
+        let name = validate_custom_script(source, name)?;
+        let source = with_config_prologue(source, config)?;
+
+        let results = session_ids
+            .iter()
+            .map(|&session_id| match self.load_script(session_id, &source, name, None, false) {
+                Ok(info) => ScriptLoadResult {
+                    session_id,
+                    script_id: Some(info.script_id),
+                    error: None,
+                },
+                Err(e) => ScriptLoadResult {
+                    session_id,
+                    script_id: None,
+                    error: Some(e),
+                },
             })
             .collect();
 
-        for session_id in detached_ids {
-            let script_ids = self
-                .sessions
-                .get(&session_id)
-                .map(|r| r.script_ids.clone())
-                .unwrap_or_default();
+        Ok(results)
+    }
 
-            debug_log(&format!(
-                "poll_detached_sessions: session_id={} detached, cleaning scripts={}",
-                session_id,
-                script_ids.len()
-            ));
+    fn unload_script(&mut self, script_id: u64) -> Result<(), String> {
+        let record = self
+            .scripts
+            .remove(&script_id)
+            .ok_or_else(|| "Unknown script_id".to_string())?;
 
-            for script_id in script_ids {
-                let _ = self.unload_script(script_id);
-            }
+        debug_log(&format!(
+            "unload_script: script_id={} session_id={}",
+            script_id, record.session_id
+        ));
 
-            let _ = self.sessions.remove(&session_id);
+        if let Some(session) = self.sessions.get_mut(&record.session_id) {
+            session.script_ids.retain(|id| *id != script_id);
+        }
 
-            let _ = self.app.emit(
-                "frida_session_detached",
-                json!({ "session_id": session_id, "reason": "disposed" }),
-            );
+        // If the session is already detached/disposed, calling into Frida to unload can be unsafe.
+        let should_unload = if let Some(session) = self.sessions.get(&record.session_id) {
+            !(&*session.session).is_detached()
+        } else {
+            false
+        };
+
+        // Safety: script was allocated via Box::into_raw in load_script.
+        let result = if should_unload {
+            unsafe { (*record.script).unload().map_err(|e| e.to_string()) }
+        } else {
+            Ok(())
+        };
+
+        // Always reclaim the leaked memory.
+        unsafe {
+            let _ = Box::from_raw(record.script);
+        }
+
+        if let Some(messages) = take_message_batch(script_id, true) {
+            self.emit_message_batch(script_id, record.session_id, messages);
         }
+
+        result
     }
 
-    fn version(&self) -> String {
-        Frida::version().to_string()
+    fn script_post(
+        &mut self,
+        script_id: u64,
+        message: serde_json::Value,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        debug_log(&format!("script_post: script_id={} - begin", script_id));
+
+        let script_ptr = self.live_script(script_id)?;
+
+        debug_log("script_post: session is valid");
+
+        if let Some(session_id) = self.scripts.get(&script_id).map(|r| r.session_id) {
+            self.touch_session(session_id);
+        }
+
+        let message_json = serde_json::to_string(&message).map_err(|e| e.to_string())?;
+        self.post_json_text(script_id, script_ptr, &message_json, data)
     }
 
-    fn list_devices(&self) -> Vec<DeviceInfo> {
-        debug_log("list_devices");
+    // Only the JSON text is checked (well-formed, no NULs); `data` is binary and passed as is.
+    fn script_post_raw(&mut self, script_id: u64, json_text: &str, data: Option<Vec<u8>>) -> Result<(), String> {
+        let script_ptr = self.live_script(script_id)?;
+        if let Some(session_id) = self.scripts.get(&script_id).map(|r| r.session_id) {
+            self.touch_session(session_id);
+        }
 
-        self.device_manager
-            .enumerate_all_devices()
-            .into_iter()
-            .map(|device| DeviceInfo {
-                id: device.get_id().to_string(),
-                name: device.get_name().to_string(),
-                device_type: device.get_type().to_string(),
-            })
-            .collect()
+        serde_json::from_str::<serde::de::IgnoredAny>(json_text)
+            .map_err(|e| FridaError::InvalidInput(format!("json_text is not valid JSON: {e}")))?;
+        self.post_json_text(script_id, script_ptr, json_text, data)
     }
 
-    fn list_processes(&mut self, device_id: &str) -> Result<Vec<ProcessInfo>, String> {
-        validate_no_nul("device_id", device_id)?;
+    fn post_json_text(
+        &mut self,
+        script_id: u64,
+        script_ptr: *mut Script<'static>,
+        message_json: &str,
+        data: Option<Vec<u8>>,
+    ) -> Result<(), String> {
+        validate_no_nul("message", message_json)?;
 
-        debug_log(&format!("list_processes: device_id={device_id}"));
+        debug_log(&format!("script_post: message_json len={}", message_json.len()));
 
-        // Skip devices that don't support process enumeration
-        if device_id == "socket" || device_id == "barebone" {
-            debug_log(&format!("list_processes: skipping unsupported device {device_id}"));
-            return Ok(Vec::new());
+        // Safety: script was allocated via Box::into_raw in load_script.
+        // We do NOT use catch_unwind here because frida-rust crashes happen in C code
+        // which catch_unwind cannot catch. Instead we just call directly.
+        debug_log("script_post: about to call script.post()");
+        let result = unsafe {
+            (*script_ptr)
+                .post(message_json, data.as_deref())
+                .map_err(|e| e.to_string())
+        };
+        debug_log(&format!("script_post: post() returned {:?}", result.is_ok()));
+
+        if result.is_ok() {
+            if let Some(record) = self.scripts.get_mut(&script_id) {
+                record.messages_out += 1;
+            }
         }
+        result
+    }
 
-        if let Some(cache) = self.process_list_cache.as_ref() {
-            if cache.device_id == device_id && cache.fetched_at.elapsed() < Duration::from_secs(2) {
-                debug_log(&format!(
-                    "list_processes: returning cached results ({} processes)",
-                    cache.processes.len()
-                ));
-                return Ok(cache.processes.clone());
+    // Resolve a script that is safe to call into: known, and its session still attached.
+    fn live_script(&self, script_id: u64) -> Result<*mut Script<'static>, String> {
+        let record = self
+            .scripts
+            .get(&script_id)
+            .ok_or_else(|| "Unknown script_id".to_string())?;
+
+        match self.sessions.get(&record.session_id) {
+            Some(session) if !(&*session.session).is_detached() => Ok(record.script),
+            _ => Err("Session is detached".to_string()),
+        }
+    }
+
+    // Requires the V8 runtime; QuickJS scripts reject the call.
+    fn enable_debugger(&mut self, script_id: u64, port: Option<u16>) -> Result<DebuggerInfo, String> {
+        let script_ptr = self.live_script(script_id)?;
+        let port = port.unwrap_or(DEFAULT_DEBUGGER_PORT);
+
+        debug_log(&format!("enable_debugger: script_id={} port={}", script_id, port));
+
+        // Safety: script was allocated via Box::into_raw in load_script.
+        unsafe {
+            (*script_ptr)
+                .enable_debugger(port)
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(DebuggerInfo { script_id, port })
+    }
+
+    fn disable_debugger(&mut self, script_id: u64) -> Result<(), String> {
+        let script_ptr = self.live_script(script_id)?;
+
+        debug_log(&format!("disable_debugger: script_id={}", script_id));
+
+        // Safety: script was allocated via Box::into_raw in load_script.
+        unsafe { (*script_ptr).disable_debugger().map_err(|e| e.to_string()) }
+    }
+
+    fn spawn(
+        &mut self,
+        device_id: &str,
+        program: String,
+        argv: Option<Vec<String>>,
+        config: SpawnConfig,
+    ) -> Result<u32, String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
+        validate_no_nul("program", &program)?;
+
+        self.process_list_cache.clear();
+
+        if let Some(ref argv) = argv {
+            for (i, arg) in argv.iter().enumerate() {
+                validate_no_nul(&format!("argv[{i}]"), arg)?;
             }
         }
+        validate_spawn_config(&config)?;
 
-        let device = self
+        let mut device = self
             .device_manager
             .get_device_by_id(device_id)
             .map_err(|e| e.to_string())?;
 
-        debug_log(&format!(
-            "list_processes: resolved device name={} type={}",
-            device.get_name(),
-            device.get_type()
-        ));
+        let mut options = SpawnOptions::new();
+        if let Some(argv) = argv {
+            options = options.argv(argv);
+        }
+        if let Some(env) = config.env {
+            options = options.env(env);
+        }
+        if let Some(cwd) = config.cwd {
+            let cwd = std::ffi::CString::new(cwd).map_err(|e| e.to_string())?;
+            options = options.cwd(cwd);
+        }
+        if let Some(stdio) = config.stdio {
+            options = options.stdio(match stdio {
+                SpawnStdioMode::Inherit => SpawnStdio::Inherit,
+                SpawnStdioMode::Pipe => SpawnStdio::Pipe,
+            });
+        }
 
-        // Wrap enumerate_processes in catch_unwind to prevent crashes from propagating
-        let processes = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            device.enumerate_processes()
-        }))
-        .map_err(|_| "enumerate_processes panicked - device may not support this operation".to_string())?;
-        
-        debug_log(&format!(
-            "list_processes: enumerated {} processes",
-            processes.len()
-        ));
+        let pid = device.spawn(program, &options).map_err(|e| e.to_string())?;
+        self.pending_spawns.insert((device_id.to_string(), pid));
 
-        debug_log("list_processes: mapping begin");
-        let infos: Vec<ProcessInfo> = processes
-            .into_iter()
-            .map(|process| ProcessInfo {
-                pid: process.get_pid(),
-                name: process.get_name().to_string(),
-            })
-            .collect();
-        debug_log(&format!("list_processes: mapping done ({} processes)", infos.len()));
+        Ok(pid)
+    }
 
-        self.process_list_cache = Some(ProcessListCache {
-            device_id: device_id.to_string(),
-            fetched_at: Instant::now(),
-            processes: infos.clone(),
-        });
+    fn resume(&mut self, device_id: &str, pid: u32) -> Result<(), String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
 
-        Ok(infos)
+        let device = self
+            .device_manager
+            .get_device_by_id(device_id)
+            .map_err(|e| e.to_string())?;
+
+        device.resume(pid).map_err(|e| e.to_string())?;
+        self.pending_spawns.remove(&(device_id.to_string(), pid));
+
+        Ok(())
     }
 
-    fn attach(&mut self, device_id: &str, pid: u32) -> Result<SessionInfo, String> {
+    fn kill(&mut self, device_id: &str, pid: u32) -> Result<(), String> {
         validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
 
-        debug_log(&format!("attach: device_id={} pid={} - begin", device_id, pid));
+        self.process_list_cache.clear();
 
-        let device = self
+        let mut device = self
             .device_manager
             .get_device_by_id(device_id)
             .map_err(|e| e.to_string())?;
 
-        debug_log("attach: about to call device.attach");
-        let session = device.attach(pid).map_err(|e| e.to_string())?;
-        debug_log("attach: device.attach succeeded");
+        device.kill(pid).map_err(|e| e.to_string())?;
+        self.pending_spawns.remove(&(device_id.to_string(), pid));
 
-        // Keep a ref to the underlying device alive for the lifetime of the session.
-        // This prevents potential use-after-free inside Frida when creating/loading scripts.
-        //
-        // Safety: Frida objects are reference-counted internally. We keep the Frida runtime alive
-        // for the lifetime of this context, so it's safe to extend lifetimes to 'static.
-        debug_log("attach: about to transmute session and device");
-        let session: Session<'static> = unsafe { std::mem::transmute(session) };
-        let device_keepalive: Device<'static> = unsafe { std::mem::transmute(device) };
-        debug_log("attach: transmute succeeded");
+        Ok(())
+    }
 
-        let session_id = self.next_session_id;
-        self.next_session_id = self.next_session_id.saturating_add(1);
+    // Kill several processes in one job; each pid gets its own result.
+    fn kill_many(&mut self, device_id: &str, pids: &[u32]) -> Result<Vec<KillResult>, String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
 
-        debug_log(&format!("attach: about to insert session_id={}", session_id));
-        self.sessions.insert(
-            session_id,
-            SessionRecord {
-                _device_id: device_id.to_string(),
-                _pid: pid,
-                session: ManuallyDrop::new(session),
-                _device: ManuallyDrop::new(device_keepalive),
-                script_ids: Vec::new(),
-            },
-        );
-        debug_log("attach: session inserted");
+        let mut device = self
+            .device_manager
+            .get_device_by_id(device_id)
+            .map_err(|e| e.to_string())?;
 
-        // Load the default agent as part of attach so the caller doesn't need a separate step.
-        debug_log("attach: about to load_default_script");
-        let script_info = match self.load_default_script(session_id) {
-            Ok(info) => {
-                debug_log(&format!("attach: load_default_script succeeded script_id={}", info.script_id));
-                info
-            },
-            Err(e) => {
-                debug_log(&format!("attach: load_default_script failed: {}", e));
-                // Best-effort cleanup so callers don't end up with a half-attached session.
-                let _ = self.sessions.remove(&session_id);
-                return Err(e);
-            }
-        };
+        let results = pids
+            .iter()
+            .map(|&pid| match device.kill(pid) {
+                Ok(()) => {
+                    self.pending_spawns.remove(&(device_id.to_string(), pid));
+                    KillResult {
+                        pid,
+                        success: true,
+                        error: None,
+                    }
+                }
+                Err(e) => KillResult {
+                    pid,
+                    success: false,
+                    error: Some(e.to_string()),
+                },
+            })
+            .collect();
 
-        debug_log(&format!(
-            "attach: device_id={} pid={} => session_id={} script_id={}",
-            device_id, pid, session_id, script_info.script_id
-        ));
+        // `device` borrows the device manager; release it before touching `self` as a whole.
+        drop(device);
+        self.invalidate_process_cache(device_id);
 
-        debug_log("attach: about to emit frida_session_attached event");
-        let _ = self.app.emit(
-            "frida_session_attached",
-            json!({ "session_id": session_id, "script_id": script_info.script_id, "device_id": device_id, "pid": pid }),
-        );
-        debug_log("attach: event emitted");
+        Ok(results)
+    }
+
+    fn invalidate_process_cache(&mut self, device_id: &str) {
+        self.process_list_cache.remove(device_id);
+    }
+
+    // While gating is on, new processes are held suspended until `frida_resume`. frida-rust
+    // exposes neither the `spawn-added` signal nor pending-spawn enumeration, so spawns are
+    // approximated by diffing the process list each tick (see `poll_spawn_gating`).
+    fn enable_spawn_gating(&mut self, device_id: &str) -> Result<(), String> {
+        validate_no_nul("device_id", device_id)?;
+        let device_id = self.resolve_device_id(device_id)?;
+        if !supports_processes(&device_id) {
+            return Err(FridaError::InvalidInput(format!("device {device_id} has no process list")).into());
+        }
+
+        let device = self
+            .device_manager
+            .get_device_by_id(&device_id)
+            .map_err(|e| e.to_string())?;
+        device.enable_spawn_gating().map_err(|e| e.to_string())?;
 
-        Ok(SessionInfo {
-            session_id,
-            script_id: script_info.script_id,
-        })
+        self.process_list_cache.clear();
+        let known = self.list_processes(&device_id)?.iter().map(|process| process.pid).collect();
+        self.spawn_gating.insert(device_id, known);
+        Ok(())
     }
 
-    fn detach(&mut self, session_id: u64) -> Result<(), String> {
-        debug_log(&format!("detach: session_id={}", session_id));
+    // Spawns still held stay suspended; resume or discard them as usual.
+    fn disable_spawn_gating(&mut self, device_id: &str) -> Result<(), String> {
+        validate_no_nul("device_id", device_id)?;
+        let device_id = self.resolve_device_id(device_id)?;
 
-        let script_ids = self
-            .sessions
-            .get(&session_id)
-            .ok_or_else(|| "Unknown session_id".to_string())?
-            .script_ids
-            .clone();
+        let device = self
+            .device_manager
+            .get_device_by_id(&device_id)
+            .map_err(|e| e.to_string())?;
+        device.disable_spawn_gating().map_err(|e| e.to_string())?;
 
-        for script_id in script_ids {
-            let _ = self.unload_script(script_id);
-        }
+        self.spawn_gating.remove(&device_id);
+        Ok(())
+    }
 
-        let record = self
-            .sessions
-            .remove(&session_id)
-            .ok_or_else(|| "Unknown session_id".to_string())?;
+    // A process-appearance heuristic: every pid that shows up while gating is on is reported,
+    // whether or not gating actually holds it, and `identifier` is only the process name.
+    // Nothing is added to `pending_spawns`, since we can't tell which of them are suspended.
+    // Goes through the process list cache, so a spawn can take up to the cache TTL to be
+    // reported; a held one stays suspended meanwhile.
+    fn poll_spawn_gating(&mut self) {
+        if self.spawn_gating.is_empty() || self.spawn_gating_checked.elapsed() < SPAWN_GATING_POLL_INTERVAL {
+            return;
+        }
+        self.spawn_gating_checked = Instant::now();
+
+        let device_ids: Vec<String> = self.spawn_gating.keys().cloned().collect();
+        for device_id in device_ids {
+            let processes = match self.list_processes(&device_id) {
+                Ok(processes) => processes,
+                Err(e) => {
+                    debug_log(&format!("poll_spawn_gating: device_id={device_id} enumerate failed: {e}"));
+                    continue;
+                }
+            };
+            let Some(known) = self.spawn_gating.get_mut(&device_id) else {
+                continue;
+            };
+            let added: Vec<&ProcessInfo> = processes.iter().filter(|process| !known.contains(&process.pid)).collect();
+            *known = processes.iter().map(|process| process.pid).collect();
 
-        match record.session.detach() {
-            Ok(()) => {
-                let _ = self.app.emit(
-                    "frida_session_detached",
-                    json!({ "session_id": session_id, "reason": "user" }),
+            for process in added {
+                self.emit(
+                    "frida_spawn_added",
+                    json!({ "device_id": device_id, "pid": process.pid, "identifier": process.name }),
                 );
-                Ok(())
             }
+        }
+    }
+
+    // Abandon a suspended spawn. Succeeds if the process is gone afterwards, even if it had
+    // already exited before we got to it.
+    fn discard_spawn(&mut self, device_id: &str, pid: u32) -> Result<(), String> {
+        validate_no_nul("device_id", device_id)?;
+        let resolved = self.resolve_device_id(device_id)?;
+        let device_id = resolved.as_str();
+
+        self.pending_spawns.remove(&(device_id.to_string(), pid));
+        self.process_list_cache.clear();
+
+        let mut device = self
+            .device_manager
+            .get_device_by_id(device_id)
+            .map_err(|e| e.to_string())?;
+
+        match device.kill(pid) {
+            Ok(()) => Ok(()),
             Err(e) => {
-                if (&*record.session).is_detached() {
-                    let _ = self.app.emit(
-                        "frida_session_detached",
-                        json!({ "session_id": session_id, "reason": "disposed" }),
-                    );
-                    Ok(())
+                let still_running = device
+                    .enumerate_processes()
+                    .iter()
+                    .any(|process| process.get_pid() == pid);
+                if still_running {
+                    Err(FridaError::KillFailed(e.to_string()).into())
                 } else {
-                    self.sessions.insert(session_id, record);
-                    Err(e.to_string())
+                    debug_log(&format!("discard_spawn: pid={} already gone", pid));
+                    Ok(())
                 }
             }
         }
     }
+}
 
-    fn load_default_script(&mut self, session_id: u64) -> Result<ScriptInfo, String> {
-        debug_log(&format!("load_default_script: session_id={} - begin", session_id));
-        static DEFAULT_SCRIPT_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/carf_default_agent.js"));
+fn emit_audited(app: &tauri::AppHandle, audit: &EventAudit, event: &str, payload: serde_json::Value) {
+    if !event_admitted(event) {
+        return;
+    }
+    record_and_emit(app, audit, event, payload);
+}
 
-        debug_log("load_default_script: about to validate embedded script");
-        let default_script = std::str::from_utf8(DEFAULT_SCRIPT_BYTES).map_err(|e| {
-            format!(
-                "Embedded agent script (src-frida/dist/index.js) is not valid UTF-8: {e}"
-            )
-        })?;
-        if default_script.contains("__CARF_AGENT_MISSING__") {
-            return Err(
-                "Default agent bundle is missing. Build it first: `bun run compile`".to_string(),
-            );
-        }
-        if default_script.trim().is_empty() {
-            return Err("Embedded agent script (src-frida/dist/index.js) is empty".to_string());
+// `emit_audited` without the rate limit, for emissions a caller is blocked on.
+fn record_and_emit(app: &tauri::AppHandle, audit: &EventAudit, event: &str, payload: serde_json::Value) {
+    let entry = AuditedEvent {
+        event: event.to_string(),
+        summary: summarize_payload(&payload),
+        timestamp_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+    };
+
+    {
+        let mut audit = audit.lock().unwrap_or_else(|e| e.into_inner());
+        if audit.len() >= EVENT_AUDIT_CAPACITY {
+            audit.pop_front();
         }
-        validate_no_nul("default_script", default_script)?;
-        debug_log("load_default_script: embedded script validation succeeded");
+        audit.push_back(entry);
+    }
 
-        debug_log("load_default_script: about to get session record");
-        let record = self
-            .sessions
-            .get_mut(&session_id)
-            .ok_or_else(|| "Unknown session_id".to_string())?;
+    let _ = app.emit(event, payload);
+}
 
-        debug_log("load_default_script: about to check if session is detached");
-        if (&*record.session).is_detached() {
-            return Err("Session is detached".to_string());
+// Keep ids and other scalars; drop bulky nested data so the audit ring stays small.
+fn summarize_payload(payload: &serde_json::Value) -> serde_json::Value {
+    let Some(fields) = payload.as_object() else {
+        return serde_json::Value::Null;
+    };
+
+    let mut summary = serde_json::Map::new();
+    for (key, value) in fields {
+        if !value.is_object() && !value.is_array() {
+            summary.insert(key.clone(), value.clone());
         }
+    }
+    if let Some(message_type) = payload.pointer("/message/type") {
+        summary.insert("message_type".to_string(), message_type.clone());
+    }
+    if let Some(payload_type) = payload.pointer("/message/payload/type") {
+        summary.insert("payload_type".to_string(), payload_type.clone());
+    }
 
-        let script_id = self.next_script_id;
-        self.next_script_id = self.next_script_id.saturating_add(1);
+    serde_json::Value::Object(summary)
+}
 
-        debug_log("load_default_script: about to create script");
-        let mut options = ScriptOption::new().set_name("carf-agent");
+// Exposes `config` to the script as `globalThis.__CARF_CONFIG__` before any of its code runs.
+fn with_config_prologue(source: &str, config: Option<&serde_json::Value>) -> Result<String, String> {
+    let Some(config) = config else {
+        return Ok(source.to_string());
+    };
+    let config_json = serde_json::to_string(config).map_err(|e| e.to_string())?;
+    validate_no_nul("config", &config_json)?;
+    Ok(format!("globalThis.__CARF_CONFIG__ = {config_json};\n{source}"))
+}
 
-        let script = record
-            .session
-            .create_script(default_script, &mut options)
-            .map_err(|e| e.to_string())?;
-        debug_log("load_default_script: create_script succeeded");
+fn replacement_params(spec: FunctionReplacementSpec) -> Result<serde_json::Value, String> {
+    validate_no_nul("target", &spec.target)?;
+    if spec.target.is_empty() {
+        return Err(FridaError::InvalidInput("target cannot be empty".to_string()).into());
+    }
 
-        // Safety: frida-rust has a known bug where the ScriptHandler callback pointer becomes
-        // dangling after handle_message returns. We leak the Script to heap to ensure the
-        // internal callback_handler RefCell stays valid for the Frida GLib main loop.
-        debug_log("load_default_script: about to transmute script to 'static");
-        let script: Script<'static> = unsafe { std::mem::transmute(script) };
+    let mut params = json!({ "target": spec.target });
+    match (spec.body, spec.return_value) {
+        (Some(body), None) => {
+            validate_no_nul("body", &body)?;
+            if body.trim().is_empty() {
+                return Err(FridaError::InvalidInput("body cannot be empty".to_string()).into());
+            }
+            params["body"] = json!(body);
+        }
+        (None, Some(return_value)) => params["returnValue"] = return_value,
+        _ => {
+            return Err(FridaError::InvalidInput("exactly one of body and return_value is required".to_string()).into())
+        }
+    }
+    if let Some(return_type) = spec.return_type {
+        validate_no_nul("return_type", &return_type)?;
+        params["returnType"] = json!(return_type);
+    }
+    if let Some(arg_types) = spec.arg_types {
+        for arg_type in &arg_types {
+            validate_no_nul("arg_types", arg_type)?;
+        }
+        params["argTypes"] = json!(arg_types);
+    }
+    Ok(params)
+}
 
-        // Leak to heap so the callback handler pointer remains valid.
-        let script_ptr = Box::into_raw(Box::new(script));
-        debug_log("load_default_script: script leaked to heap");
+// `frida_list_processes` filter: case-insensitive name substring, or an exact pid when the
+// filter is a number.
+fn process_matches(process: &ProcessInfo, filter: &str) -> bool {
+    filter.trim().parse::<u32>().is_ok_and(|pid| pid == process.pid)
+        || process.name.to_lowercase().contains(&filter.to_lowercase())
+}
 
-        // Load script FIRST, before registering message handler.
-        // This avoids the frida-rust bug where the callback pointer becomes dangling.
-        debug_log("load_default_script: about to script.load()");
-        unsafe {
-            (*script_ptr).load().map_err(|e| {
-                // Clean up on failure
-                let _ = Box::from_raw(script_ptr);
-                e.to_string()
-            })?;
+// Host output files refuse to replace an existing file unless `overwrite` is set.
+fn create_output_file(path: &str, overwrite: bool) -> Result<std::fs::File, String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    options.open(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => {
+            FridaError::InvalidInput(format!("{path} already exists (pass overwrite to replace it)")).to_string()
         }
-        debug_log("load_default_script: script.load() succeeded");
+        _ => format!("Failed to create {path}: {e}"),
+    })
+}
 
-        // Register message handler AFTER load succeeds.
-        // Use global registry to store handler data - workaround for frida-rust callback issues
-        debug_log("load_default_script: about to handle_message");
-        register_handler(script_id, session_id, self.app.clone());
-        debug_log("load_default_script: handler registered in global registry");
-        
-        unsafe {
-            (*script_ptr)
-                .handle_message(TauriScriptHandler { script_id })
-                .map_err(|e| {
-                    // Try to unload on failure, but don't fail if unload fails
-                    let _ = (*script_ptr).unload();
-                    let _ = Box::from_raw(script_ptr);
-                    // Unregister handler on failure
-                    unregister_handler(script_id);
-                    e.to_string()
-                })?;
+fn read_agent_bundle(path: &std::path::Path) -> Result<String, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read agent bundle {}: {e}", path.display()))?;
+    if source.trim().is_empty() {
+        return Err(format!("Agent bundle {} is empty", path.display()));
+    }
+    validate_no_nul("agent_bundle", &source)?;
+    Ok(source)
+}
+
+fn validate_spawn_config(config: &SpawnConfig) -> Result<(), String> {
+    for (i, (key, value)) in config.env.iter().flatten().enumerate() {
+        validate_no_nul(&format!("env[{i}] key"), key)?;
+        validate_no_nul(&format!("env[{i}] value"), value)?;
+        if key.is_empty() || key.contains('=') {
+            return Err(FridaError::InvalidInput(format!("env[{i}]: invalid variable name {key:?}")).into());
+        }
+    }
+    if let Some(cwd) = &config.cwd {
+        validate_no_nul("cwd", cwd)?;
+        if cwd.is_empty() {
+            return Err(FridaError::InvalidInput("cwd cannot be empty".to_string()).into());
         }
-        debug_log("load_default_script: handle_message succeeded");
+    }
+    Ok(())
+}
 
-        debug_log("load_default_script: about to insert script record");
-        self.scripts.insert(
-            script_id,
-            ScriptRecord {
-                session_id,
-                script: script_ptr,
-            },
-        );
-        record.script_ids.push(script_id);
-        debug_log("load_default_script: script record inserted");
+fn parse_js_runtime(runtime: Option<&str>) -> Result<Option<JsRuntime>, String> {
+    match runtime {
+        None => Ok(None),
+        Some("qjs") => Ok(Some(JsRuntime::Qjs)),
+        Some("v8") => Ok(Some(JsRuntime::V8)),
+        Some(other) => {
+            Err(FridaError::InvalidInput(format!("unknown runtime {other:?} (expected \"qjs\" or \"v8\")")).into())
+        }
+    }
+}
 
-        debug_log(&format!(
-            "load_default_script: session_id={} => script_id={}",
-            session_id, script_id
-        ));
+// Returns the script name to use (`carf-script` when none is given).
+fn validate_custom_script<'a>(source: &str, name: Option<&'a str>) -> Result<&'a str, String> {
+    validate_no_nul("source", source)?;
+    let name = name.unwrap_or("carf-script");
+    validate_no_nul("name", name)?;
 
-        Ok(ScriptInfo { script_id })
+    if source.trim().is_empty() {
+        return Err(FridaError::InvalidInput("source cannot be empty".to_string()).into());
     }
+    Ok(name)
+}
 
-    fn unload_script(&mut self, script_id: u64) -> Result<(), String> {
-        let record = self
-            .scripts
-            .remove(&script_id)
-            .ok_or_else(|| "Unknown script_id".to_string())?;
+// `host` or `host:port`; IPv6 hosts need brackets when a port is given (`[::1]:27042`).
+fn validate_remote_address(address: &str) -> Result<(), String> {
+    let invalid = || -> String { FridaError::InvalidInput(format!("invalid remote address: {address}")).into() };
+    if address.trim().is_empty() || address.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let host = match address.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+            port.parse::<u16>().ok().filter(|port| *port != 0).ok_or_else(invalid)?;
+            host
+        }
+        // No port, or a bare IPv6 address.
+        Some(_) | None => address,
+    };
+    if host.is_empty() || host == "[]" {
+        return Err(invalid());
+    }
+    Ok(())
+}
 
-        debug_log(&format!(
-            "unload_script: script_id={} session_id={}",
-            script_id, record.session_id
-        ));
+// Accepts `0x`-prefixed hex (as the agent reports addresses) or plain decimal.
+fn parse_address(address: &str) -> Result<u64, String> {
+    let trimmed = address.trim();
+    let parsed = match trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => trimmed.parse::<u64>(),
+    };
+    parsed.map_err(|_| FridaError::InvalidInput(format!("invalid address: {address}")).into())
+}
 
-        if let Some(session) = self.sessions.get_mut(&record.session_id) {
-            session.script_ids.retain(|id| *id != script_id);
-        }
+// Pull a line/column out of a compile error. Frida reports either "... line N ... column M"
+// or a "file.js:N:M: ..." prefix depending on the runtime.
+fn parse_error_location(error: &str) -> (Option<u32>, Option<u32>) {
+    let lower = error.to_ascii_lowercase();
+    let number_after = |marker: &str| -> Option<u32> {
+        let rest = &lower[lower.find(marker)? + marker.len()..];
+        let digits: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok()
+    };
 
-        // If the session is already detached/disposed, calling into Frida to unload can be unsafe.
-        let should_unload = if let Some(session) = self.sessions.get(&record.session_id) {
-            !(&*session.session).is_detached()
-        } else {
-            false
-        };
+    if let Some(line) = number_after("line") {
+        return (Some(line), number_after("column").or_else(|| number_after("col")));
+    }
 
-        // Safety: script was allocated via Box::into_raw in load_default_script.
-        let result = if should_unload {
-            unsafe { (*record.script).unload().map_err(|e| e.to_string()) }
-        } else {
-            Ok(())
-        };
+    let parts: Vec<&str> = error.split(':').collect();
+    parts
+        .windows(2)
+        .find_map(|pair| Some((pair[0].trim().parse().ok()?, pair[1].trim().parse().ok()?)))
+        .map_or((None, None), |(line, column)| (Some(line), Some(column)))
+}
 
-        // Always reclaim the leaked memory.
-        unsafe {
-            let _ = Box::from_raw(record.script);
-        }
+// frida-rust's name getters decode with `to_str().unwrap_or_default()`, so a name that isn't
+// valid UTF-8 comes back empty. These read the raw C string instead. frida-rust keeps the
+// handles private, but `Device` and `Process` are each a lone pointer plus `PhantomData`.
+fn device_name_ptr(device: &Device) -> *const c_char {
+    unsafe {
+        let raw = *(device as *const Device as *const *mut frida_sys::_FridaDevice);
+        frida_sys::frida_device_get_name(raw)
+    }
+}
 
-        result
+fn process_name_ptr(process: &Process) -> *const c_char {
+    unsafe {
+        let raw = *(process as *const Process as *const *mut frida_sys::_FridaProcess);
+        frida_sys::frida_process_get_name(raw)
     }
+}
 
-    fn script_post(
-        &mut self,
-        script_id: u64,
-        message: serde_json::Value,
-        data: Option<Vec<u8>>,
-    ) -> Result<(), String> {
-        debug_log(&format!("script_post: script_id={} - begin", script_id));
+// `raw` must be null or a NUL-terminated string that outlives the call.
+fn read_name(raw: *const c_char, fallback: &str) -> (String, bool) {
+    if raw.is_null() {
+        return (fallback.to_string(), true);
+    }
+    decode_name(unsafe { CStr::from_ptr(raw) }, fallback)
+}
 
-        let record = self
-            .scripts
-            .get(&script_id)
-            .ok_or_else(|| "Unknown script_id".to_string())?;
+// Invalid UTF-8 is replaced with U+FFFD and an empty name with `fallback`; either one marks
+// the name as sanitized.
+fn decode_name(name: &CStr, fallback: &str) -> (String, bool) {
+    match name.to_string_lossy() {
+        Cow::Borrowed("") => (fallback.to_string(), true),
+        Cow::Borrowed(name) => (name.to_string(), false),
+        Cow::Owned(name) => (name, true),
+    }
+}
 
-        debug_log("script_post: found script record");
+// Timeouts and not-yet-ready sessions are worth retrying; compile and runtime errors in the
+// agent itself are not, nor is a session that's already gone.
+fn is_transient_load_error(error: &str) -> bool {
+    const TRANSIENT: [&str; 5] = ["timeout", "timed out", "not ready", "connection", "transport"];
+    const PERMANENT: [&str; 4] = ["syntaxerror", "referenceerror", "typeerror", "session is detached"];
 
-        if let Some(session) = self.sessions.get(&record.session_id) {
-            if (&*session.session).is_detached() {
-                return Err("Session is detached".to_string());
-            }
-        } else {
-            return Err("Session is detached".to_string());
+    let error = error.to_ascii_lowercase();
+    !PERMANENT.iter().any(|marker| error.contains(marker))
+        && TRANSIENT.iter().any(|marker| error.contains(marker))
+}
+
+// Numeric ops take a number or a decimal/hex string; `contains` takes a string.
+fn validate_hook_condition(condition: &HookCondition) -> Result<(), String> {
+    if condition.arg_index > MAX_HOOK_ARG_INDEX {
+        return Err(format!("arg_index must be at most {MAX_HOOK_ARG_INDEX}"));
+    }
+    match (condition.op.as_str(), &condition.value) {
+        ("contains", serde_json::Value::String(s)) if !s.is_empty() => Ok(()),
+        ("contains", _) => Err("contains requires a non-empty string value".to_string()),
+        ("eq" | "ne" | "gt" | "lt", serde_json::Value::Number(n)) if n.is_u64() => Ok(()),
+        ("eq" | "ne" | "gt" | "lt", serde_json::Value::String(s)) => parse_address(s).map(drop),
+        ("eq" | "ne" | "gt" | "lt", _) => Err("numeric ops require an unsigned integer value".to_string()),
+        (op, _) => Err(format!("unknown op {op}; expected eq, ne, gt, lt or contains")),
+    }
+}
+
+// FNV-1a 64: stable across runs and trivial to reproduce in the frontend for diffing.
+fn source_hash(source: &str) -> String {
+    let hash = source
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    format!("{hash:016x}")
+}
+
+// Checks names, kinds and that every field ends within MAX_STRUCT_SPAN; returns the span.
+// Pointers are sized as 8 bytes so the bound holds on 64-bit targets.
+fn validate_struct_layout(fields: &[StructField]) -> Result<u64, String> {
+    if fields.is_empty() || fields.len() > MAX_STRUCT_FIELDS {
+        return Err(format!("layout must have 1-{MAX_STRUCT_FIELDS} fields"));
+    }
+
+    let mut names = HashSet::new();
+    let mut span = 0;
+    for field in fields {
+        if field.name.is_empty() || !names.insert(field.name.as_str()) {
+            return Err(format!("field names must be unique and non-empty: {:?}", field.name));
         }
+        let size = match field.kind.as_str() {
+            "s8" | "u8" | "utf8" => 1,
+            "s16" | "u16" => 2,
+            "s32" | "u32" | "float" => 4,
+            "s64" | "u64" | "double" | "pointer" => 8,
+            other => return Err(format!("field {}: unknown kind {other}", field.name)),
+        };
+        let end = field.offset.saturating_add(size);
+        if end > MAX_STRUCT_SPAN {
+            return Err(format!("field {} ends past the {MAX_STRUCT_SPAN}-byte limit", field.name));
+        }
+        span = span.max(end);
+    }
+    Ok(span)
+}
 
-        debug_log("script_post: session is valid");
+// Maps the agent's module observer events to backend event names.
+fn module_event(returns: &serde_json::Value) -> Option<(&'static str, serde_json::Value)> {
+    let event = match returns.get("event")?.as_str()? {
+        "module_added" => "frida_module_loaded",
+        "module_removed" => "frida_module_unloaded",
+        _ => return None,
+    };
+    let mut module = returns.clone();
+    module.as_object_mut()?.remove("event");
+    Some((event, module))
+}
 
-        let message_json = serde_json::to_string(&message).map_err(|e| e.to_string())?;
-        validate_no_nul("message", &message_json)?;
+// `base  size  prot  file` for the text memory map; unbacked ranges get an empty file column.
+fn memory_map_line(range: &serde_json::Value) -> String {
+    let base = range.get("base").and_then(|v| v.as_str()).unwrap_or("?");
+    let size = range.get("size").and_then(|v| v.as_u64()).unwrap_or(0);
+    let protection = range.get("protection").and_then(|v| v.as_str()).unwrap_or("---");
+    let file = range
+        .get("file")
+        .and_then(|f| f.get("path"))
+        .and_then(|p| p.as_str())
+        .unwrap_or("");
+    format!("{base:<18} {size:#12x} {protection} {file}").trim_end().to_string()
+}
 
-        debug_log(&format!("script_post: message_json len={}", message_json.len()));
+// Paired interceptor calls (`interceptor_attach` with `paired`), minus the event tag.
+fn hook_call_event(returns: &serde_json::Value) -> Option<serde_json::Value> {
+    if returns.get("event")?.as_str()? != "hook_call" {
+        return None;
+    }
+    let mut call = returns.clone();
+    call.as_object_mut()?.remove("event");
+    Some(call)
+}
 
-        let script_ptr = record.script;
+// RPC replies whose payload doesn't fit frida-rust's send layout (error replies carry the
+// exception name and stack after the message) arrive as raw `Message::Other` values:
+//   { "type": "send", "payload": ["frida:rpc", id, "ok" | "error", value, ...] }
+fn frida_rpc_reply(value: &serde_json::Value) -> Option<(u64, Result<serde_json::Value, String>)> {
+    if value.get("type")?.as_str()? != "send" {
+        return None;
+    }
+    let payload = value.get("payload")?.as_array()?;
+    if payload.first()?.as_str()? != "frida:rpc" {
+        return None;
+    }
+    let id = payload.get(1)?.as_u64()?;
+    let returns = payload.get(3).cloned().unwrap_or(serde_json::Value::Null);
+    let reply = match payload.get(2)?.as_str()? {
+        "ok" => Ok(returns),
+        _ => Err(returns.as_str().unwrap_or("rpc call failed").to_string()),
+    };
+    Some((id, reply))
+}
 
-        // Safety: script was allocated via Box::into_raw in load_default_script.
-        // We do NOT use catch_unwind here because frida-rust crashes happen in C code
-        // which catch_unwind cannot catch. Instead we just call directly.
-        debug_log("script_post: about to call script.post()");
-        let result = unsafe {
-            (*script_ptr)
-                .post(&message_json, data.as_deref())
-                .map_err(|e| e.to_string())
-        };
-        debug_log(&format!("script_post: post() returned {:?}", result.is_ok()));
+// Protocol messages some agents send outside `send()`, as `Message::Other`:
+//   { "__carf_rpc_reply": id, "result": "ok" | "error", "returns": ... }
+//   { "__carf_ack": id }
+//   { "__carf_chunk": { "stream": "s", "index": 0, "last": false, "data": "..." } }
+#[derive(Debug, PartialEq)]
+enum OtherRoute {
+    RpcReply { id: u64, reply: Result<serde_json::Value, String> },
+    Ack(u64),
+    Chunk(MessageChunk),
+}
 
-        result
+#[derive(Debug, PartialEq)]
+struct MessageChunk {
+    stream: String,
+    index: u64,
+    last: bool,
+    data: String,
+}
+
+// `None` for unmarked payloads, malformed markers and markers disabled in `routing`.
+fn classify_other_message(value: &serde_json::Value, routing: OtherMessageRouting) -> Option<OtherRoute> {
+    let object = value.as_object()?;
+    if let Some(id) = object.get("__carf_rpc_reply") {
+        if !routing.rpc_reply {
+            return None;
+        }
+        let returns = object.get("returns").cloned().unwrap_or(serde_json::Value::Null);
+        let reply = match object.get("result").and_then(|r| r.as_str()) {
+            Some("ok") => Ok(returns),
+            _ => Err(agent_error_message(&returns)),
+        };
+        return Some(OtherRoute::RpcReply { id: id.as_u64()?, reply });
     }
+    if let Some(id) = object.get("__carf_ack") {
+        return routing.ack.then_some(OtherRoute::Ack(id.as_u64()?));
+    }
+    let chunk = object.get("__carf_chunk")?;
+    if !routing.chunk {
+        return None;
+    }
+    Some(OtherRoute::Chunk(MessageChunk {
+        stream: chunk.get("stream")?.as_str()?.to_string(),
+        index: chunk.get("index")?.as_u64()?,
+        last: chunk.get("last").and_then(|l| l.as_bool()).unwrap_or(false),
+        data: chunk.get("data")?.as_str()?.to_string(),
+    }))
+}
 
-    fn spawn(
-        &mut self,
-        device_id: &str,
-        program: String,
-        argv: Option<Vec<String>>,
-    ) -> Result<u32, String> {
-        validate_no_nul("device_id", device_id)?;
-        validate_no_nul("program", &program)?;
+// One in-progress `__carf_chunk` stream. Chunks must arrive in index order, which Frida
+// guarantees as long as the agent sends them in order.
+#[derive(Default)]
+struct ChunkAssembly {
+    next_index: u64,
+    data: String,
+}
 
-        self.process_list_cache = None;
+impl ChunkAssembly {
+    // Returns the whole payload once the last chunk is in.
+    fn push(&mut self, chunk: MessageChunk) -> Result<Option<String>, String> {
+        if chunk.index != self.next_index {
+            return Err(format!(
+                "stream {}: expected chunk {}, got {}",
+                chunk.stream, self.next_index, chunk.index
+            ));
+        }
+        if self.data.len() + chunk.data.len() > MAX_CHUNKED_MESSAGE_BYTES {
+            return Err(format!("stream {}: exceeds {MAX_CHUNKED_MESSAGE_BYTES} bytes", chunk.stream));
+        }
+        self.data.push_str(&chunk.data);
+        self.next_index += 1;
+        Ok(chunk.last.then(|| std::mem::take(&mut self.data)))
+    }
+}
 
-        if let Some(ref argv) = argv {
-            for (i, arg) in argv.iter().enumerate() {
-                validate_no_nul(&format!("argv[{i}]"), arg)?;
-            }
+// Messages of one script waiting to go out together. The window starts at the first message.
+struct MessageBatch {
+    window: Duration,
+    started: Option<Instant>,
+    messages: Vec<serde_json::Value>,
+}
+
+enum BatchOutcome {
+    Unbatched(serde_json::Value),
+    Queued,
+    Full(Vec<serde_json::Value>),
+}
+
+impl MessageBatch {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            started: None,
+            messages: Vec::new(),
         }
+    }
 
-        let mut device = self
-            .device_manager
-            .get_device_by_id(device_id)
-            .map_err(|e| e.to_string())?;
+    // Returns the batch when this message fills it.
+    fn push(&mut self, message: serde_json::Value, now: Instant) -> Option<Vec<serde_json::Value>> {
+        self.started.get_or_insert(now);
+        self.messages.push(message);
+        if self.messages.len() >= MAX_MESSAGE_BATCH_LEN {
+            return self.take();
+        }
+        None
+    }
 
-        let mut options = SpawnOptions::new();
-        if let Some(argv) = argv {
-            options = options.argv(argv);
+    fn take_due(&mut self, now: Instant) -> Option<Vec<serde_json::Value>> {
+        let started = self.started?;
+        if now.duration_since(started) < self.window {
+            return None;
         }
+        self.take()
+    }
 
-        device.spawn(program, &options).map_err(|e| e.to_string())
+    fn take(&mut self) -> Option<Vec<serde_json::Value>> {
+        self.started = None;
+        (!self.messages.is_empty()).then(|| std::mem::take(&mut self.messages))
     }
+}
 
-    fn resume(&mut self, device_id: &str, pid: u32) -> Result<(), String> {
-        validate_no_nul("device_id", device_id)?;
+fn variant_json(variant: Variant) -> serde_json::Value {
+    match variant {
+        Variant::String(s) => json!(s),
+        Variant::Int64(n) => json!(n),
+        Variant::Boolean(b) => json!(b),
+        Variant::Map(map) => variant_map_json(map),
+        Variant::MapList(list) => list.into_iter().map(variant_map_json).collect(),
+    }
+}
 
-        let device = self
-            .device_manager
-            .get_device_by_id(device_id)
-            .map_err(|e| e.to_string())?;
+fn variant_map_json(map: HashMap<String, Variant>) -> serde_json::Value {
+    map.into_iter()
+        .map(|(key, value)| (key, variant_json(value)))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
 
-        device.resume(pid).map_err(|e| e.to_string())
+// Exact, case-sensitive match. More than one match is an error rather than a guess.
+fn unique_process_named(processes: &[ProcessInfo], name: &str) -> Result<u32, String> {
+    let pids: Vec<u32> = processes
+        .iter()
+        .filter(|process| process.name == name)
+        .map(|process| process.pid)
+        .collect();
+    match pids.as_slice() {
+        [] => Err(format!("No running process named {name:?}")),
+        [pid] => Ok(*pid),
+        _ => {
+            let pids = pids.iter().map(u32::to_string).collect::<Vec<_>>().join(", ");
+            Err(format!("Several processes named {name:?} (pids {pids}); attach by pid instead"))
+        }
     }
+}
 
-    fn kill(&mut self, device_id: &str, pid: u32) -> Result<(), String> {
-        validate_no_nul("device_id", device_id)?;
+// (added, removed) between two enumerations, matched by device id.
+fn device_changes(
+    known: &HashMap<String, DeviceInfo>,
+    current: &[DeviceInfo],
+) -> (Vec<DeviceInfo>, Vec<DeviceInfo>) {
+    let added = current
+        .iter()
+        .filter(|device| !known.contains_key(&device.id))
+        .cloned()
+        .collect();
+    let current_ids: HashSet<&str> = current.iter().map(|device| device.id.as_str()).collect();
+    let mut removed: Vec<DeviceInfo> = known
+        .values()
+        .filter(|device| !current_ids.contains(device.id.as_str()))
+        .cloned()
+        .collect();
+    removed.sort_by(|a, b| a.id.cmp(&b.id));
+    (added, removed)
+}
 
-        self.process_list_cache = None;
+fn device_labels_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(DEVICE_LABELS_FILE))
+}
 
-        let mut device = self
-            .device_manager
-            .get_device_by_id(device_id)
-            .map_err(|e| e.to_string())?;
+// A missing or unreadable file just means no labels yet.
+fn load_device_labels(app: &tauri::AppHandle) -> HashMap<String, DeviceLabel> {
+    device_labels_path(app)
+        .ok()
+        .and_then(|path| std::fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
 
-        device.kill(pid).map_err(|e| e.to_string())
+fn save_device_labels(app: &tauri::AppHandle, labels: &HashMap<String, DeviceLabel>) -> Result<(), String> {
+    let path = device_labels_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
     }
+    let json = serde_json::to_vec_pretty(labels).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+// Socket and barebone devices can't enumerate or manage processes.
+fn supports_processes(device_id: &str) -> bool {
+    device_id != "socket" && device_id != "barebone"
 }
 
 // Global storage for handler data - workaround for frida-rust callback lifetime issues
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::sync::OnceLock;
 
+// `frida_eval` runs arbitrary code in the target, so it stays off until explicitly enabled.
+static EVAL_ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Per-event emission caps set with `frida_set_event_rate_limit`, shared by every workspace and
+// script handler. Events without an entry are unlimited.
+static EVENT_RATE_LIMITS: Mutex<Option<HashMap<String, EventRateLimit>>> = Mutex::new(None);
+
+const EVENT_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+// Markers consumed from `Message::Other` payloads; everything is routed by default.
+static OTHER_MESSAGE_ROUTING: Mutex<OtherMessageRouting> = Mutex::new(OtherMessageRouting::ALL);
+
+// A reassembled `__carf_chunk` stream larger than this is dropped.
+const MAX_CHUNKED_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+// Message batching: the window is capped, and a batch that fills up is emitted right away
+// instead of waiting for its window.
+const MAX_MESSAGE_BATCH_WINDOW: Duration = Duration::from_secs(5);
+const MAX_MESSAGE_BATCH_LEN: usize = 1000;
+
+// Fixed one-second windows; emissions past `max_per_sec` in a window are dropped and counted.
+struct EventRateLimit {
+    max_per_sec: u32,
+    window_start: Instant,
+    sent: u32,
+    dropped: u64,
+    last_report: Instant,
+}
+
+impl EventRateLimit {
+    fn new(max_per_sec: u32, now: Instant) -> Self {
+        Self {
+            max_per_sec,
+            window_start: now,
+            sent: 0,
+            dropped: 0,
+            last_report: now,
+        }
+    }
+
+    fn admit(&mut self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.window_start) >= EVENT_RATE_WINDOW {
+            self.window_start = now;
+            self.sent = 0;
+        }
+        if self.sent < self.max_per_sec {
+            self.sent += 1;
+            true
+        } else {
+            self.dropped += 1;
+            false
+        }
+    }
+
+    // Drops accumulated since the last report, at most once per window.
+    fn take_dropped(&mut self, now: Instant) -> Option<u64> {
+        if self.dropped == 0 || now.saturating_duration_since(self.last_report) < EVENT_RATE_WINDOW {
+            return None;
+        }
+        self.last_report = now;
+        Some(std::mem::take(&mut self.dropped))
+    }
+}
+
+fn event_admitted(event: &str) -> bool {
+    let mut limits = EVENT_RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+    match limits.as_mut().and_then(|limits| limits.get_mut(event)) {
+        Some(limit) => limit.admit(Instant::now()),
+        None => true,
+    }
+}
+
 struct HandlerRegistry {
-    handlers: HashMap<u64, (tauri::AppHandle, u64)>, // script_id -> (app, session_id)
+    handlers: HashMap<u64, (tauri::AppHandle, u64, EventAudit)>, // script_id -> (app, session_id, audit)
+    // Replies the worker is blocking on (e.g. flush echoes), keyed by request id.
+    pending_replies: HashMap<u64, Sender<AgentReply>>,
+    // Scripts whose message `data` goes over a raw IPC channel instead of inline JSON.
+    binary_channels: HashMap<u64, BinaryChannel>,
+    // Last uncaught error per script, kept until the script is unloaded or replaced.
+    last_errors: HashMap<u64, ScriptError>,
+    // Scripts with message statistics enabled; absent means not collecting.
+    message_stats: HashMap<u64, MessageStats>,
+    // Partially received `__carf_chunk` streams, keyed by (script_id, stream).
+    chunk_streams: HashMap<(u64, String), ChunkAssembly>,
+    // Scripts whose `frida_script_message`s are coalesced into `frida_script_message_batch`.
+    message_batches: HashMap<u64, MessageBatch>,
+}
+
+struct BinaryChannel {
+    channel: Channel,
+    next_seq: u64,
 }
 
 static HANDLER_REGISTRY: OnceLock<Mutex<HandlerRegistry>> = OnceLock::new();
@@ -704,25 +5324,158 @@ fn get_handler_registry() -> &'static Mutex<HandlerRegistry> {
     HANDLER_REGISTRY.get_or_init(|| {
         Mutex::new(HandlerRegistry {
             handlers: HashMap::new(),
+            pending_replies: HashMap::new(),
+            binary_channels: HashMap::new(),
+            last_errors: HashMap::new(),
+            message_stats: HashMap::new(),
+            chunk_streams: HashMap::new(),
+            message_batches: HashMap::new(),
         })
     })
 }
 
-fn register_handler(script_id: u64, session_id: u64, app: tauri::AppHandle) {
+fn register_handler(script_id: u64, session_id: u64, app: tauri::AppHandle, audit: EventAudit) {
     if let Ok(mut registry) = get_handler_registry().lock() {
-        registry.handlers.insert(script_id, (app, session_id));
+        registry.handlers.insert(script_id, (app, session_id, audit));
     }
 }
 
 fn unregister_handler(script_id: u64) {
     if let Ok(mut registry) = get_handler_registry().lock() {
         registry.handlers.remove(&script_id);
+        registry.binary_channels.remove(&script_id);
+        registry.last_errors.remove(&script_id);
+        registry.message_stats.remove(&script_id);
+        registry.chunk_streams.retain(|(id, _), _| *id != script_id);
+        registry.message_batches.remove(&script_id);
+    }
+}
+
+// Queues a `frida_script_message` payload if the script is batching. Returns it untouched when
+// it should be emitted on its own, or the whole batch once that is full.
+fn batch_message(script_id: u64, payload: serde_json::Value) -> BatchOutcome {
+    let Ok(mut registry) = get_handler_registry().lock() else {
+        return BatchOutcome::Unbatched(payload);
+    };
+    match registry.message_batches.get_mut(&script_id) {
+        Some(batch) => match batch.push(payload, Instant::now()) {
+            Some(messages) => BatchOutcome::Full(messages),
+            None => BatchOutcome::Queued,
+        },
+        None => BatchOutcome::Unbatched(payload),
+    }
+}
+
+// With `force`, takes whatever is queued; otherwise only a batch whose window has passed.
+fn take_message_batch(script_id: u64, force: bool) -> Option<Vec<serde_json::Value>> {
+    let mut registry = get_handler_registry().lock().ok()?;
+    let batch = registry.message_batches.get_mut(&script_id)?;
+    if force {
+        batch.take()
+    } else {
+        batch.take_due(Instant::now())
+    }
+}
+
+// A finished or broken stream is forgotten; the next chunk with its name starts over.
+fn push_message_chunk(script_id: u64, chunk: MessageChunk) -> Result<Option<String>, String> {
+    let mut registry = get_handler_registry().lock().map_err(|e| e.to_string())?;
+    let key = (script_id, chunk.stream.clone());
+    let result = registry.chunk_streams.entry(key.clone()).or_default().push(chunk);
+    if !matches!(result, Ok(None)) {
+        registry.chunk_streams.remove(&key);
+    }
+    result
+}
+
+// `None` when stats are not being collected for the script.
+fn with_message_stats<T>(script_id: u64, f: impl FnOnce(&mut MessageStats) -> T) -> Option<T> {
+    let mut registry = get_handler_registry().lock().ok()?;
+    registry.message_stats.get_mut(&script_id).map(f)
+}
+
+fn last_script_error(script_id: u64) -> Option<ScriptError> {
+    get_handler_registry()
+        .lock()
+        .ok()
+        .and_then(|registry| registry.last_errors.get(&script_id).cloned())
+}
+
+fn set_binary_channel(script_id: u64, channel: Option<Channel>) {
+    if let Ok(mut registry) = get_handler_registry().lock() {
+        match channel {
+            Some(channel) => {
+                registry
+                    .binary_channels
+                    .insert(script_id, BinaryChannel { channel, next_seq: 0 });
+            }
+            None => {
+                registry.binary_channels.remove(&script_id);
+            }
+        }
+    }
+}
+
+// Frame layout: script_id (u64 LE), seq (u64 LE), then the raw data. Returns the sequence
+// number the JSON message should reference, or `None` if the script has no channel or the
+// frontend dropped it (in which case data falls back to inline JSON).
+fn send_binary_frame(script_id: u64, data: &[u8]) -> Option<u64> {
+    let mut registry = get_handler_registry().lock().ok()?;
+    let binary = registry.binary_channels.get_mut(&script_id)?;
+    let seq = binary.next_seq;
+
+    let mut frame = Vec::with_capacity(16 + data.len());
+    frame.extend_from_slice(&script_id.to_le_bytes());
+    frame.extend_from_slice(&seq.to_le_bytes());
+    frame.extend_from_slice(data);
+
+    if binary.channel.send(InvokeResponseBody::Raw(frame)).is_err() {
+        registry.binary_channels.remove(&script_id);
+        return None;
+    }
+    binary.next_seq += 1;
+    Some(seq)
+}
+
+fn register_pending_reply(id: u64, tx: Sender<AgentReply>) {
+    if let Ok(mut registry) = get_handler_registry().lock() {
+        registry.pending_replies.insert(id, tx);
+    }
+}
+
+fn unregister_pending_reply(id: u64) {
+    if let Ok(mut registry) = get_handler_registry().lock() {
+        registry.pending_replies.remove(&id);
+    }
+}
+
+// Hand a reply to the waiting worker. Returns false if nobody is waiting for `id`.
+fn complete_pending_reply(id: u64, reply: AgentReply) -> bool {
+    let tx = match get_handler_registry().lock() {
+        Ok(mut registry) => registry.pending_replies.remove(&id),
+        Err(_) => None,
+    };
+    match tx {
+        Some(tx) => {
+            let _ = tx.send(reply);
+            true
+        }
+        None => false,
     }
 }
 
+fn agent_error_message(returns: &serde_json::Value) -> String {
+    returns
+        .get("message")
+        .and_then(|m| m.as_str())
+        .unwrap_or("Agent error")
+        .to_string()
+}
+
 #[derive(Clone)]
 struct TauriScriptHandler {
     script_id: u64,
+    messages_in: Arc<AtomicU64>,
 }
 
 impl ScriptHandler for TauriScriptHandler {
@@ -731,18 +5484,22 @@ impl ScriptHandler for TauriScriptHandler {
             "on_message: script_id={} - begin",
             self.script_id
         ));
+        self.messages_in.fetch_add(1, Ordering::Relaxed);
 
         // Get handler data from global registry
-        let (app, session_id) = {
-            let registry = match get_handler_registry().lock() {
+        let (app, session_id, audit) = {
+            let mut registry = match get_handler_registry().lock() {
                 Ok(r) => r,
                 Err(_) => {
                     debug_log("on_message: failed to lock registry");
                     return;
                 }
             };
+            if let Some(stats) = registry.message_stats.get_mut(&self.script_id) {
+                stats.record(&message, msg_data.as_deref());
+            }
             match registry.handlers.get(&self.script_id) {
-                Some((app, session_id)) => (app.clone(), *session_id),
+                Some((app, session_id, audit)) => (app.clone(), *session_id, audit.clone()),
                 None => {
                     debug_log("on_message: handler not found in registry");
                     return;
@@ -755,6 +5512,99 @@ impl ScriptHandler for TauriScriptHandler {
             session_id, self.script_id
         ));
 
+        // Flush echoes and replies to backend-originated requests never reach the frontend.
+        if let Message::Send(m) = &message {
+            let id = m.payload.id as u64;
+            match m.payload.r#type.as_str() {
+                "carf:flush" => {
+                    complete_pending_reply(id, Ok((serde_json::Value::Null, None)));
+                    return;
+                }
+                // Module observer and paired hook events become first-class backend events
+                // instead of raw messages; other agent events are forwarded below.
+                "carf:event" => {
+                    if let Some((event, module)) = module_event(&m.payload.returns) {
+                        emit_audited(
+                            &app,
+                            &audit,
+                            event,
+                            json!({ "session_id": session_id, "script_id": self.script_id, "module": module }),
+                        );
+                        return;
+                    }
+                    if let Some(call) = hook_call_event(&m.payload.returns) {
+                        emit_audited(
+                            &app,
+                            &audit,
+                            "frida_hook_call",
+                            json!({ "session_id": session_id, "script_id": self.script_id, "call": call }),
+                        );
+                        return;
+                    }
+                }
+                // Replies to `frida_script_rpc_call`; on error `returns` is the message.
+                "frida:rpc" if id >= INTERNAL_REPLY_ID_BASE => {
+                    let reply = if m.payload.result == "ok" {
+                        Ok((m.payload.returns.clone(), msg_data.clone()))
+                    } else {
+                        Err(m.payload.returns.as_str().unwrap_or("rpc call failed").to_string())
+                    };
+                    if complete_pending_reply(id, reply) {
+                        return;
+                    }
+                }
+                "carf:response" if id >= INTERNAL_REPLY_ID_BASE => {
+                    let reply = if m.payload.result == "ok" {
+                        Ok((m.payload.returns.clone(), msg_data.clone()))
+                    } else {
+                        Err(agent_error_message(&m.payload.returns))
+                    };
+                    if complete_pending_reply(id, reply) {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Protocol markers in `Message::Other` are consumed here; a completed chunk stream
+        // continues below as a single message carrying the reassembled payload.
+        let mut reassembled = None;
+        if let Message::Other(v) = &message {
+            if let Some((id, reply)) = frida_rpc_reply(v) {
+                if complete_pending_reply(id, reply.map(|returns| (returns, msg_data.clone()))) {
+                    return;
+                }
+            }
+            let routing = *OTHER_MESSAGE_ROUTING.lock().unwrap_or_else(|e| e.into_inner());
+            match classify_other_message(v, routing) {
+                // Replies below the internal id range answer the frontend and are forwarded.
+                Some(OtherRoute::RpcReply { id, reply }) if id >= INTERNAL_REPLY_ID_BASE => {
+                    if !complete_pending_reply(id, reply.map(|returns| (returns, msg_data.clone()))) {
+                        debug_log(&format!("on_message: unexpected rpc reply {id}"));
+                    }
+                    return;
+                }
+                Some(OtherRoute::RpcReply { .. }) => {}
+                // An ack only says the request arrived; its reply is still to come.
+                Some(OtherRoute::Ack(id)) => {
+                    debug_log(&format!("on_message: ack for request {id}"));
+                    return;
+                }
+                Some(OtherRoute::Chunk(chunk)) => match push_message_chunk(self.script_id, chunk) {
+                    Ok(Some(data)) => {
+                        reassembled = Some(serde_json::from_str(&data).unwrap_or(serde_json::Value::String(data)));
+                    }
+                    Ok(None) => return,
+                    Err(e) => {
+                        debug_log(&format!("on_message: dropped chunk stream: {e}"));
+                        return;
+                    }
+                },
+                None => {}
+            }
+        }
+
         let message_value = match &message {
             Message::Send(m) => {
                 debug_log("on_message: Message::Send");
@@ -780,6 +5630,18 @@ impl ScriptHandler for TauriScriptHandler {
             }
             Message::Error(m) => {
                 debug_log("on_message: Message::Error");
+                if let Ok(mut registry) = get_handler_registry().lock() {
+                    registry.last_errors.insert(
+                        self.script_id,
+                        ScriptError {
+                            description: m.description.to_string(),
+                            stack: m.stack.to_string(),
+                            file: m.file_name.to_string(),
+                            line: m.line_number as u64,
+                            column: m.column_number as u64,
+                        },
+                    );
+                }
                 json!({
                     "type": "error",
                     "payload": {
@@ -795,29 +5657,83 @@ impl ScriptHandler for TauriScriptHandler {
                 debug_log("on_message: Message::Other");
                 json!({
                     "type": "other",
-                    "payload": v,
+                    "payload": reassembled.as_ref().unwrap_or(v),
                 })
             }
         };
 
         debug_log("on_message: building payload");
 
-        let payload = json!({
-            "session_id": session_id,
-            "script_id": self.script_id,
-            "message": message_value,
-            "data": msg_data,
-        });
+        let data_seq = msg_data
+            .as_deref()
+            .and_then(|data| send_binary_frame(self.script_id, data));
+        let payload = match data_seq {
+            Some(seq) => json!({
+                "session_id": session_id,
+                "script_id": self.script_id,
+                "message": message_value,
+                "data": null,
+                "data_ref": { "seq": seq, "size": msg_data.as_ref().map_or(0, |d| d.len()) },
+            }),
+            None => json!({
+                "session_id": session_id,
+                "script_id": self.script_id,
+                "message": message_value,
+                "data": msg_data,
+            }),
+        };
+
+        // Replies to the frontend's agent RPC calls skip the rate limit; a dropped reply would
+        // leave the caller waiting until it times out. They also skip batching, as do logs,
+        // errors and other non-`send` messages, since only `frida_script_message` reaches
+        // `useAgentRpc`.
+        let is_reply = matches!(
+            &message,
+            Message::Send(m) if matches!(m.payload.r#type.as_str(), "carf:response" | "frida:rpc")
+        );
+        let batchable = matches!(&message, Message::Send(_)) && !is_reply;
 
         debug_log("on_message: about to emit");
-        let _ = app.emit("frida_script_message", payload);
+        let outcome = if batchable {
+            batch_message(self.script_id, payload)
+        } else {
+            BatchOutcome::Unbatched(payload)
+        };
+        match outcome {
+            BatchOutcome::Unbatched(payload) if is_reply => {
+                record_and_emit(&app, &audit, "frida_script_message", payload)
+            }
+            BatchOutcome::Unbatched(payload) => emit_audited(&app, &audit, "frida_script_message", payload),
+            BatchOutcome::Queued => {}
+            BatchOutcome::Full(messages) => emit_audited(
+                &app,
+                &audit,
+                "frida_script_message_batch",
+                json!({ "session_id": session_id, "script_id": self.script_id, "messages": messages }),
+            ),
+        }
         debug_log("on_message: emit done");
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{
+        classify_other_message, decode_name, device_changes, frida_rpc_reply, hook_call_event,
+        is_transient_load_error, memory_map_line, module_event, parse_address, parse_error_location,
+        parse_js_runtime, process_matches, replacement_params, source_hash,
+        unique_process_named, validate_hook_condition, validate_remote_address,
+        validate_spawn_config, validate_struct_layout, variant_map_json, ChunkAssembly, Clock,
+        DeviceInfo, EventRateLimit, FunctionReplacementSpec, HookCondition, JobQueue, JsRuntime,
+        LoadTimeStats, ManualClock, MessageBatch, MessageChunk, OtherMessageRouting, OtherRoute,
+        ProcessInfo, ProcessListCache, QueuePolicy, SpawnConfig, StructField, Variant,
+        DEFAULT_PROCESS_LIST_CACHE_TTL, MAX_HOOK_ARG_INDEX, MAX_MESSAGE_BATCH_LEN, MAX_STRUCT_SPAN,
+    };
     use crate::error::validate_no_nul;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::ffi::CString;
+    use std::time::{Duration, Instant};
 
     #[test]
     fn validate_no_nul_allows_regular_strings() {
@@ -828,4 +5744,375 @@ mod tests {
     fn validate_no_nul_rejects_nul_bytes() {
         assert!(validate_no_nul("device_id", "a\0b").is_err());
     }
+
+    #[test]
+    fn replacement_params_require_exactly_one_implementation() {
+        let spec = |body: Option<&str>, return_value: Option<serde_json::Value>| FunctionReplacementSpec {
+            target: "open".to_string(),
+            return_type: Some("int".to_string()),
+            arg_types: Some(vec!["pointer".to_string(), "int".to_string()]),
+            body: body.map(str::to_string),
+            return_value,
+        };
+
+        let params = replacement_params(spec(Some("return original(...args);"), None)).unwrap();
+        assert_eq!(params["argTypes"], json!(["pointer", "int"]));
+        assert!(params.get("returnValue").is_none());
+        assert_eq!(replacement_params(spec(None, Some(json!(-1)))).unwrap()["returnValue"], json!(-1));
+        assert!(replacement_params(spec(None, None)).is_err());
+        assert!(replacement_params(spec(Some("return 0;"), Some(json!(0)))).is_err());
+        assert!(replacement_params(spec(Some("  "), None)).is_err());
+    }
+
+    #[test]
+    fn process_matches_name_substring_or_pid() {
+        let process = ProcessInfo {
+            pid: 4242,
+            name: "SpringBoard".to_string(),
+            name_sanitized: false,
+        };
+        assert!(process_matches(&process, "spring"));
+        assert!(process_matches(&process, "4242"));
+        assert!(!process_matches(&process, "424"));
+        assert!(!process_matches(&process, "safari"));
+    }
+
+    #[test]
+    fn decode_name_replaces_invalid_utf8() {
+        let name = CString::new(b"bad\xffname".to_vec()).unwrap();
+        assert_eq!(decode_name(&name, "x"), ("bad\u{FFFD}name".to_string(), true));
+    }
+
+    #[test]
+    fn decode_name_keeps_valid_names_and_replaces_empty_ones() {
+        let name = CString::new("Safari").unwrap();
+        assert_eq!(decode_name(&name, "x"), ("Safari".to_string(), false));
+        assert_eq!(decode_name(&CString::default(), "<pid 7>"), ("<pid 7>".to_string(), true));
+    }
+
+    #[test]
+    fn parse_address_accepts_hex_and_decimal() {
+        assert_eq!(parse_address("0x7fff0000"), Ok(0x7fff_0000));
+        assert_eq!(parse_address("4096"), Ok(4096));
+        assert!(parse_address("0xzz").is_err());
+    }
+
+    #[test]
+    fn process_list_cache_expires_with_manual_clock() {
+        let clock = ManualClock::new();
+        let cache = ProcessListCache {
+            device_id: "local".to_string(),
+            fetched_at: clock.now(),
+            processes: Vec::new(),
+        };
+        let ttl = DEFAULT_PROCESS_LIST_CACHE_TTL;
+        assert!(cache.is_fresh("local", clock.now(), ttl));
+        assert!(!cache.is_fresh("usb", clock.now(), ttl));
+        assert!(!cache.is_fresh("local", clock.now(), Duration::ZERO));
+
+        clock.advance(ttl);
+        assert!(!cache.is_fresh("local", clock.now(), ttl));
+    }
+
+    #[test]
+    fn transient_load_errors_are_retryable() {
+        assert!(is_transient_load_error("Timeout was reached"));
+        assert!(is_transient_load_error("Script is not ready"));
+    }
+
+    #[test]
+    fn compile_errors_are_not_retryable() {
+        assert!(!is_transient_load_error("Script(line 3): SyntaxError: unexpected token"));
+        assert!(!is_transient_load_error("Session is detached"));
+        assert!(!is_transient_load_error("Unknown session_id"));
+    }
+
+    #[test]
+    fn parse_error_location_reads_line_and_column() {
+        assert_eq!(
+            parse_error_location("Script(line 3, column 7): SyntaxError: unexpected token"),
+            (Some(3), Some(7))
+        );
+        assert_eq!(parse_error_location("/script.js:12:4: SyntaxError: expecting ';'"), (Some(12), Some(4)));
+        assert_eq!(parse_error_location("could not parse line 5"), (Some(5), None));
+        assert_eq!(parse_error_location("Timeout was reached"), (None, None));
+    }
+
+    #[test]
+    fn job_queue_applies_overload_policy() {
+        let queue = JobQueue::new();
+        queue.set_policy(QueuePolicy::RejectNew, 1);
+        assert!(queue.push(Box::new(|_| {})).is_ok());
+        assert!(queue.push(Box::new(|_| {})).is_err());
+
+        queue.set_policy(QueuePolicy::DropOldest, 1);
+        assert!(queue.push(Box::new(|_| {})).is_ok());
+        assert!(queue.pop_timeout(Duration::ZERO).is_ok());
+        assert!(queue.pop_timeout(Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn closed_queue_drops_pending_jobs() {
+        let queue = JobQueue::new();
+        let (reply_tx, reply_rx) = std::sync::mpsc::channel::<()>();
+        assert!(queue
+            .push(Box::new(move |_| {
+                let _ = reply_tx.send(());
+            }))
+            .is_ok());
+
+        queue.close_and_drain();
+        assert!(queue.is_closed());
+        assert!(reply_rx.recv().is_err());
+        assert!(queue.push(Box::new(|_| {})).is_err());
+        assert!(queue.pop_timeout(Duration::ZERO).is_err());
+    }
+
+    #[test]
+    fn hook_condition_validation() {
+        let condition = |op: &str, value: serde_json::Value| HookCondition {
+            arg_index: 1,
+            op: op.to_string(),
+            value,
+        };
+        assert!(validate_hook_condition(&condition("eq", json!(42))).is_ok());
+        assert!(validate_hook_condition(&condition("gt", json!("0x1000"))).is_ok());
+        assert!(validate_hook_condition(&condition("contains", json!("secret"))).is_ok());
+        assert!(validate_hook_condition(&condition("contains", json!(1))).is_err());
+        assert!(validate_hook_condition(&condition("lt", json!(-1))).is_err());
+        assert!(validate_hook_condition(&condition("matches", json!("x"))).is_err());
+        assert!(validate_hook_condition(&HookCondition {
+            arg_index: MAX_HOOK_ARG_INDEX + 1,
+            ..condition("eq", json!(0))
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn source_hash_is_fnv1a_64() {
+        assert_eq!(source_hash(""), "cbf29ce484222325");
+        assert_eq!(source_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn struct_layout_validation() {
+        let field = |name: &str, kind: &str, offset: u64| StructField {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            offset,
+        };
+        assert_eq!(validate_struct_layout(&[field("a", "u32", 0), field("b", "pointer", 8)]), Ok(16));
+        assert!(validate_struct_layout(&[]).is_err());
+        assert!(validate_struct_layout(&[field("a", "u32", 0), field("a", "u8", 4)]).is_err());
+        assert!(validate_struct_layout(&[field("a", "int", 0)]).is_err());
+        assert!(validate_struct_layout(&[field("a", "u64", MAX_STRUCT_SPAN - 4)]).is_err());
+        assert!(validate_struct_layout(&[field("a", "u8", u64::MAX)]).is_err());
+    }
+
+    #[test]
+    fn module_observer_events_map_to_backend_events() {
+        let (event, module) =
+            module_event(&json!({ "event": "module_added", "name": "libfoo.so", "base": "0x1000" })).unwrap();
+        assert_eq!(event, "frida_module_loaded");
+        assert_eq!(module, json!({ "name": "libfoo.so", "base": "0x1000" }));
+        assert_eq!(module_event(&json!({ "event": "module_removed" })).unwrap().0, "frida_module_unloaded");
+        assert!(module_event(&json!({ "event": "thread_added" })).is_none());
+        assert!(module_event(&json!({})).is_none());
+    }
+
+    #[test]
+    fn event_rate_limit_drops_past_the_cap_and_resets_each_window() {
+        let start = Instant::now();
+        let mut limit = EventRateLimit::new(2, start);
+        assert!(limit.admit(start));
+        assert!(limit.admit(start));
+        assert!(!limit.admit(start));
+        assert!(!limit.admit(start + Duration::from_millis(999)));
+        assert!(limit.admit(start + Duration::from_secs(1)));
+
+        assert_eq!(limit.take_dropped(start + Duration::from_millis(500)), None);
+        assert_eq!(limit.take_dropped(start + Duration::from_secs(1)), Some(2));
+        assert_eq!(limit.take_dropped(start + Duration::from_secs(3)), None);
+    }
+
+    #[test]
+    fn memory_map_lines_are_aligned_columns() {
+        let range = json!({ "base": "0x1000", "size": 4096, "protection": "r-x", "file": { "path": "/lib/libc.so" } });
+        assert_eq!(memory_map_line(&range), "0x1000                   0x1000 r-x /lib/libc.so");
+        let anon = json!({ "base": "0x2000", "size": 16, "protection": "rw-", "file": null });
+        assert_eq!(memory_map_line(&anon), "0x2000                     0x10 rw-");
+    }
+
+    #[test]
+    fn paired_hook_calls_strip_the_event_tag() {
+        let call = hook_call_event(&json!({ "event": "hook_call", "hookId": "h", "retval": "0x0" })).unwrap();
+        assert_eq!(call, json!({ "hookId": "h", "retval": "0x0" }));
+        assert!(hook_call_event(&json!({ "event": "module_added" })).is_none());
+        assert!(hook_call_event(&json!({ "hookId": "h" })).is_none());
+    }
+
+    #[test]
+    fn other_messages_route_by_marker() {
+        let all = OtherMessageRouting::ALL;
+        assert_eq!(
+            classify_other_message(&json!({ "__carf_rpc_reply": 7, "result": "ok", "returns": 1 }), all),
+            Some(OtherRoute::RpcReply { id: 7, reply: Ok(json!(1)) })
+        );
+        assert_eq!(
+            classify_other_message(&json!({ "__carf_rpc_reply": 7, "returns": { "message": "boom" } }), all),
+            Some(OtherRoute::RpcReply { id: 7, reply: Err("boom".to_string()) })
+        );
+        assert_eq!(classify_other_message(&json!({ "__carf_ack": 3 }), all), Some(OtherRoute::Ack(3)));
+        assert!(classify_other_message(&json!({ "__carf_ack": "3" }), all).is_none());
+        assert!(classify_other_message(&json!({ "kind": "custom" }), all).is_none());
+
+        let no_ack = OtherMessageRouting { ack: false, ..all };
+        assert!(classify_other_message(&json!({ "__carf_ack": 3 }), no_ack).is_none());
+    }
+
+    #[test]
+    fn chunk_streams_reassemble_in_order() {
+        let chunk = |index, last, data: &str| MessageChunk {
+            stream: "s".to_string(),
+            index,
+            last,
+            data: data.to_string(),
+        };
+        let mut assembly = ChunkAssembly::default();
+        assert_eq!(assembly.push(chunk(0, false, "{\"a\":")), Ok(None));
+        assert_eq!(assembly.push(chunk(1, true, "1}")), Ok(Some("{\"a\":1}".to_string())));
+
+        let mut assembly = ChunkAssembly::default();
+        assert!(assembly.push(chunk(1, true, "x")).is_err());
+    }
+
+    #[test]
+    fn load_time_stats_track_average_and_max() {
+        let mut stats = LoadTimeStats::default();
+        stats.record(100);
+        stats.record(50);
+        assert_eq!((stats.count, stats.total_ms, stats.max_ms), (2, 150, 100));
+        assert_eq!(stats.average_ms, 75.0);
+    }
+
+    #[test]
+    fn remote_addresses_take_an_optional_port() {
+        assert!(validate_remote_address("192.168.1.20").is_ok());
+        assert!(validate_remote_address("127.0.0.1:27042").is_ok());
+        assert!(validate_remote_address("[::1]:27042").is_ok());
+        assert!(validate_remote_address("::1").is_ok());
+        assert!(validate_remote_address("phone.local:0").is_err());
+        assert!(validate_remote_address("phone.local:abc").is_err());
+        assert!(validate_remote_address(":27042").is_err());
+        assert!(validate_remote_address("").is_err());
+    }
+
+    #[test]
+    fn device_changes_match_by_id() {
+        let device = |id: &str| DeviceInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            device_type: "usb".to_string(),
+            name_sanitized: false,
+            label: None,
+        };
+        let known: HashMap<String, DeviceInfo> =
+            [device("local"), device("usb-1")].into_iter().map(|d| (d.id.clone(), d)).collect();
+        let (added, removed) = device_changes(&known, &[device("local"), device("usb-2")]);
+        assert_eq!(added.iter().map(|d| d.id.as_str()).collect::<Vec<_>>(), ["usb-2"]);
+        assert_eq!(removed.iter().map(|d| d.id.as_str()).collect::<Vec<_>>(), ["usb-1"]);
+    }
+
+    #[test]
+    fn raw_frida_rpc_replies_are_recognised() {
+        let ok = json!({ "type": "send", "payload": ["frida:rpc", 5, "ok", { "n": 1 }] });
+        assert_eq!(frida_rpc_reply(&ok), Some((5, Ok(json!({ "n": 1 })))));
+        let error = json!({ "type": "send", "payload": ["frida:rpc", 6, "error", "boom", "Error", "stack"] });
+        assert_eq!(frida_rpc_reply(&error), Some((6, Err("boom".to_string()))));
+        assert!(frida_rpc_reply(&json!({ "type": "send", "payload": ["carf:event", 1] })).is_none());
+        assert!(frida_rpc_reply(&json!({ "type": "log", "payload": "x" })).is_none());
+    }
+
+    #[test]
+    fn spawn_config_rejects_bad_env_names() {
+        let config = |key: &str| SpawnConfig {
+            env: Some(vec![(key.to_string(), "1".to_string())]),
+            ..SpawnConfig::default()
+        };
+        assert!(validate_spawn_config(&config("DEBUG")).is_ok());
+        assert!(validate_spawn_config(&config("")).is_err());
+        assert!(validate_spawn_config(&config("A=B")).is_err());
+        assert!(validate_spawn_config(&config("A\0")).is_err());
+        let empty_cwd = SpawnConfig {
+            cwd: Some(String::new()),
+            ..SpawnConfig::default()
+        };
+        assert!(validate_spawn_config(&empty_cwd).is_err());
+    }
+
+    #[test]
+    fn js_runtime_parses_known_names_only() {
+        assert_eq!(parse_js_runtime(None), Ok(None));
+        assert_eq!(parse_js_runtime(Some("qjs")), Ok(Some(JsRuntime::Qjs)));
+        assert_eq!(parse_js_runtime(Some("v8")), Ok(Some(JsRuntime::V8)));
+        assert!(parse_js_runtime(Some("V8")).is_err());
+        assert!(parse_js_runtime(Some("")).is_err());
+    }
+
+    #[test]
+    fn system_parameters_convert_to_json() {
+        let os = HashMap::from([
+            ("id".to_string(), Variant::String("android".to_string())),
+            ("version".to_string(), Variant::String("14".to_string())),
+        ]);
+        let parameters = HashMap::from([
+            ("os".to_string(), Variant::Map(os)),
+            ("api-level".to_string(), Variant::Int64(34)),
+            ("rooted".to_string(), Variant::Boolean(true)),
+            ("users".to_string(), Variant::MapList(vec![HashMap::new()])),
+        ]);
+        assert_eq!(
+            variant_map_json(parameters),
+            json!({
+                "os": { "id": "android", "version": "14" },
+                "api-level": 34,
+                "rooted": true,
+                "users": [{}],
+            })
+        );
+    }
+
+    #[test]
+    fn message_batch_flushes_after_window_or_when_full() {
+        let start = Instant::now();
+        let mut batch = MessageBatch::new(Duration::from_millis(200));
+        assert!(batch.take_due(start).is_none());
+
+        assert!(batch.push(json!(1), start).is_none());
+        assert!(batch.push(json!(2), start + Duration::from_millis(150)).is_none());
+        assert!(batch.take_due(start + Duration::from_millis(199)).is_none());
+        assert_eq!(batch.take_due(start + Duration::from_millis(200)), Some(vec![json!(1), json!(2)]));
+        assert!(batch.take_due(start + Duration::from_secs(1)).is_none());
+
+        for i in 0..MAX_MESSAGE_BATCH_LEN - 1 {
+            assert!(batch.push(json!(i), start).is_none());
+        }
+        let full = batch.push(json!("last"), start).unwrap();
+        assert_eq!(full.len(), MAX_MESSAGE_BATCH_LEN);
+        assert!(batch.take().is_none());
+    }
+
+    #[test]
+    fn process_name_match_must_be_exact_and_unique() {
+        let process = |pid: u32, name: &str| ProcessInfo {
+            pid,
+            name: name.to_string(),
+            name_sanitized: false,
+        };
+        let processes = vec![process(10, "Safari"), process(11, "helper"), process(12, "helper")];
+        assert_eq!(unique_process_named(&processes, "Safari"), Ok(10));
+        assert!(unique_process_named(&processes, "safari").is_err());
+        let err = unique_process_named(&processes, "helper").unwrap_err();
+        assert!(err.contains("11, 12"));
+    }
 }
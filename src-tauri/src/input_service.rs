@@ -1,13 +1,20 @@
 use rdev::{listen, Event, EventType, Key};
 use serde::Serialize;
 use serde_json::json;
-use std::sync::{Mutex, Once};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, Once,
+    },
+    time::{Duration, Instant},
+};
 use tauri::Emitter;
 
-// Global keyboard listener based on rdev.
-// We emit normalized key events to the frontend so it can implement hotkeys / command palette.
+// Global keyboard and mouse listener based on rdev.
+// We emit normalized key events to the frontend so it can implement hotkeys / command palette,
+// and mouse events for recording.
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
 pub struct Modifiers {
     pub ctrl: bool,
     pub shift: bool,
@@ -30,6 +37,42 @@ pub struct GlobalKeyEvent {
     pub modifiers: Modifiers,
 }
 
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseAction {
+    Press,
+    Release,
+    Move,
+    Wheel,
+}
+
+// `x`/`y` is the last known pointer position (rdev only reports it on moves), so clicks before
+// the first move have none. `button` is set for press/release, the deltas for wheel.
+#[derive(Debug, Clone, Serialize)]
+pub struct GlobalMouseEvent {
+    pub action: MouseAction,
+    pub button: Option<String>,
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub delta_x: Option<i64>,
+    pub delta_y: Option<i64>,
+    pub modifiers: Modifiers,
+}
+
+// A registered chord: `key` is the `{:?}` name of the rdev::Key, matched with exactly these
+// modifiers held.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hotkey {
+    key: String,
+    modifiers: Modifiers,
+}
+
+// Outcome of one key event: the raw event (unless filtered out) and ids of hotkeys it triggered.
+struct KeyOutcome {
+    event: Option<GlobalKeyEvent>,
+    hotkeys: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ModifierCounters {
     ctrl: u8,
@@ -58,8 +101,39 @@ impl ModifierCounters {
     }
 }
 
+// A `listen` that returns sooner than this never got a working hook (no display, no
+// permission); it is reported but not retried automatically.
+const MIN_HOOK_LIFETIME: Duration = Duration::from_secs(5);
+// Moves are emitted at most ~60 times a second; the position is still tracked for every one.
+const MOUSE_MOVE_INTERVAL: Duration = Duration::from_millis(16);
+
 static START: Once = Once::new();
 static MODS: Mutex<ModifierCounters> = Mutex::new(ModifierCounters::new());
+// rdev keeps its callback in a process-wide static and has no way to remove an installed
+// hook, so at most one `listen` may be in flight. A new one only starts once it has returned.
+static LISTENING: AtomicBool = AtomicBool::new(false);
+// Key names (`{:?}` of rdev::Key, e.g. "F1", "Escape") to emit; empty means every key.
+static KEY_ALLOWLIST: Mutex<Vec<String>> = Mutex::new(Vec::new());
+// While false, key events are dropped but the OS hook stays installed.
+static KEYS_ENABLED: AtomicBool = AtomicBool::new(true);
+static HOTKEYS: Mutex<Vec<(String, Hotkey)>> = Mutex::new(Vec::new());
+static MOUSE_POSITION: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+static LAST_MOUSE_MOVE: Mutex<Option<Instant>> = Mutex::new(None);
+
+// `{:?}` names of the rdev::Key variants a chord may use.
+#[rustfmt::skip]
+const KEY_NAMES: &[&str] = &[
+    "Backspace", "CapsLock", "Delete", "DownArrow", "End", "Escape", "F1", "F2", "F3", "F4",
+    "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12", "Home", "LeftArrow", "PageDown",
+    "PageUp", "Return", "RightArrow", "Space", "Tab", "UpArrow", "PrintScreen", "ScrollLock",
+    "Pause", "NumLock", "BackQuote", "Num1", "Num2", "Num3", "Num4", "Num5", "Num6", "Num7",
+    "Num8", "Num9", "Num0", "Minus", "Equal", "KeyQ", "KeyW", "KeyE", "KeyR", "KeyT", "KeyY",
+    "KeyU", "KeyI", "KeyO", "KeyP", "LeftBracket", "RightBracket", "KeyA", "KeyS", "KeyD",
+    "KeyF", "KeyG", "KeyH", "KeyJ", "KeyK", "KeyL", "SemiColon", "Quote", "BackSlash",
+    "IntlBackslash", "KeyZ", "KeyX", "KeyC", "KeyV", "KeyB", "KeyN", "KeyM", "Comma", "Dot",
+    "Slash", "Insert", "KpReturn", "KpMinus", "KpPlus", "KpMultiply", "KpDivide", "Kp0", "Kp1",
+    "Kp2", "Kp3", "Kp4", "Kp5", "Kp6", "Kp7", "Kp8", "Kp9", "KpDelete", "Function",
+];
 
 fn bump(counter: &mut u8, is_down: bool) {
     if is_down {
@@ -79,7 +153,7 @@ fn apply_modifier(counters: &mut ModifierCounters, key: Key, is_down: bool) {
     }
 }
 
-fn translate_key_event(event: Event) -> Option<GlobalKeyEvent> {
+fn translate_key_event(event: Event) -> Option<KeyOutcome> {
     let (action, key) = match event.event_type {
         EventType::KeyPress(key) => (KeyAction::Press, key),
         EventType::KeyRelease(key) => (KeyAction::Release, key),
@@ -88,33 +162,251 @@ fn translate_key_event(event: Event) -> Option<GlobalKeyEvent> {
 
     let is_down = matches!(action, KeyAction::Press);
 
+    // Modifiers are tracked even when filtered out so allowlisted keys report them correctly.
     let mut counters = MODS.lock().unwrap_or_else(|e| e.into_inner());
     apply_modifier(&mut counters, key, is_down);
+    if !KEYS_ENABLED.load(Ordering::SeqCst) {
+        return None;
+    }
+
+    let key = format!("{:?}", key);
+    let modifiers = counters.snapshot();
+
+    // Hotkeys fire regardless of the allowlist.
+    let hotkeys = if is_down {
+        HOTKEYS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, hotkey)| hotkey.key == key && hotkey.modifiers == modifiers)
+            .map(|(id, _)| id.clone())
+            .collect()
+    } else {
+        Vec::new()
+    };
 
-    Some(GlobalKeyEvent {
+    let allowlist = KEY_ALLOWLIST.lock().unwrap_or_else(|e| e.into_inner());
+    let event = (allowlist.is_empty() || allowlist.contains(&key)).then(|| GlobalKeyEvent {
         action,
-        key: format!("{:?}", key),
+        key,
         name: event.name,
-        modifiers: counters.snapshot(),
+        modifiers,
+    });
+
+    Some(KeyOutcome { event, hotkeys })
+}
+
+// Parses e.g. "Ctrl+Shift+P" or "Alt+F4": any modifiers plus exactly one key. Keys are
+// case-insensitive rdev names ("Escape", "KeyP", "Num1"), with single letters and digits
+// accepted as shorthand.
+fn parse_chord(chord: &str) -> Result<Hotkey, String> {
+    let mut modifiers = Modifiers::default();
+    let mut key = None;
+    for token in chord.split('+').map(str::trim) {
+        match token.to_ascii_lowercase().as_str() {
+            "" => return Err(format!("invalid chord {chord:?}")),
+            "ctrl" | "control" => modifiers.ctrl = true,
+            "shift" => modifiers.shift = true,
+            "alt" | "option" => modifiers.alt = true,
+            "meta" | "cmd" | "super" | "win" => modifiers.meta = true,
+            _ if key.is_some() => return Err(format!("chord {chord:?} has more than one key")),
+            _ => key = Some(parse_key_name(token).ok_or_else(|| format!("unknown key {token:?}"))?),
+        }
+    }
+    let key = key.ok_or_else(|| format!("chord {chord:?} has no key"))?;
+    Ok(Hotkey { key, modifiers })
+}
+
+fn parse_key_name(token: &str) -> Option<String> {
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphabetic() {
+            return Some(format!("Key{}", c.to_ascii_uppercase()));
+        }
+        if c.is_ascii_digit() {
+            return Some(format!("Num{c}"));
+        }
+    }
+    KEY_NAMES
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(token))
+        .map(|name| name.to_string())
+}
+
+// Re-registering an id replaces its chord.
+pub fn register_hotkey(id: String, chord: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("hotkey id cannot be empty".to_string());
+    }
+    let hotkey = parse_chord(chord)?;
+    let mut hotkeys = HOTKEYS.lock().unwrap_or_else(|e| e.into_inner());
+    hotkeys.retain(|(existing, _)| *existing != id);
+    hotkeys.push((id, hotkey));
+    Ok(())
+}
+
+// Returns whether the id was registered.
+pub fn unregister_hotkey(id: &str) -> bool {
+    let mut hotkeys = HOTKEYS.lock().unwrap_or_else(|e| e.into_inner());
+    let before = hotkeys.len();
+    hotkeys.retain(|(existing, _)| existing != id);
+    hotkeys.len() != before
+}
+
+fn translate_mouse_event(event: &Event) -> Option<GlobalMouseEvent> {
+    let mut button = None;
+    let mut delta = None;
+    let action = match event.event_type {
+        EventType::ButtonPress(b) => {
+            button = Some(format!("{:?}", b));
+            MouseAction::Press
+        }
+        EventType::ButtonRelease(b) => {
+            button = Some(format!("{:?}", b));
+            MouseAction::Release
+        }
+        EventType::Wheel { delta_x, delta_y } => {
+            delta = Some((delta_x, delta_y));
+            MouseAction::Wheel
+        }
+        EventType::MouseMove { x, y } => {
+            *MOUSE_POSITION.lock().unwrap_or_else(|e| e.into_inner()) = Some((x, y));
+            let mut last = LAST_MOUSE_MOVE.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            if last.is_some_and(|at| now.duration_since(at) < MOUSE_MOVE_INTERVAL) {
+                return None;
+            }
+            *last = Some(now);
+            MouseAction::Move
+        }
+        _ => return None,
+    };
+
+    let position = *MOUSE_POSITION.lock().unwrap_or_else(|e| e.into_inner());
+    Some(GlobalMouseEvent {
+        action,
+        button,
+        x: position.map(|(x, _)| x),
+        y: position.map(|(_, y)| y),
+        delta_x: delta.map(|(x, _)| x),
+        delta_y: delta.map(|(_, y)| y),
+        modifiers: MODS.lock().unwrap_or_else(|e| e.into_inner()).snapshot(),
     })
 }
 
-pub fn start_global_key_listener(app: tauri::AppHandle) {
-    START.call_once(move || {
-        std::thread::spawn(move || {
-            let app_for_callback = app.clone();
+pub fn set_keys_enabled(enabled: bool) {
+    KEYS_ENABLED.store(enabled, Ordering::SeqCst);
+}
 
-            let callback = move |event: Event| {
-                let Some(payload) = translate_key_event(event) else {
-                    return;
-                };
+pub fn keys_enabled() -> bool {
+    KEYS_ENABLED.load(Ordering::SeqCst)
+}
 
-                let _ = app_for_callback.emit("rdev_key_event", payload);
-            };
+pub fn set_key_allowlist(keys: Vec<String>) {
+    *KEY_ALLOWLIST.lock().unwrap_or_else(|e| e.into_inner()) = keys;
+}
+
+fn event_callback(app: tauri::AppHandle) -> impl FnMut(Event) + 'static {
+    move |event: Event| {
+        if let Some(payload) = translate_mouse_event(&event) {
+            let _ = app.emit("rdev_mouse_event", payload);
+            return;
+        }
+
+        let Some(outcome) = translate_key_event(event) else {
+            return;
+        };
+
+        for id in outcome.hotkeys {
+            let _ = app.emit("hotkey_triggered", json!({ "id": id }));
+        }
+        if let Some(payload) = outcome.event {
+            let _ = app.emit("rdev_key_event", payload);
+        }
+    }
+}
 
-            if let Err(e) = listen(callback) {
+fn reset_modifiers() {
+    *MODS.lock().unwrap_or_else(|e| e.into_inner()) = ModifierCounters::new();
+}
+
+// Starts the listener thread unless one is still inside `listen`. A hook that had been working
+// and then returns is reinstalled from the same thread, after the old `listen` is gone.
+fn spawn_listener(app: tauri::AppHandle) -> bool {
+    if LISTENING.swap(true, Ordering::SeqCst) {
+        return false;
+    }
+
+    std::thread::spawn(move || {
+        loop {
+            let started = Instant::now();
+            if let Err(e) = listen(event_callback(app.clone())) {
                 let _ = app.emit("rdev_listen_error", json!({ "error": format!("{:?}", e) }));
             }
-        });
+            if started.elapsed() < MIN_HOOK_LIFETIME {
+                break;
+            }
+            reset_modifiers();
+            let _ = app.emit(
+                "rdev_listener_restarted",
+                json!({ "reason": "exited", "restarted": true }),
+            );
+        }
+        LISTENING.store(false, Ordering::SeqCst);
+    });
+
+    true
+}
+
+// A dead hook can't be detected or removed while `listen` still blocks, so this only installs
+// a new one if the previous `listen` has returned. Either way held-modifier state is reset,
+// since releases may have been missed. Returns whether a listener was started.
+pub fn restart_key_listener(app: tauri::AppHandle, reason: &str) -> bool {
+    reset_modifiers();
+    let restarted = spawn_listener(app.clone());
+    let _ = app.emit(
+        "rdev_listener_restarted",
+        json!({ "reason": reason, "restarted": restarted }),
+    );
+    restarted
+}
+
+pub fn start_global_key_listener(app: tauri::AppHandle) {
+    START.call_once(move || {
+        spawn_listener(app);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_chord, Modifiers};
+
+    #[test]
+    fn chord_parses_modifiers_and_key() {
+        let hotkey = parse_chord("Ctrl+Shift+P").unwrap();
+        assert_eq!(hotkey.key, "KeyP");
+        assert_eq!(
+            hotkey.modifiers,
+            Modifiers {
+                ctrl: true,
+                shift: true,
+                alt: false,
+                meta: false
+            }
+        );
+        assert_eq!(parse_chord("alt + f4").unwrap().key, "F4");
+        assert_eq!(parse_chord("Cmd+1").unwrap().key, "Num1");
+        assert_eq!(
+            parse_chord("Escape").unwrap().modifiers,
+            Modifiers::default()
+        );
+    }
+
+    #[test]
+    fn chord_rejects_unknown_or_missing_keys() {
+        assert!(parse_chord("Ctrl+Bogus").is_err());
+        assert!(parse_chord("Ctrl+Shift").is_err());
+        assert!(parse_chord("Ctrl+A+B").is_err());
+        assert!(parse_chord("Ctrl++").is_err());
+    }
+}
@@ -16,6 +16,12 @@ pub fn run() {
         })
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(commands::handler())
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Unload scripts and detach sessions so no instrumentation stays behind in targets.
+            if let tauri::RunEvent::Exit = event {
+                app.state::<FridaWorker>().shutdown();
+            }
+        });
 }
@@ -1,6 +1,75 @@
-import { invoke } from "@tauri-apps/api/core";
+import { Channel, invoke } from "@tauri-apps/api/core";
 
-import type { DeviceInfo, ProcessInfo, ScriptInfo, SessionInfo } from "./types";
+import type {
+  AddressDescription,
+  AgentCapabilities,
+  AgentGcResult,
+  AgentLogLevel,
+  AgentOperation,
+  AllocationSnapshot,
+  AllocationTotals,
+  AllocationTrackOptions,
+  AttachProbe,
+  AuditedEvent,
+  DebuggerInfo,
+  DeviceCapabilities,
+  DeviceInfo,
+  DeviceLabel,
+  DumpInfo,
+  EvalResult,
+  FdInfo,
+  FunctionReplacement,
+  FunctionReplacementOptions,
+  HookCondition,
+  HostInfo,
+  IdentifierAttachInfo,
+  InterceptorInfo,
+  JsRuntime,
+  KillResult,
+  LayoutImportResult,
+  MainModuleInfo,
+  MemoryMapExport,
+  MemoryMapFormat,
+  MemorySummary,
+  MessageStats,
+  MethodSearchResult,
+  ModuleDependency,
+  ModuleHookOptions,
+  ModuleHookResult,
+  ModuleOffset,
+  ModuleTimeline,
+  OtherMessageRouting,
+  PidWatch,
+  PingStats,
+  PinnedDevice,
+  PointerSearchResult,
+  ProcessCount,
+  ProcessInfo,
+  QueuePolicy,
+  RangeInfo,
+  ResetSummary,
+  ScriptBinaryFrame,
+  ScriptError,
+  ScriptInfo,
+  ScriptLoadResult,
+  ScriptLoadStats,
+  ScriptSource,
+  ScriptStats,
+  ScriptValidation,
+  SearchRange,
+  SessionInfo,
+  SessionLayout,
+  SpawnAttachInfo,
+  SpawnOptions,
+  StructField,
+  StructReadResult,
+  SymbolPage,
+  SymbolicatedAddress,
+  ThreadContext,
+  VtableHookResult,
+  WatchInfo,
+  WorkspaceInfo,
+} from "./types";
 
 // Thin typed wrappers around Tauri commands.
 export const fridaBackendApi = {
@@ -8,54 +77,152 @@ export const fridaBackendApi = {
     return await invoke<string>("frida_version");
   },
 
-  listDevices: async () => {
-    return await invoke<DeviceInfo[]>("frida_list_devices");
+  hostInfo: async () => {
+    return await invoke<HostInfo>("carf_host_info");
   },
 
-  listProcesses: async (deviceId: string) => {
+  // Device-level calls take an optional `workspaceId` (default workspace when omitted).
+  // Session- and script-scoped calls route to their workspace on their own.
+  listDevices: async (workspaceId?: number) => {
+    return await invoke<DeviceInfo[]>("frida_list_devices", {
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // `address` is `host` or `host:port` (default port 27042).
+  addRemoteDevice: async (address: string, workspaceId?: number) => {
+    return await invoke<DeviceInfo>("frida_add_remote_device", {
+      address,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  removeRemoteDevice: async (address: string, workspaceId?: number) => {
+    return await invoke<void>("frida_remove_remote_device", {
+      address,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // `filter` keeps processes whose name contains it (case-insensitive) or whose pid it is.
+  listProcesses: async (deviceId: string, workspaceId?: number, filter?: string) => {
     return await invoke<ProcessInfo[]>("frida_list_processes", {
       device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+      filter: filter ?? null,
     });
   },
 
-  attach: async (deviceId: string, pid: number) => {
+  // `freezeOnAttach` suspends all target threads after the agent loads; resume them with
+  // `resumeTarget`. Opt-in because a thread frozen inside malloc can deadlock the agent.
+  // `silent` skips the `frida_session_attached` event; the return value is the only signal.
+  attach: async (
+    deviceId: string,
+    pid: number,
+    freezeOnAttach?: boolean,
+    workspaceId?: number,
+    silent?: boolean,
+  ) => {
     return await invoke<SessionInfo>("frida_attach", {
       device_id: deviceId,
       pid,
+      freeze_on_attach: freezeOnAttach ?? null,
+      silent: silent ?? null,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Errors unless exactly one process has this (case-sensitive) name.
+  attachByName: async (deviceId: string, processName: string, workspaceId?: number) => {
+    return await invoke<SessionInfo>("frida_attach_by_name", {
+      device_id: deviceId,
+      process_name: processName,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  attachAndProbe: async (deviceId: string, pid: number, workspaceId?: number) => {
+    return await invoke<AttachProbe>("frida_attach_and_probe", {
+      device_id: deviceId,
+      pid,
+      workspace_id: workspaceId ?? null,
     });
   },
 
-  detach: async (sessionId: number) => {
+  // `silent` skips the `frida_session_detached` event.
+  detach: async (sessionId: number, silent?: boolean) => {
     return await invoke<void>("frida_detach", {
       session_id: sessionId,
+      silent: silent ?? null,
     });
   },
 
-  spawn: async (deviceId: string, program: string, argv?: string[] | null) => {
+  spawn: async (
+    deviceId: string,
+    program: string,
+    argv?: string[] | null,
+    workspaceId?: number,
+    options?: SpawnOptions,
+  ) => {
     return await invoke<number>("frida_spawn", {
       device_id: deviceId,
       program,
       argv: argv ?? null,
+      env: options?.env ?? null,
+      cwd: options?.cwd ?? null,
+      stdio: options?.stdio ?? null,
+      workspace_id: workspaceId ?? null,
     });
   },
 
-  resume: async (deviceId: string, pid: number) => {
+  // Spawn + attach + default agent; the target stays suspended until `resume`.
+  spawnAttach: async (
+    deviceId: string,
+    program: string,
+    argv?: string[] | null,
+    workspaceId?: number,
+    options?: SpawnOptions,
+  ) => {
+    return await invoke<SpawnAttachInfo>("frida_spawn_attach", {
+      device_id: deviceId,
+      program,
+      argv: argv ?? null,
+      env: options?.env ?? null,
+      cwd: options?.cwd ?? null,
+      stdio: options?.stdio ?? null,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  resume: async (deviceId: string, pid: number, workspaceId?: number) => {
     return await invoke<void>("frida_resume", {
       device_id: deviceId,
       pid,
+      workspace_id: workspaceId ?? null,
     });
   },
 
-  kill: async (deviceId: string, pid: number) => {
+  kill: async (deviceId: string, pid: number, workspaceId?: number) => {
     return await invoke<void>("frida_kill", {
       device_id: deviceId,
       pid,
+      workspace_id: workspaceId ?? null,
     });
   },
 
-  loadDefaultScript: async (sessionId: number) => {
+  // `config` is exposed to the agent as `globalThis.__CARF_CONFIG__`.
+  // Returns the already-loaded agent unless `force` is set.
+  loadDefaultScript: async (
+    sessionId: number,
+    config?: Record<string, unknown>,
+    runtime?: JsRuntime,
+    force?: boolean,
+  ) => {
     return await invoke<ScriptInfo>("frida_load_default_script", {
       session_id: sessionId,
+      config: config ?? null,
+      runtime: runtime ?? null,
+      force: force ?? null,
     });
   },
 
@@ -72,4 +239,730 @@ export const fridaBackendApi = {
       data: data ? Array.from(data) : undefined,
     });
   },
+
+  // Requires the V8 runtime.
+  enableDebugger: async (scriptId: number, port?: number) => {
+    return await invoke<DebuggerInfo>("frida_enable_debugger", {
+      script_id: scriptId,
+      port: port ?? null,
+    });
+  },
+
+  disableDebugger: async (scriptId: number) => {
+    return await invoke<void>("frida_disable_debugger", {
+      script_id: scriptId,
+    });
+  },
+
+  deviceCapabilities: async (deviceId: string, workspaceId?: number) => {
+    return await invoke<DeviceCapabilities>("frida_device_capabilities", {
+      device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  agentCapabilities: async (scriptId: number) => {
+    return await invoke<AgentCapabilities>("frida_agent_capabilities", {
+      script_id: scriptId,
+    });
+  },
+
+  // Load the same source into several sessions; partial failures are reported per session.
+  loadScript: async (sessionId: number, source: string, name?: string, runtime?: JsRuntime) => {
+    return await invoke<ScriptInfo>("frida_load_script", {
+      session_id: sessionId,
+      source,
+      name: name ?? null,
+      runtime: runtime ?? null,
+    });
+  },
+
+  // `config` is injected as `globalThis.__CARF_CONFIG__`, as for the default agent.
+  loadScriptMany: async (
+    sessionIds: number[],
+    source: string,
+    name?: string,
+    config?: Record<string, unknown>,
+  ) => {
+    return await invoke<ScriptLoadResult[]>("frida_load_script_many", {
+      session_ids: sessionIds,
+      source,
+      name: name ?? null,
+      config: config ?? null,
+    });
+  },
+
+  // Kill a suspended spawn we no longer want; succeeds if the pid is already gone.
+  // Held spawns arrive as `frida_spawn_added`; attach, then `resume`.
+  enableSpawnGating: async (deviceId: string, workspaceId?: number) => {
+    return await invoke<void>("frida_enable_spawn_gating", {
+      device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  disableSpawnGating: async (deviceId: string, workspaceId?: number) => {
+    return await invoke<void>("frida_disable_spawn_gating", {
+      device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  discardSpawn: async (deviceId: string, pid: number, workspaceId?: number) => {
+    return await invoke<void>("frida_discard_spawn", {
+      device_id: deviceId,
+      pid,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Pinned devices can be passed anywhere a device id is accepted as `pin:<alias>`.
+  pinDevice: async (alias: string, deviceId: string, workspaceId?: number) => {
+    return await invoke<PinnedDevice>("frida_pin_device", {
+      alias,
+      device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  unpinDevice: async (alias: string, workspaceId?: number) => {
+    return await invoke<void>("frida_unpin_device", {
+      alias,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  listPinnedDevices: async (workspaceId?: number) => {
+    return await invoke<PinnedDevice[]>("frida_list_pinned_devices", {
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Lines stream back as `file_tail` agent events.
+  tailFile: async (scriptId: number, path: string) => {
+    return await invoke<{ tailId: string; path: string }>("frida_tail_file", {
+      script_id: scriptId,
+      path,
+    });
+  },
+
+  stopTail: async (scriptId: number, tailId: string) => {
+    return await invoke<void>("frida_stop_tail", {
+      script_id: scriptId,
+      tail_id: tailId,
+    });
+  },
+
+  getAgentLogLevel: async (scriptId: number) => {
+    return await invoke<AgentLogLevel>("frida_get_agent_log_level", {
+      script_id: scriptId,
+    });
+  },
+
+  setAgentLogLevel: async (scriptId: number, level: AgentLogLevel) => {
+    return await invoke<void>("frida_set_agent_log_level", {
+      script_id: scriptId,
+      level,
+    });
+  },
+
+  // Backend-side record of recently emitted events (oldest first).
+  recentEvents: async (limit?: number, workspaceId?: number) => {
+    return await invoke<AuditedEvent[]>("frida_recent_events", {
+      limit: limit ?? null,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  killMany: async (deviceId: string, pids: number[], workspaceId?: number) => {
+    return await invoke<KillResult[]>("frida_kill_many", {
+      device_id: deviceId,
+      pids,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  pingAgent: async (scriptId: number, samples?: number) => {
+    return await invoke<PingStats>("frida_ping_agent", {
+      script_id: scriptId,
+      samples: samples ?? null,
+    });
+  },
+
+  // Watch the agent bundle on disk and hot-swap default agents on change (dev loop).
+  // Returns the watched path, or null when disabled.
+  setAgentAutoreload: async (enabled: boolean, path?: string, workspaceId?: number) => {
+    return await invoke<string | null>("frida_set_agent_autoreload", {
+      enabled,
+      path: path ?? null,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  enumerateRanges: async (scriptId: number, protection?: string) => {
+    return await invoke<RangeInfo[]>("frida_enumerate_ranges", {
+      script_id: scriptId,
+      protection: protection ?? null,
+    });
+  },
+
+  // Dump target memory straight to a file on the host.
+  // Fails if `path` exists unless `overwrite`; a failed dump leaves no partial file.
+  dumpRegion: async (scriptId: number, address: string, size: number, path: string, overwrite?: boolean) => {
+    return await invoke<DumpInfo>("frida_dump_region", {
+      script_id: scriptId,
+      address,
+      size,
+      path,
+      overwrite: overwrite ?? null,
+    });
+  },
+
+  // Writes every mapped range to `outPath` on the host; fails if the file exists unless `overwrite`.
+  exportMemoryMap: async (scriptId: number, outPath: string, format: MemoryMapFormat, overwrite?: boolean) => {
+    return await invoke<MemoryMapExport>("frida_export_memory_map", {
+      script_id: scriptId,
+      out_path: outPath,
+      format,
+      overwrite: overwrite ?? null,
+    });
+  },
+
+  // Replace `target` (address or export name) with a JS `body` or a constant `returnValue`.
+  // A wrong signature or a throwing body can crash the target.
+  replaceFunction: async (scriptId: number, target: string, options: FunctionReplacementOptions) => {
+    return await invoke<FunctionReplacement>("frida_replace_function", {
+      script_id: scriptId,
+      target,
+      return_type: options.returnType ?? null,
+      arg_types: options.argTypes ?? null,
+      body: options.body ?? null,
+      return_value: options.returnValue ?? null,
+    });
+  },
+
+  restoreFunction: async (scriptId: number, id: string) => {
+    return await invoke<void>("frida_restore_function", {
+      script_id: scriptId,
+      id,
+    });
+  },
+
+  // Detach automatically after `timeoutMs` without posts, RPC or agent messages; 0 disables.
+  setSessionIdleTimeout: async (sessionId: number, timeoutMs: number) => {
+    return await invoke<void>("frida_set_session_idle_timeout", {
+      session_id: sessionId,
+      timeout_ms: timeoutMs,
+    });
+  },
+
+  // Reload this session's scripts, in order, when it drops and the process is attached again.
+  setReloadOnReattach: async (sessionId: number, enabled: boolean) => {
+    return await invoke<void>("frida_set_reload_on_reattach", {
+      session_id: sessionId,
+      enabled,
+    });
+  },
+
+  // Case-insensitive process name filter applied in the backend.
+  // Snapshot attached sessions/scripts so they can be restored with importLayout.
+  exportLayout: async (workspaceId?: number) => {
+    return await invoke<SessionLayout[]>("frida_export_layout", {
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  importLayout: async (layout: SessionLayout[], workspaceId?: number) => {
+    return await invoke<LayoutImportResult[]>("frida_import_layout", {
+      layout,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Cap a backend event at `maxPerSec` across all sessions; extra emissions are dropped and
+  // reported via `frida_events_dropped`. `null` or 0 removes the cap.
+  setEventRateLimit: async (eventName: string, maxPerSec: number | null) => {
+    return await invoke<void>("frida_set_event_rate_limit", {
+      event_name: eventName,
+      max_per_sec: maxPerSec,
+    });
+  },
+
+  // Open file descriptors of the target (Linux/Android and Darwin only).
+  listFds: async (scriptId: number) => {
+    return await invoke<FdInfo[]>("frida_list_fds", { script_id: scriptId });
+  },
+
+  // Bounded retry for transient default-agent load failures during attach.
+  setAgentLoadRetry: async (retries: number, delayMs: number, workspaceId?: number) => {
+    return await invoke<void>("frida_set_agent_load_retry", {
+      retries,
+      delay_ms: delayMs,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Persisted UI label/color; pass empty fields to clear.
+  setDeviceLabel: async (deviceId: string, label: DeviceLabel, workspaceId?: number) => {
+    return await invoke<void>("frida_set_device_label", {
+      device_id: deviceId,
+      label,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Compile-only check; nothing is loaded into a target.
+  validateScript: async (source: string, workspaceId?: number) => {
+    return await invoke<ScriptValidation>("frida_validate_script", {
+      source,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Backpressure for one workspace's worker queue (the default one when omitted); bounded
+  // policies default to 64 entries.
+  setQueuePolicy: async (policy: QueuePolicy, capacity?: number, workspaceId?: number) => {
+    return await invoke<void>("frida_set_queue_policy", {
+      policy,
+      capacity: capacity ?? null,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Aligned pointer-sized references to `address` across readable memory.
+  // Scans every readable range unless `ranges` narrows it down; waits up to 120s.
+  findPointers: async (
+    scriptId: number,
+    address: string,
+    limit?: number,
+    ranges?: SearchRange[],
+  ) => {
+    return await invoke<PointerSearchResult>("frida_find_pointers", {
+      script_id: scriptId,
+      address,
+      limit: limit ?? null,
+      ranges: ranges ?? null,
+    });
+  },
+
+  // Deliver this script's message `data` as raw frames instead of JSON number arrays.
+  // The matching `frida_script_message` carries `data_ref.seq` instead of `data`.
+  setScriptBinaryChannel: async (
+    scriptId: number,
+    onFrame: ((frame: ScriptBinaryFrame) => void) | null,
+  ) => {
+    let channel: Channel<ArrayBuffer> | null = null;
+    if (onFrame) {
+      channel = new Channel<ArrayBuffer>();
+      channel.onmessage = (buffer) => {
+        // Header: script_id (u64 LE), seq (u64 LE).
+        const view = new DataView(buffer);
+        onFrame({
+          script_id: Number(view.getBigUint64(0, true)),
+          seq: Number(view.getBigUint64(8, true)),
+          data: new Uint8Array(buffer, 16),
+        });
+      };
+    }
+    return await invoke<void>("frida_set_script_binary_channel", {
+      script_id: scriptId,
+      channel,
+    });
+  },
+
+  listHooks: async (scriptId: number) => {
+    return await invoke<InterceptorInfo[]>("frida_list_hooks", { script_id: scriptId });
+  },
+
+  // Muted hooks stay installed but stop emitting interceptor events.
+  setHookEnabled: async (scriptId: number, hookId: string, enabled: boolean) => {
+    return await invoke<void>("frida_set_hook_enabled", {
+      script_id: scriptId,
+      hook_id: hookId,
+      enabled,
+    });
+  },
+
+  mainModule: async (scriptId: number) => {
+    return await invoke<MainModuleInfo>("frida_main_module", { script_id: scriptId });
+  },
+
+  agentGc: async (scriptId: number) => {
+    return await invoke<AgentGcResult>("frida_agent_gc", { script_id: scriptId });
+  },
+
+  // Batch address -> symbol lookup; unresolved addresses come back with null fields.
+  symbolicate: async (scriptId: number, addresses: string[]) => {
+    return await invoke<SymbolicatedAddress[]>("frida_symbolicate", {
+      script_id: scriptId,
+      addresses,
+    });
+  },
+
+  // Attach to an app by bundle/package identifier, optionally spawning it if not running. The
+  // running instance is matched by process name, so on iOS only the spawn path finds it.
+  attachByIdentifier: async (
+    deviceId: string,
+    identifier: string,
+    spawn?: boolean,
+    workspaceId?: number,
+  ) => {
+    return await invoke<IdentifierAttachInfo>("frida_attach_by_identifier", {
+      device_id: deviceId,
+      identifier,
+      spawn: spawn ?? null,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Last uncaught agent error, kept until the script is unloaded or reloaded.
+  lastScriptError: async (scriptId: number) => {
+    return await invoke<ScriptError | null>("frida_last_script_error", { script_id: scriptId });
+  },
+
+  setScriptStatsEnabled: async (scriptId: number, enabled: boolean) => {
+    return await invoke<void>("frida_set_script_stats_enabled", {
+      script_id: scriptId,
+      enabled,
+    });
+  },
+
+  // null when stats collection isn't enabled for the script.
+  scriptMessageStats: async (scriptId: number) => {
+    return await invoke<MessageStats | null>("frida_script_message_stats", { script_id: scriptId });
+  },
+
+  resetScriptStats: async (scriptId: number) => {
+    return await invoke<void>("frida_reset_script_stats", { script_id: scriptId });
+  },
+
+  // Freeze all target threads except Frida's. Suspending a thread that holds a lock the
+  // agent needs (e.g. the allocator) can deadlock the process. Darwin and Windows only.
+  suspendTarget: async (scriptId: number) => {
+    return await invoke<{ suspended: number[]; failed: number[] }>("frida_suspend_target", {
+      script_id: scriptId,
+    });
+  },
+
+  resumeTarget: async (scriptId: number) => {
+    return await invoke<{ resumed: number[]; failed: number[] }>("frida_resume_target", {
+      script_id: scriptId,
+    });
+  },
+
+  memorySummary: async (scriptId: number) => {
+    return await invoke<MemorySummary>("frida_memory_summary", { script_id: scriptId });
+  },
+
+  // Glob patterns are `class!method` (case-insensitive); set `regex` to match `class!method`
+  // with a regular expression instead.
+  searchMethods: async (
+    scriptId: number,
+    runtime: "java" | "objc",
+    pattern: string,
+    options?: { regex?: boolean; limit?: number },
+  ) => {
+    return await invoke<MethodSearchResult>("frida_search_methods", {
+      script_id: scriptId,
+      runtime,
+      pattern,
+      regex: options?.regex ?? null,
+      limit: options?.limit ?? null,
+    });
+  },
+
+  // Logging hook on `target` (address or export name); `condition` filters calls in the agent.
+  // `paired` emits one `frida_hook_call` per call instead of enter/leave events; `capture` lists
+  // argument indices to read as strings at entry.
+  hookFunction: async (
+    scriptId: number,
+    target: string,
+    condition?: HookCondition,
+    options?: { paired?: boolean; capture?: number[] },
+  ) => {
+    return await invoke<{ id: string; target: string; paired: boolean }>("frida_hook_function", {
+      script_id: scriptId,
+      target,
+      condition: condition ?? null,
+      paired: options?.paired ?? null,
+      capture: options?.capture ?? null,
+    });
+  },
+  // Hook the virtual function in slot `index` of the object at `address` (or of the vtable at
+  // `address` when `vtable` is set). Events carry `argCount` arguments.
+  hookVtable: async (scriptId: number, address: string, index: number, argCount: number, vtable?: boolean) => {
+    return await invoke<VtableHookResult>("frida_hook_vtable", {
+      script_id: scriptId,
+      address,
+      index,
+      arg_count: argCount,
+      vtable: vtable ?? null,
+    });
+  },
+
+
+  getScriptSource: async (scriptId: number, hashOnly?: boolean) => {
+    return await invoke<ScriptSource>("frida_get_script_source", {
+      script_id: scriptId,
+      hash_only: hashOnly ?? null,
+    });
+  },
+
+  addressToOffset: async (scriptId: number, address: string) => {
+    return await invoke<ModuleOffset>("frida_address_to_offset", { script_id: scriptId, address });
+  },
+
+  describeAddress: async (scriptId: number, address: string) => {
+    return await invoke<AddressDescription>("frida_describe_address", { script_id: scriptId, address });
+  },
+
+  offsetToAddress: async (scriptId: number, module: string, offset: string) => {
+    return await invoke<ModuleOffset>("frida_offset_to_address", { script_id: scriptId, module, offset });
+  },
+
+  // Isolated Frida context with its own DeviceManager and worker thread.
+  createWorkspace: async (label?: string) => {
+    return await invoke<WorkspaceInfo>("frida_create_workspace", { label: label ?? null });
+  },
+
+  listWorkspaces: async () => {
+    return await invoke<WorkspaceInfo[]>("frida_list_workspaces");
+  },
+
+  // Detaches the workspace's sessions first.
+  removeWorkspace: async (workspaceId: number) => {
+    return await invoke<void>("frida_remove_workspace", { workspace_id: workspaceId });
+  },
+
+  readStruct: async (scriptId: number, address: string, layout: StructField[]) => {
+    return await invoke<StructReadResult>("frida_read_struct", {
+      script_id: scriptId,
+      address,
+      layout,
+    });
+  },
+
+  // Emits `frida_module_loaded` / `frida_module_unloaded` as the target loads libraries.
+  // Also records a load/unload timeline in the agent (`timelineCap` entries, default 1000).
+  watchModules: async (scriptId: number, timelineCap?: number) => {
+    return await invoke<void>("frida_watch_modules", {
+      script_id: scriptId,
+      timeline_cap: timelineCap ?? null,
+    });
+  },
+
+  unwatchModules: async (scriptId: number) => {
+    return await invoke<void>("frida_unwatch_modules", { script_id: scriptId });
+  },
+
+  // Drains the timeline unless `drain` is false.
+  moduleTimeline: async (scriptId: number, drain?: boolean) => {
+    return await invoke<ModuleTimeline>("frida_module_timeline", {
+      script_id: scriptId,
+      drain: drain ?? null,
+    });
+  },
+
+  // Detach everything and recreate the DeviceManager; recovers from wedged state without an
+  // app restart. Pinned devices, labels and settings are kept.
+  reset: async (workspaceId?: number) => {
+    return await invoke<ResetSummary>("frida_reset", { workspace_id: workspaceId ?? null });
+  },
+
+  moduleDependencies: async (scriptId: number, module: string) => {
+    return await invoke<ModuleDependency[]>("frida_module_dependencies", { script_id: scriptId, module });
+  },
+
+  // "compact" sends interceptor events as positional arrays to cut IPC on hot hooks.
+  setHookFormat: async (scriptId: number, format: "verbose" | "compact") => {
+    return await invoke<void>("frida_set_hook_format", { script_id: scriptId, format });
+  },
+
+  threadContext: async (scriptId: number, threadId: number) => {
+    return await invoke<ThreadContext>("frida_thread_context", { script_id: scriptId, thread_id: threadId });
+  },
+
+  // Logs every matching export of `module` (onEnter only). Hooking hot libraries wholesale
+  // can stall the target; use `dry_run` to preview and `include` to narrow.
+  hookModule: async (scriptId: number, module: string, options?: ModuleHookOptions) => {
+    return await invoke<ModuleHookResult>("frida_hook_module", {
+      script_id: scriptId,
+      module,
+      options: options ?? null,
+    });
+  },
+
+  unhookModule: async (scriptId: number, module: string) => {
+    return await invoke<{ module: string; detached: number }>("frida_unhook_module", {
+      script_id: scriptId,
+      module,
+    });
+  },
+
+  processCount: async (deviceId: string, workspaceId?: number) => {
+    return await invoke<ProcessCount>("frida_process_count", {
+      device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // 0 disables process-list caching (default 2000).
+  setProcessCacheTtl: async (millis: number, workspaceId?: number) => {
+    return await invoke<void>("frida_set_process_cache_ttl", {
+      millis,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Polls until a process named exactly `matcher` appears, then attaches. Bundle identifiers
+  // don't match on iOS. Fails with a timeout error after `timeoutMs` (max 10 minutes).
+  waitAndAttach: async (deviceId: string, matcher: string, timeoutMs: number, workspaceId?: number) => {
+    return await invoke<SessionInfo>("frida_wait_and_attach", {
+      device_id: deviceId,
+      matcher,
+      timeout_ms: timeoutMs,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  trackAllocations: async (scriptId: number, options?: AllocationTrackOptions) => {
+    return await invoke<{ minSize: number; intervalMs: number; opId: string }>("frida_track_allocations", {
+      script_id: scriptId,
+      options: options ?? null,
+    });
+  },
+
+  stopTrackingAllocations: async (scriptId: number) => {
+    return await invoke<{ stopped: boolean } & Partial<AllocationTotals>>("frida_stop_tracking_allocations", {
+      script_id: scriptId,
+    });
+  },
+
+  allocationSnapshot: async (scriptId: number, limit?: number) => {
+    return await invoke<AllocationSnapshot>("frida_allocation_snapshot", {
+      script_id: scriptId,
+      limit: limit ?? null,
+    });
+  },
+
+  watchPid: async (deviceId: string, pid: number, workspaceId?: number) => {
+    return await invoke<PidWatch>("frida_watch_pid", {
+      device_id: deviceId,
+      pid,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  unwatchPid: async (deviceId: string, pid: number, workspaceId?: number) => {
+    return await invoke<boolean>("frida_unwatch_pid", {
+      device_id: deviceId,
+      pid,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Full debug symbol table of a module in address order, one page at a time.
+  listSymbols: async (scriptId: number, module: string, offset?: number, limit?: number) => {
+    return await invoke<SymbolPage>("frida_list_symbols", {
+      script_id: scriptId,
+      module,
+      offset: offset ?? null,
+      limit: limit ?? null,
+    });
+  },
+
+  // Eval runs arbitrary code inside the target and is refused until enabled.
+  setEvalEnabled: async (enabled: boolean) => {
+    return await invoke<void>("frida_set_eval_enabled", { enabled });
+  },
+
+  eval: async (scriptId: number, expression: string) => {
+    return await invoke<EvalResult>("frida_eval", { script_id: scriptId, expression });
+  },
+
+  listAgentOperations: async (scriptId: number) => {
+    return await invoke<AgentOperation[]>("frida_list_agent_operations", { script_id: scriptId });
+  },
+
+  cancelAgentOperation: async (scriptId: number, opId: string) => {
+    return await invoke<{ opId: string; cancelled: boolean }>("frida_cancel_agent_operation", {
+      script_id: scriptId,
+      op_id: opId,
+    });
+  },
+
+  getOtherMessageRouting: async () => {
+    return await invoke<OtherMessageRouting>("frida_get_other_message_routing");
+  },
+
+  // Omitted markers keep their current setting.
+  setOtherMessageRouting: async (routing: Partial<OtherMessageRouting>) => {
+    return await invoke<OtherMessageRouting>("frida_set_other_message_routing", {
+      rpc_reply: routing.rpc_reply ?? null,
+      ack: routing.ack ?? null,
+      chunk: routing.chunk ?? null,
+    });
+  },
+
+  scriptLoadStats: async (workspaceId?: number) => {
+    return await invoke<ScriptLoadStats>("frida_script_load_stats", { workspace_id: workspaceId ?? null });
+  },
+
+  listWatches: async (scriptId: number) => {
+    return await invoke<WatchInfo[]>("frida_list_watches", { script_id: scriptId });
+  },
+
+  // Returns what was stopped.
+  stopAllWatches: async (scriptId: number, includePidWatches?: boolean) => {
+    return await invoke<WatchInfo[]>("frida_stop_all_watches", {
+      script_id: scriptId,
+      include_pid_watches: includePidWatches ?? null,
+    });
+  },
+
+  // Call an `rpc.exports` function; rejects with the agent's error or after 30s.
+  scriptRpcCall: async (scriptId: number, method: string, args?: unknown[]) => {
+    return await invoke<unknown>("frida_script_rpc_call", {
+      script_id: scriptId,
+      method,
+      args: args ?? null,
+    });
+  },
+
+  scriptListExports: async (scriptId: number) => {
+    return await invoke<string[]>("frida_script_list_exports", {
+      script_id: scriptId,
+    });
+  },
+
+  // Raw system parameters from the device (os, platform, arch, access, name, ...).
+  deviceQuerySystemParameters: async (deviceId: string, workspaceId?: number) => {
+    return await invoke<Record<string, unknown>>("frida_device_query_system_parameters", {
+      device_id: deviceId,
+      workspace_id: workspaceId ?? null,
+    });
+  },
+
+  // Posts pre-serialized JSON as is; `data` (e.g. a protobuf frame) reaches the agent unchanged.
+  scriptPostRaw: async (scriptId: number, jsonText: string, data?: Uint8Array) => {
+    return await invoke<void>("frida_script_post_raw", {
+      script_id: scriptId,
+      json_text: jsonText,
+      data: data ? Array.from(data) : null,
+    });
+  },
+
+  // 0 turns batching off. Batched messages arrive as `frida_script_message_batch`.
+  setMessageBatching: async (scriptId: number, windowMs: number) => {
+    return await invoke<void>("frida_set_message_batching", {
+      script_id: scriptId,
+      window_ms: windowMs,
+    });
+  },
+
+  scriptStats: async (scriptId: number) => {
+    return await invoke<ScriptStats>("frida_script_stats", {
+      script_id: scriptId,
+    });
+  },
 };
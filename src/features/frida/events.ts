@@ -1,6 +1,21 @@
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
-import type { ScriptMessageEvent, SessionAttachedEvent, SessionDetachedEvent } from "./types";
+import type {
+  AgentLoadRetryEvent,
+  AgentReloadedEvent,
+  DeviceInfo,
+  EventsDroppedEvent,
+  HookCallEvent,
+  ModuleEvent,
+  PidExitedEvent,
+  ResetSummary,
+  ScriptMessageBatchEvent,
+  ScriptMessageEvent,
+  ScriptsReloadedEvent,
+  SessionAttachedEvent,
+  SessionDetachedEvent,
+  SpawnAddedEvent,
+} from "./types";
 
 // Typed event listeners around Tauri events.
 export const fridaEvents = {
@@ -27,4 +42,108 @@ export const fridaEvents = {
       handler(event.payload);
     });
   },
+
+  scriptMessageBatch: async (
+    handler: (payload: ScriptMessageBatchEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<ScriptMessageBatchEvent>("frida_script_message_batch", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  agentReloaded: async (
+    handler: (payload: AgentReloadedEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<AgentReloadedEvent>("frida_agent_reloaded", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  agentLoadRetry: async (
+    handler: (payload: AgentLoadRetryEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<AgentLoadRetryEvent>("frida_agent_load_retry", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  moduleLoaded: async (
+    handler: (payload: ModuleEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<ModuleEvent>("frida_module_loaded", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  moduleUnloaded: async (
+    handler: (payload: ModuleEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<ModuleEvent>("frida_module_unloaded", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  resetComplete: async (
+    handler: (payload: ResetSummary) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<ResetSummary>("frida_reset_complete", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onScriptsReloaded: async (
+    handler: (payload: ScriptsReloadedEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<ScriptsReloadedEvent>("frida_scripts_reloaded", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onHookCall: async (
+    handler: (payload: HookCallEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<HookCallEvent>("frida_hook_call", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onPidExited: async (
+    handler: (payload: PidExitedEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<PidExitedEvent>("frida_pid_exited", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onEventsDropped: async (
+    handler: (payload: EventsDroppedEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<EventsDroppedEvent>("frida_events_dropped", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onDeviceAdded: async (
+    handler: (payload: DeviceInfo) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<DeviceInfo>("frida_device_added", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onDeviceRemoved: async (
+    handler: (payload: DeviceInfo) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<DeviceInfo>("frida_device_removed", (event) => {
+      handler(event.payload);
+    });
+  },
+
+  onSpawnAdded: async (
+    handler: (payload: SpawnAddedEvent) => void,
+  ): Promise<UnlistenFn> => {
+    return await listen<SpawnAddedEvent>("frida_spawn_added", (event) => {
+      handler(event.payload);
+    });
+  },
 };
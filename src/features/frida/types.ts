@@ -1,24 +1,529 @@
+export type HostInfo = {
+  os: string;
+  arch: string;
+  build_profile: "debug" | "release";
+  frida_core_version: string;
+};
+
 export type DeviceInfo = {
   id: string;
   name: string;
   device_type: string;
+  name_sanitized: boolean;
+  label?: DeviceLabel;
+};
+
+export type DeviceLabel = {
+  label?: string;
+  color?: string;
+};
+
+export type PinnedDevice = {
+  alias: string;
+  name: string;
+  device_type: string;
 };
 
 export type ProcessInfo = {
   pid: number;
   name: string;
+  name_sanitized: boolean;
 };
 
 export type SessionInfo = {
   session_id: number;
   script_id: number;
+  pid: number;
+  // Present when attached with `freezeOnAttach`
+  frozen?: { suspended: number[]; failed: number[] };
 };
 
 export type ScriptInfo = {
   script_id: number;
+  load_ms: number;
+  already_loaded: boolean; // existing default agent returned, nothing loaded
+};
+
+export type LoadTimeStats = {
+  count: number;
+  total_ms: number;
+  average_ms: number;
+  max_ms: number;
+};
+
+export type ScriptLoadStats = {
+  default_agent: LoadTimeStats;
+  other: LoadTimeStats;
+};
+
+export type ScriptLoadResult = {
+  session_id: number;
+  script_id?: number;
+  error?: string;
+};
+
+export type KillResult = {
+  pid: number;
+  success: boolean;
+  error?: string;
+};
+
+// Unset leaves the choice to frida (QuickJS by default).
+export type JsRuntime = "qjs" | "v8";
+
+export type ScriptLayout = {
+  name: string;
+  source?: string; // omitted for the default agent
+  runtime?: JsRuntime;
+};
+
+export type SessionLayout = {
+  device_id: string;
+  pid: number;
+  process_name?: string;
+  scripts: ScriptLayout[];
+};
+
+export type LayoutImportResult = {
+  device_id: string;
+  pid: number;
+  session_id?: number;
+  script_ids: number[];
+  errors: string[];
+};
+
+export type FdInfo = {
+  fd: number;
+  path: string | null; // null for sockets/pipes without a path
+};
+
+export type ScriptValidation = {
+  valid: boolean;
+  error?: string;
+  line?: number;
+  column?: number;
+};
+
+export type QueuePolicy = "unbounded" | "reject_new" | "drop_oldest";
+
+// A range to limit a pointer search to; `base` is hex or decimal.
+export type SearchRange = {
+  base: string;
+  size: number;
+};
+
+export type PointerSearchResult = {
+  address: string;
+  results: string[]; // locations holding the address, hex
+  count: number;
+  truncated: boolean;
+};
+
+export type InterceptorInfo = {
+  id: string;
+  target: string;
+  address: string;
+  enabled: boolean;
+};
+
+export type MainModuleInfo = {
+  name: string;
+  base: string;
+  size: number;
+  path: string;
+  entrypoint: string | null; // null when the image header can't be parsed
+};
+
+export type AgentGcResult = {
+  runtime: "QJS" | "V8";
+  // Frida heap size in bytes; null where the runtime doesn't report it.
+  heapBefore: number | null;
+  heapAfter: number | null;
+};
+
+export type SymbolicatedAddress = {
+  address: string;
+  name: string | null;
+  module: string | null;
+  offset: string | null; // from the module base, hex
+};
+
+export type IdentifierAttachInfo = {
+  session_id: number;
+  script_id: number;
+  pid: number;
+  spawned: boolean;
+};
+
+// The target stays suspended until `frida_resume`.
+export type SpawnAttachInfo = {
+  pid: number;
+  session_id: number;
+  script_id: number;
+};
+
+// Best-effort snapshot from `frida_attach_and_probe`; failed steps are null and listed in `errors`.
+export type AttachProbe = {
+  session_id: number;
+  script_id: number;
+  pid: number;
+  frida_core_version: string;
+  agent_version: { frida: string; runtime: string } | null;
+  capabilities: Record<string, unknown> | null;
+  main_module: MainModuleInfo | null;
+  arch: string | null;
+  module_count: number | null;
+  errors: string[];
+};
+
+export type ScriptError = {
+  description: string;
+  stack: string;
+  file: string;
+  line: number;
+  column: number;
+};
+
+export type MessageTally = {
+  count: number;
+  bytes: number;
+};
+
+export type MessageStats = {
+  send: MessageTally;
+  log: MessageTally;
+  error: MessageTally;
+  other: MessageTally;
+};
+
+// Byte totals over all mapped ranges.
+export type MemorySummary = {
+  total: number;
+  rx: number;
+  rw: number;
+  ro: number;
+  none: number;
+  private: number; // anonymous mappings
+  shared: number; // file-backed mappings
+  modules: { name: string; size: number }[]; // largest first
+};
+
+export type MethodSearchResult = {
+  results: { class: string; method: string }[];
+  count: number;
+  truncated: boolean;
+};
+
+// Numeric ops compare the argument as an unsigned integer; `contains` reads it as a C string.
+export type HookCondition = {
+  arg_index: number;
+  op: "eq" | "ne" | "gt" | "lt" | "contains";
+  value: number | string;
 };
 
-export type SessionDetachReason = "user" | "disposed";
+// `hash` is FNV-1a 64 (hex) of the UTF-8 source; `source` is absent in hash-only mode or
+// for scripts above 8 MiB.
+export type ScriptSource = {
+  script_id: number;
+  name: string;
+  is_default: boolean;
+  size: number;
+  hash: string;
+  source?: string;
+};
+
+// `module`/`offset` are null for addresses outside any module; `mapped` is false when
+// nothing is mapped there at all.
+export type ModuleOffset = {
+  address: string;
+  module: string | null;
+  path?: string;
+  offset: string | null;
+  mapped: boolean;
+};
+
+// Everything is null when `mapped` is false.
+export type AddressDescription = {
+  address: string;
+  mapped: boolean;
+  range: { base: string; size: number; protection: string; file: string | null } | null;
+  module: { name: string; path: string; base: string; offset: string } | null;
+  symbol: {
+    name: string;
+    address: string;
+    offset: string;
+    fileName: string | null;
+    lineNumber: number | null;
+  } | null;
+  inMainModule: boolean;
+};
+
+// Workspace 0 is the default and always exists.
+export type WorkspaceInfo = {
+  workspace_id: number;
+  label?: string;
+};
+
+export type StructFieldKind =
+  | "s8"
+  | "u8"
+  | "s16"
+  | "u16"
+  | "s32"
+  | "u32"
+  | "s64"
+  | "u64"
+  | "float"
+  | "double"
+  | "pointer"
+  | "utf8";
+
+export type StructField = {
+  name: string;
+  kind: StructFieldKind;
+  offset: number;
+};
+
+// Values are strings (64-bit safe); fields that faulted are in `errors` instead.
+export type StructReadResult = {
+  address: string;
+  values: Record<string, string>;
+  errors: Record<string, string>;
+};
+
+export type ResetSummary = {
+  sessions: number;
+  scripts: number;
+  errors: string[];
+};
+
+// `resolvedModule` is the module containing the bound address; null for unbound lazy/weak imports.
+export type ModuleDependency = {
+  import: string;
+  type: "function" | "variable" | null;
+  declaredModule: string | null;
+  resolvedModule: string | null;
+  address: string | null;
+};
+
+// `registers` uses the architecture's names (rax.. on x64, x0..x28/fp/lr on arm64).
+export type ThreadContext = {
+  threadId: number;
+  arch: "ia32" | "x64" | "arm" | "arm64";
+  state: string;
+  pc: string;
+  sp: string;
+  registers: Record<string, string>;
+};
+
+export type ModuleHookOptions = {
+  include?: string; // regex over export names
+  exclude?: string;
+  limit?: number; // default 500, max 5000
+  dry_run?: boolean;
+};
+
+export type ModuleHookResult = {
+  module: string;
+  exports: { name: string; address: string }[];
+  truncated: boolean;
+  hookIds: string[];
+  failed: { name: string; error: string }[];
+};
+
+// `approximate` counts come from a cached enumeration `age_ms` old, at most the cache TTL.
+export type ProcessCount = {
+  device_id: string;
+  count: number;
+  approximate: boolean;
+  age_ms: number;
+};
+
+export type AllocationTrackOptions = {
+  min_size?: number; // bytes; smaller allocations are not recorded
+  interval_ms?: number; // summary period, default 2000, min 250
+};
+
+export type AllocationSite = {
+  site: string;
+  symbol: string | null;
+  module: string | null;
+  count: number;
+  bytes: number;
+};
+
+export type AllocationTotals = {
+  minSize: number;
+  outstandingCount: number;
+  outstandingBytes: number;
+  totalAllocs: number;
+  totalFrees: number;
+  siteCount: number;
+  elapsedMs: number;
+};
+
+// Payload of the agent's periodic `allocation_summary` event.
+export type AllocationSummary = AllocationTotals & { topSites: AllocationSite[] };
+
+export type AllocationSnapshot = AllocationTotals & {
+  sites: AllocationSite[];
+  allocations: { address: string; size: number; site: string; ageMs: number }[];
+  truncated: boolean;
+};
+
+// `watching: false` means the pid was already gone and `frida_pid_exited` fired immediately.
+export type PidWatch = {
+  device_id: string;
+  pid: number;
+  watching: boolean;
+};
+
+export type VtableHookResult = {
+  id: string;
+  vtable: string;
+  slot: string; // address of the vtable entry
+  function: string;
+  symbol: string | null;
+};
+
+export type ModuleTimeline = {
+  entries: { event: "added" | "removed"; name: string; base: string; path: string; t: number }[];
+  dropped: number; // oldest entries discarded past `cap`
+  cap: number;
+  observing: boolean;
+};
+
+// `available: false` means the module has no symbol table (stripped).
+export type SymbolPage = {
+  module: string;
+  available: boolean;
+  total: number;
+  offset: number;
+  symbols: { type: string; name: string; address: string; size: number | null; isGlobal: boolean }[];
+};
+
+// `type` is the JS `typeof` of the result ("pointer" for NativePointer); `value` is its JSON form.
+export type EvalResult = {
+  type: string;
+  value: unknown;
+};
+
+// A long-running agent operation (memory/value scan, allocation tracking).
+export type AgentOperation = {
+  id: string;
+  kind: string;
+  description: string;
+  startedAt: number;
+  elapsedMs: number;
+  cancelled: boolean;
+};
+
+// `Message::Other` protocol markers the backend consumes instead of forwarding.
+export type OtherMessageRouting = {
+  rpc_reply: boolean;
+  ack: boolean;
+  chunk: boolean;
+};
+
+// kind: "module" | "thread" | "file_tail" | "allocations" (agent) or "pid" (backend pid watch).
+export type WatchInfo = {
+  id: string;
+  kind: string;
+  description: string;
+};
+
+// `env` entries are added to the inherited environment.
+export type SpawnOptions = {
+  env?: [string, string][];
+  cwd?: string;
+  stdio?: "inherit" | "pipe";
+};
+
+// Exactly one of `body` (run as `(args, original) => ...`) and `returnValue`.
+export type FunctionReplacementOptions = {
+  returnType?: string;
+  argTypes?: string[];
+  body?: string;
+  returnValue?: number | string;
+};
+
+export type FunctionReplacement = {
+  id: string;
+  target: string;
+};
+
+// Lifetime counts; `messages_in` includes replies the backend consumes itself.
+export type ScriptStats = {
+  script_id: number;
+  messages_in: number;
+  messages_out: number;
+  loaded_at_ms: number;
+};
+
+export type DeviceCapabilities = {
+  device_id: string;
+  device_type: string;
+  enumerate_processes: boolean;
+  attach: boolean;
+  spawn: boolean;
+  kill: boolean;
+};
+
+export type AgentCapabilities = {
+  arch: string;
+  platform: string;
+  pointerSize: number;
+  runtime: string;
+  objc: boolean;
+  java: boolean;
+  kernel: boolean;
+  stalker: boolean;
+  hardwareBreakpoints: boolean;
+};
+
+export type PingStats = {
+  script_id: number;
+  samples: number;
+  min_ms: number;
+  avg_ms: number;
+  max_ms: number;
+};
+
+export type RangeInfo = {
+  base: string;
+  size: number;
+  protection: string;
+  file: { path: string; offset: number } | null;
+};
+
+export type MemoryMapFormat = "text" | "json";
+
+export type MemoryMapExport = {
+  path: string;
+  format: MemoryMapFormat;
+  count: number;
+};
+
+export type DumpInfo = {
+  path: string;
+  address: string;
+  size: number;
+};
+
+export type DebuggerInfo = {
+  script_id: number;
+  port: number;
+};
+
+export type AgentLogLevel = "error" | "warn" | "info" | "debug";
+
+export type AuditedEvent = {
+  event: string;
+  summary: Record<string, unknown>;
+  timestamp_ms: number;
+};
+
+export type SessionDetachReason = "user" | "disposed" | "idle-timeout" | "device_removed";
 
 export type SessionAttachedEvent = {
   session_id: number;
@@ -36,5 +541,81 @@ export type ScriptMessageEvent = {
   session_id: number;
   script_id: number;
   message: unknown;
-  data?: number[];
+  data?: number[] | null;
+  // Set instead of `data` when the script has a binary channel; matches ScriptBinaryFrame.seq.
+  data_ref?: { seq: number; size: number };
+};
+
+// `frida_set_message_batching`: each entry is what `frida_script_message` would have carried.
+export type ScriptMessageBatchEvent = {
+  session_id: number;
+  script_id: number;
+  messages: ScriptMessageEvent[];
+};
+
+export type ScriptBinaryFrame = {
+  script_id: number;
+  seq: number;
+  data: Uint8Array;
+};
+
+export type AgentReloadedEvent = {
+  session_id: number;
+  old_script_id: number;
+  script_id?: number;
+  error?: string;
 };
+
+export type EventsDroppedEvent = {
+  event: string;
+  max_per_sec: number;
+  dropped: number;
+};
+
+export type PidExitedEvent = {
+  device_id: string;
+  pid: number;
+};
+
+// `identifier` is the process name as enumerated.
+export type SpawnAddedEvent = {
+  device_id: string;
+  pid: number;
+  identifier: string;
+};
+
+export type HookCallEvent = {
+  session_id: number;
+  script_id: number;
+  call: {
+    hookId: string;
+    target: string;
+    threadId: number;
+    args: (string | null)[];
+    captured: { index: number; value: string | null }[];
+    context: { pc: string; sp: string };
+    time: number;
+    retval: string;
+    durationMs: number;
+  };
+};
+
+export type ScriptsReloadedEvent = {
+  session_id: number;
+  script_ids: number[];
+  errors: string[];
+};
+
+export type ModuleEvent = {
+  session_id: number;
+  script_id: number;
+  module: { name: string; base: string; size: number; path: string };
+};
+
+export type AgentLoadRetryEvent = {
+  session_id: number;
+  attempt: number;
+  max_retries: number;
+  error: string;
+};
+